@@ -0,0 +1,58 @@
+use Position;
+
+/// Return the positions of the items of an array of length `N`. This is a
+/// `const fn`, so the positions can be computed at compile time.
+///
+/// ```
+/// use with_position::{positions_of, Position};
+///
+/// const POSITIONS: [Position; 3] = positions_of::<3>();
+///
+/// assert_eq!(POSITIONS, [Position::First, Position::Middle, Position::Last]);
+/// ```
+pub const fn positions_of<const N: usize>() -> [Position; N] {
+    let mut positions = [Position::Middle; N];
+    let mut index = 0;
+    while index < N {
+        positions[index] = Position::from_index(index, N);
+        index += 1;
+    }
+    positions
+}
+
+/// Extension trait for fixed-size arrays which pairs every item with its
+/// position without going through an iterator, so the result is again an
+/// array of the same length.
+pub trait FixedArrayWithPosition<T, const N: usize> {
+    /// Return an array of `(Position, item)` tuples.
+    fn each_with_position(self) -> [(Position, T); N];
+}
+
+impl<T, const N: usize> FixedArrayWithPosition<T, N> for [T; N] {
+    fn each_with_position(self) -> [(Position, T); N] {
+        let positions = const { positions_of::<N>() };
+        let mut index = 0;
+        self.map(|item| {
+            let position = positions[index];
+            index += 1;
+            (position, item)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {positions_of, FixedArrayWithPosition, Position};
+
+    #[test]
+    fn it_computes_positions_of_arrays() {
+        assert_eq!(positions_of::<0>(), []);
+        assert_eq!(positions_of::<1>(), [Position::Only]);
+        assert_eq!(positions_of::<2>(), [Position::First, Position::Last]);
+    }
+
+    #[test]
+    fn it_pairs_array_items_with_positions() {
+        assert_eq!(["a", "b", "c"].each_with_position(), [(Position::First, "a"), (Position::Middle, "b"), (Position::Last, "c")]);
+    }
+}
@@ -0,0 +1,96 @@
+use ndarray::iter::{AxisIter, LanesIter};
+use ndarray::{ArrayBase, Axis, Data, Dimension, RemoveAxis};
+
+use {PositionIterator, WithPosition};
+
+/// Extension trait for ndarray arrays which adds iteration over rows, columns
+/// and lanes with their position along the axis. Every lane is an
+/// `ArrayView1`, so the positions of its elements are available through
+/// `lane.iter().with_position()`.
+///
+/// ```
+/// extern crate ndarray;
+/// # extern crate with_position;
+/// use ndarray::array;
+/// use with_position::{ArrayWithPosition, WithPosition};
+///
+/// # fn main() {
+/// let matrix = array![[1, 2], [3, 4]];
+/// let mut output = String::new();
+/// for (row_position, row) in matrix.rows_with_position() {
+///     for (position, value) in row.iter().with_position() {
+///         output.push_str(&value.to_string());
+///         if !position.is_last() {
+///             output.push(' ');
+///         }
+///     }
+///     if !row_position.is_last() {
+///         output.push('\n');
+///     }
+/// }
+///
+/// assert_eq!(output, "1 2\n3 4");
+/// # }
+/// ```
+pub trait ArrayWithPosition<A, D> where D: Dimension {
+    /// Iterate over the rows, which are the lanes along the last axis,
+    /// yielding a tuple of `(Position, ArrayView1)`.
+    fn rows_with_position(&self) -> PositionIterator<LanesIter<'_, A, D::Smaller>>;
+
+    /// Iterate over the columns, which are the lanes along the first axis,
+    /// yielding a tuple of `(Position, ArrayView1)`.
+    fn columns_with_position(&self) -> PositionIterator<LanesIter<'_, A, D::Smaller>>;
+
+    /// Iterate over the lanes along `axis`, yielding a tuple of
+    /// `(Position, ArrayView1)`.
+    fn lanes_with_position(&self, axis: Axis) -> PositionIterator<LanesIter<'_, A, D::Smaller>>;
+
+    /// Iterate over the subviews along `axis`, yielding a tuple of
+    /// `(Position, ArrayView)` with one dimension less than the array.
+    fn axis_iter_with_position(&self, axis: Axis) -> PositionIterator<AxisIter<'_, A, D::Smaller>> where D: RemoveAxis;
+}
+
+impl<A, S, D> ArrayWithPosition<A, D> for ArrayBase<S, D> where S: Data<Elem = A>, D: Dimension {
+    fn rows_with_position(&self) -> PositionIterator<LanesIter<'_, A, D::Smaller>> {
+        self.rows().into_iter().with_position()
+    }
+
+    fn columns_with_position(&self) -> PositionIterator<LanesIter<'_, A, D::Smaller>> {
+        self.columns().into_iter().with_position()
+    }
+
+    fn lanes_with_position(&self, axis: Axis) -> PositionIterator<LanesIter<'_, A, D::Smaller>> {
+        self.lanes(axis).into_iter().with_position()
+    }
+
+    fn axis_iter_with_position(&self, axis: Axis) -> PositionIterator<AxisIter<'_, A, D::Smaller>> where D: RemoveAxis {
+        self.axis_iter(axis).with_position()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{Array2, Array3, Axis};
+    use {ArrayWithPosition, Position};
+
+    #[test]
+    fn it_marks_positions_of_rows_and_columns() {
+        let matrix = Array2::from_shape_vec((2, 3), vec![1, 2, 3, 4, 5, 6]).unwrap();
+
+        let rows: Vec<_> = matrix.rows_with_position().map(|(position, row)| (position, row.sum())).collect();
+        let columns: Vec<_> = matrix.columns_with_position().map(|(position, column)| (position, column.sum())).collect();
+
+        assert_eq!(rows, vec![(Position::First, 6), (Position::Last, 15)]);
+        assert_eq!(columns, vec![(Position::First, 5), (Position::Middle, 7), (Position::Last, 9)]);
+    }
+
+    #[test]
+    fn it_marks_positions_along_an_axis() {
+        let cube = Array3::<u8>::zeros((1, 2, 3));
+
+        let positions: Vec<_> = cube.axis_iter_with_position(Axis(0)).map(|(position, view)| (position, view.shape().to_vec())).collect();
+
+        assert_eq!(positions, vec![(Position::Only, vec![2, 3])]);
+        assert_eq!(cube.lanes_with_position(Axis(1)).count(), 3);
+    }
+}
@@ -0,0 +1,99 @@
+use std::iter::Peekable;
+use std::time::{Duration, Instant};
+
+use Position;
+
+/// An iterator adapter that groups items into batches, created by
+/// `WithPosition::batcher`. A batch is closed once it has reached the
+/// maximum size, or once the maximum age has passed since its first item was
+/// read, and is yielded together with its position among the batches. The
+/// final batch is always yielded when the source ends, even if it is partial,
+/// and is labelled `Last`. By default there are no limits, so everything ends
+/// up in a single batch.
+///
+/// The age is only checked as items are read, so a slow source can still
+/// make a batch older than the maximum age.
+///
+/// ```
+/// use with_position::{Position, WithPosition};
+///
+/// let batches: Vec<_> = (1..6).batcher().max_size(2).collect();
+///
+/// assert_eq!(batches, vec![
+///     (Position::First, vec![1, 2]),
+///     (Position::Middle, vec![3, 4]),
+///     (Position::Last, vec![5]),
+/// ]);
+/// ```
+pub struct Batcher<I> where I: Iterator {
+    iter: Peekable<I>,
+    max_size: Option<usize>,
+    max_age: Option<Duration>,
+    did_iter: bool,
+}
+
+impl<I> Batcher<I> where I: Iterator {
+    pub(crate) fn new(iter: I) -> Batcher<I> {
+        Batcher { iter: iter.peekable(), max_size: None, max_age: None, did_iter: false }
+    }
+
+    /// Close a batch once it has `max_size` items.
+    ///
+    /// Panics if `max_size` is zero.
+    pub fn max_size(mut self, max_size: usize) -> Batcher<I> {
+        assert!(max_size != 0, "batch size must be non-zero");
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Close a batch once `max_age` has passed since its first item was read.
+    pub fn max_age(mut self, max_age: Duration) -> Batcher<I> {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    fn is_full(&self, batch: &[I::Item], opened: Instant) -> bool {
+        self.max_size.is_some_and(|max_size| batch.len() >= max_size) || self.max_age.is_some_and(|max_age| opened.elapsed() >= max_age)
+    }
+}
+
+impl<I> Iterator for Batcher<I> where I: Iterator {
+    type Item = (Position, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.iter.next()?;
+        let opened = Instant::now();
+        let mut batch = vec![first];
+        while !self.is_full(&batch, opened) {
+            match self.iter.next() {
+                Some(item) => batch.push(item),
+                None => break,
+            }
+        }
+
+        let is_first = !self.did_iter;
+        self.did_iter = true;
+        Some((Position::from_flags(is_first, self.iter.peek().is_none()), batch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use {Position, WithPosition};
+
+    #[test]
+    fn it_closes_batches_once_they_are_too_old() {
+        let batches: Vec<_> = vec!["a", "b", "c"].into_iter().batcher().max_size(5).max_age(Duration::from_secs(0)).collect();
+
+        assert_eq!(batches, vec![(Position::First, vec!["a"]), (Position::Middle, vec!["b"]), (Position::Last, vec!["c"])]);
+    }
+
+    #[test]
+    fn it_yields_everything_in_one_batch_without_limits() {
+        let batches: Vec<_> = (1..4).batcher().collect();
+
+        assert_eq!(batches, vec![(Position::Only, vec![1, 2, 3])]);
+        assert_eq!((1..1).batcher().next(), None);
+    }
+}
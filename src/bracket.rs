@@ -0,0 +1,145 @@
+use std::iter::{Fuse, FusedIterator, Peekable};
+
+/// An iterator adapter that yields the items of the source followed by a
+/// terminator, but only if the source yielded any items, created by
+/// `WithPosition::terminate_with`.
+pub struct TerminateWith<I> where I: Iterator {
+    iter: Fuse<I>,
+    terminator: Option<I::Item>,
+    did_iter: bool,
+}
+
+impl<I> TerminateWith<I> where I: Iterator {
+    pub(crate) fn new(iter: I, terminator: I::Item) -> TerminateWith<I> {
+        TerminateWith { iter: iter.fuse(), terminator: Some(terminator), did_iter: false }
+    }
+}
+
+impl<I> Iterator for TerminateWith<I> where I: Iterator {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        match self.iter.next() {
+            Some(item) => {
+                self.did_iter = true;
+                Some(item)
+            }
+            None if self.did_iter => self.terminator.take(),
+            None => {
+                self.terminator = None;
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let terminator = if self.terminator.is_some() { 1 } else { 0 };
+        let lower = if self.did_iter || lower > 0 { lower.saturating_add(terminator) } else { lower };
+        (lower, upper.and_then(|upper| upper.checked_add(terminator)))
+    }
+}
+
+impl<I> FusedIterator for TerminateWith<I> where I: Iterator {}
+
+/// An iterator adapter that yields a prefix followed by the items of the
+/// source, but only if the source yields any items, created by
+/// `WithPosition::prefix_with`.
+pub struct PrefixWith<I> where I: Iterator {
+    iter: Peekable<Fuse<I>>,
+    prefix: Option<I::Item>,
+}
+
+impl<I> PrefixWith<I> where I: Iterator {
+    pub(crate) fn new(iter: I, prefix: I::Item) -> PrefixWith<I> {
+        PrefixWith { iter: iter.fuse().peekable(), prefix: Some(prefix) }
+    }
+}
+
+impl<I> Iterator for PrefixWith<I> where I: Iterator {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if let Some(prefix) = self.prefix.take() {
+            if self.iter.peek().is_some() {
+                return Some(prefix);
+            }
+        }
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let prefix = if self.prefix.is_some() { 1 } else { 0 };
+        let lower = if lower > 0 { lower.saturating_add(prefix) } else { lower };
+        (lower, upper.and_then(|upper| upper.checked_add(prefix)))
+    }
+}
+
+impl<I> FusedIterator for PrefixWith<I> where I: Iterator {}
+
+/// An iterator adapter that yields the items of the source between an opening
+/// and a closing item, but nothing at all if the source yields no items,
+/// created by `WithPosition::surround`.
+pub struct Surround<I> where I: Iterator {
+    iter: TerminateWith<PrefixWith<I>>,
+}
+
+impl<I> Surround<I> where I: Iterator {
+    pub(crate) fn new(iter: I, open: I::Item, close: I::Item) -> Surround<I> {
+        Surround { iter: TerminateWith::new(PrefixWith::new(iter, open), close) }
+    }
+}
+
+impl<I> Iterator for Surround<I> where I: Iterator {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> FusedIterator for Surround<I> where I: Iterator {}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+    use WithPosition;
+
+    #[test]
+    fn it_terminates_a_non_empty_iteration() {
+        let result: Vec<_> = vec!["a", "b"].into_iter().terminate_with("end").collect();
+
+        assert_eq!(result, vec!["a", "b", "end"]);
+    }
+
+    #[test]
+    fn it_yields_nothing_for_an_empty_iteration() {
+        let mut iter = iter::empty::<&str>().terminate_with("end");
+
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn it_prefixes_a_non_empty_iteration() {
+        let rows = vec!["1,2", "3,4"];
+
+        let result: Vec<_> = rows.into_iter().prefix_with("a,b").terminate_with("").collect();
+        assert_eq!(result, vec!["a,b", "1,2", "3,4", ""]);
+
+        assert_eq!(iter::empty::<&str>().prefix_with("a,b").terminate_with("").next(), None);
+    }
+
+    #[test]
+    fn it_surrounds_a_non_empty_iteration() {
+        let result: String = vec!["a", "b"].into_iter().surround("[", "]").collect();
+        assert_eq!(result, "[ab]");
+
+        assert_eq!(iter::empty::<&str>().surround("[", "]").collect::<String>(), "");
+    }
+}
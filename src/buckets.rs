@@ -0,0 +1,104 @@
+use std::iter::{Fuse, FusedIterator};
+
+use Position;
+
+/// Labels items with their position within runs of items with equal keys,
+/// reading one item ahead to find the end of a run.
+pub(crate) struct Runs<I, K> where I: Iterator {
+    iter: Fuse<I>,
+    next: Option<(K, I::Item)>,
+    starts_run: bool,
+}
+
+impl<I, K> Runs<I, K> where I: Iterator, K: PartialEq {
+    pub(crate) fn new(iter: I) -> Runs<I, K> {
+        Runs { iter: iter.fuse(), next: None, starts_run: true }
+    }
+
+    /// Yield the next item with its key and its position within its run,
+    /// where `key` computes the key of an item.
+    pub(crate) fn next_with<F>(&mut self, mut key: F) -> Option<(K, Position, I::Item)> where F: FnMut(&I::Item) -> K {
+        let (item_key, item) = match self.next.take() {
+            Some(next) => next,
+            None => {
+                let item = self.iter.next()?;
+                (key(&item), item)
+            }
+        };
+        self.next = self.iter.next().map(|next| (key(&next), next));
+
+        let is_first = self.starts_run;
+        let is_last = match self.next {
+            Some((ref next_key, _)) => *next_key != item_key,
+            None => true,
+        };
+        self.starts_run = is_last;
+        Some((item_key, Position::from_flags(is_first, is_last), item))
+    }
+
+    pub(crate) fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = if self.next.is_some() { 1 } else { 0 };
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_add(pending), upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+/// An iterator adapter that labels items with the index of their histogram
+/// bucket and their position within the bucket, created by
+/// `WithPosition::with_bucket_position`.
+pub struct BucketPositions<I, K, F> where I: Iterator {
+    runs: Runs<I, usize>,
+    edges: Vec<K>,
+    key: F,
+}
+
+impl<I, K, F> BucketPositions<I, K, F> where I: Iterator, K: PartialOrd, F: FnMut(&I::Item) -> K {
+    pub(crate) fn new(iter: I, edges: Vec<K>, key: F) -> BucketPositions<I, K, F> {
+        BucketPositions { runs: Runs::new(iter), edges, key }
+    }
+}
+
+impl<I, K, F> Iterator for BucketPositions<I, K, F> where I: Iterator, K: PartialOrd, F: FnMut(&I::Item) -> K {
+    type Item = (usize, Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let edges = &self.edges;
+        let key = &mut self.key;
+        self.runs.next_with(|item| {
+            let key = key(item);
+            edges.partition_point(|edge| *edge <= key)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.runs.size_hint()
+    }
+}
+
+impl<I, K, F> FusedIterator for BucketPositions<I, K, F> where I: Iterator, K: PartialOrd, F: FnMut(&I::Item) -> K {}
+
+#[cfg(test)]
+mod tests {
+    use {Position, WithPosition};
+
+    #[test]
+    fn it_labels_items_with_buckets_and_positions_within_them() {
+        let result: Vec<_> = vec![1, 4, 5, 7, 12].into_iter().with_bucket_position(vec![5, 10], |&n| n).collect();
+
+        assert_eq!(result, vec![
+            (0, Position::First, 1),
+            (0, Position::Last, 4),
+            (1, Position::First, 5),
+            (1, Position::Last, 7),
+            (2, Position::Only, 12),
+        ]);
+    }
+
+    #[test]
+    fn it_handles_no_edges_and_no_items() {
+        let result: Vec<_> = vec![2.5, 0.5].into_iter().with_bucket_position(Vec::new(), |&n| n).collect();
+
+        assert_eq!(result, vec![(0, Position::First, 2.5), (0, Position::Last, 0.5)]);
+        assert_eq!(Vec::<u8>::new().into_iter().with_bucket_position(vec![1], |&n| n).next(), None);
+    }
+}
@@ -0,0 +1,112 @@
+use std::iter::FusedIterator;
+
+use bytes::{Buf, Bytes};
+
+use Position;
+
+/// Extension trait for `bytes::Buf` which adds iteration over its chunks with
+/// their position, so that an encoder can set an end-of-message flag on the
+/// final chunk of a chained buffer. A chunk is the last one when it holds all
+/// of the remaining bytes, so nothing is read ahead.
+pub trait BufWithPosition: Buf + Sized {
+    /// Consume the buffer, yielding a tuple of `(Position, Bytes)` for every
+    /// chunk. Chunks of a `Bytes` are yielded without copying.
+    fn chunks_with_position(self) -> BufChunks<Self>;
+
+    /// Call `f` with every chunk and its position, advancing the buffer past
+    /// the chunk afterwards.
+    ///
+    /// ```
+    /// extern crate bytes;
+    /// # extern crate with_position;
+    /// use bytes::Buf;
+    /// use with_position::{BufWithPosition, Position};
+    ///
+    /// # fn main() {
+    /// let mut buf = (&b"hello "[..]).chain(&b"world"[..]);
+    /// let mut frames = Vec::new();
+    /// buf.for_each_chunk_with_position(|position, chunk| frames.push((position.is_last(), chunk.to_vec())));
+    ///
+    /// assert_eq!(frames, vec![(false, b"hello ".to_vec()), (true, b"world".to_vec())]);
+    /// # }
+    /// ```
+    fn for_each_chunk_with_position<F>(&mut self, f: F) where F: FnMut(Position, &[u8]);
+}
+
+impl<B> BufWithPosition for B where B: Buf {
+    fn chunks_with_position(self) -> BufChunks<B> {
+        BufChunks { buf: self, did_iter: false }
+    }
+
+    fn for_each_chunk_with_position<F>(&mut self, mut f: F) where F: FnMut(Position, &[u8]) {
+        let mut is_first = true;
+        while self.has_remaining() {
+            let len = {
+                let chunk = self.chunk();
+                f(Position::from_flags(is_first, chunk.len() == self.remaining()), chunk);
+                chunk.len()
+            };
+            self.advance(len);
+            is_first = false;
+        }
+    }
+}
+
+/// An iterator over the chunks of a buffer with their position, created by
+/// `BufWithPosition::chunks_with_position`.
+pub struct BufChunks<B> {
+    buf: B,
+    did_iter: bool,
+}
+
+impl<B> BufChunks<B> {
+    /// Return the rest of the buffer.
+    pub fn into_inner(self) -> B {
+        self.buf
+    }
+}
+
+impl<B> Iterator for BufChunks<B> where B: Buf {
+    type Item = (Position, Bytes);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.buf.has_remaining() {
+            return None;
+        }
+        let len = self.buf.chunk().len();
+        let is_last = len == self.buf.remaining();
+        let is_first = !self.did_iter;
+        self.did_iter = true;
+        Some((Position::from_flags(is_first, is_last), self.buf.copy_to_bytes(len)))
+    }
+}
+
+impl<B> FusedIterator for BufChunks<B> where B: Buf {}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{Buf, Bytes};
+    use {BufWithPosition, Position};
+
+    #[test]
+    fn it_marks_positions_of_chained_chunks() {
+        let rope = Bytes::from_static(b"ab").chain(Bytes::from_static(b"cd")).chain(Bytes::from_static(b"e"));
+
+        let chunks: Vec<_> = rope.chunks_with_position().collect();
+
+        assert_eq!(chunks, vec![
+            (Position::First, Bytes::from_static(b"ab")),
+            (Position::Middle, Bytes::from_static(b"cd")),
+            (Position::Last, Bytes::from_static(b"e")),
+        ]);
+    }
+
+    #[test]
+    fn it_handles_single_and_empty_buffers() {
+        let mut positions = Vec::new();
+        Bytes::from_static(b"abc").for_each_chunk_with_position(|position, _| positions.push(position));
+
+        assert_eq!(positions, vec![Position::Only]);
+        assert_eq!(Bytes::new().chunks_with_position().next(), None);
+    }
+}
@@ -0,0 +1,60 @@
+use std::os::raw::c_void;
+
+use WithPosition;
+
+/// A C callback for `drive_positioned`, which receives the position as a
+/// `u8`, see `Position`, a pointer to the item and the context pointer. The
+/// item pointer is only valid for the duration of the call.
+pub type PositionedCallback<T> = extern "C" fn(position: u8, item: *const T, ctx: *mut c_void);
+
+/// Call the C callback with each item of the iterator, together with its
+/// position and the context pointer, and return the number of items. This is
+/// meant for embedding iteration in a C host, which can compare the position
+/// against the discriminants of `Position`.
+///
+/// ```
+/// use std::os::raw::c_void;
+/// use with_position::{drive_positioned, Position};
+///
+/// extern "C" fn collect(position: u8, item: *const i32, ctx: *mut c_void) {
+///     let output = unsafe { &mut *(ctx as *mut Vec<(u8, i32)>) };
+///     output.push((position, unsafe { *item }));
+/// }
+///
+/// let mut output: Vec<(u8, i32)> = Vec::new();
+/// let count = drive_positioned(vec![1, 2], &mut output as *mut _ as *mut c_void, collect);
+///
+/// assert_eq!(count, 2);
+/// assert_eq!(output, vec![(Position::First as u8, 1), (Position::Last as u8, 2)]);
+/// ```
+pub fn drive_positioned<I>(iter: I, ctx: *mut c_void, callback: PositionedCallback<I::Item>) -> usize where I: IntoIterator {
+    let mut count = 0;
+    for (position, item) in iter.into_iter().with_position() {
+        callback(position as u8, &item, ctx);
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::raw::c_void;
+    use std::ptr;
+    use {drive_positioned, Position};
+
+    extern "C" fn count_lasts(position: u8, _: *const &str, ctx: *mut c_void) {
+        if position == Position::Last as u8 || position == Position::Only as u8 {
+            unsafe { *(ctx as *mut u32) += 1 };
+        }
+    }
+
+    #[test]
+    fn it_passes_positions_as_u8_discriminants() {
+        assert_eq!([Position::First as u8, Position::Middle as u8, Position::Last as u8, Position::Only as u8], [0, 1, 2, 3]);
+
+        let mut lasts = 0u32;
+        assert_eq!(drive_positioned(vec!["a", "b", "c"], &mut lasts as *mut u32 as *mut c_void, count_lasts), 3);
+        assert_eq!(lasts, 1);
+        assert_eq!(drive_positioned(Vec::<&str>::new(), ptr::null_mut(), count_lasts), 0);
+    }
+}
@@ -0,0 +1,194 @@
+use std::sync::mpsc::{Receiver, TryRecvError};
+
+use Position;
+
+/// The result of trying to receive a message without blocking, shared by the
+/// receivers of the different channel implementations.
+pub(crate) enum TryRecv<T> {
+    Message(T),
+    Empty,
+    Disconnected,
+}
+
+/// A channel receiver which `LookAhead` can receive messages from.
+pub(crate) trait Recv {
+    type Message;
+
+    /// Block until a message arrives, returning `None` once the channel is
+    /// disconnected.
+    fn recv_message(&self) -> Option<Self::Message>;
+
+    /// Receive a message if one is ready, without blocking.
+    fn try_recv_message(&self) -> TryRecv<Self::Message>;
+}
+
+impl<T> Recv for Receiver<T> {
+    type Message = T;
+
+    fn recv_message(&self) -> Option<T> {
+        self.recv().ok()
+    }
+
+    fn try_recv_message(&self) -> TryRecv<T> {
+        match self.try_recv() {
+            Ok(message) => TryRecv::Message(message),
+            Err(TryRecvError::Empty) => TryRecv::Empty,
+            Err(TryRecvError::Disconnected) => TryRecv::Disconnected,
+        }
+    }
+}
+
+/// Labels the messages of a receiver with their position, holding one message
+/// back until the next one arrives or the channel disconnects.
+pub(crate) struct LookAhead<R> where R: Recv {
+    pub(crate) receiver: R,
+    pending: Option<R::Message>,
+    did_iter: bool,
+    done: bool,
+}
+
+impl<R> LookAhead<R> where R: Recv {
+    pub(crate) fn new(receiver: R) -> LookAhead<R> {
+        LookAhead { receiver, pending: None, did_iter: false, done: false }
+    }
+
+    pub(crate) fn recv(&mut self) -> Option<(Position, R::Message)> {
+        if self.done {
+            return None;
+        }
+
+        let current = match self.pending.take() {
+            Some(current) => current,
+            None => match self.receiver.recv_message() {
+                Some(current) => current,
+                None => {
+                    self.done = true;
+                    return None;
+                }
+            },
+        };
+
+        match self.receiver.recv_message() {
+            Some(next) => {
+                self.pending = Some(next);
+                Some(self.label(current, false))
+            }
+            None => {
+                self.done = true;
+                Some(self.label(current, true))
+            }
+        }
+    }
+
+    pub(crate) fn try_recv(&mut self) -> TryRecv<(Position, R::Message)> {
+        if self.done {
+            return TryRecv::Disconnected;
+        }
+
+        let current = match self.pending.take() {
+            Some(current) => current,
+            None => match self.receiver.try_recv_message() {
+                TryRecv::Message(current) => current,
+                TryRecv::Disconnected => {
+                    self.done = true;
+                    return TryRecv::Disconnected;
+                }
+                TryRecv::Empty => return TryRecv::Empty,
+            },
+        };
+
+        match self.receiver.try_recv_message() {
+            TryRecv::Message(next) => {
+                self.pending = Some(next);
+                TryRecv::Message(self.label(current, false))
+            }
+            TryRecv::Disconnected => {
+                self.done = true;
+                TryRecv::Message(self.label(current, true))
+            }
+            TryRecv::Empty => {
+                self.pending = Some(current);
+                TryRecv::Empty
+            }
+        }
+    }
+
+    fn label(&mut self, message: R::Message, is_last: bool) -> (Position, R::Message) {
+        let is_first = !self.did_iter;
+        self.did_iter = true;
+        (Position::from_flags(is_first, is_last), message)
+    }
+}
+
+/// A wrapper around a channel receiver which yields tuples of
+/// `(Position, message)`. One message is held back until the next one arrives,
+/// so the last message is only yielded once all senders have disconnected.
+pub struct PositionedReceiver<T> {
+    inner: LookAhead<Receiver<T>>,
+}
+
+impl<T> PositionedReceiver<T> {
+    pub fn new(receiver: Receiver<T>) -> PositionedReceiver<T> {
+        PositionedReceiver { inner: LookAhead::new(receiver) }
+    }
+
+    /// Block until the next positioned message can be yielded, returning `None`
+    /// once the channel is disconnected and all messages have been yielded.
+    pub fn recv(&mut self) -> Option<(Position, T)> {
+        self.inner.recv()
+    }
+
+    /// Try to yield the next positioned message without blocking. Since the
+    /// position of a message is only known once the next message has arrived
+    /// or the channel has disconnected, this returns `TryRecvError::Empty` while
+    /// a message is held back waiting for either of those to happen.
+    pub fn try_recv(&mut self) -> Result<(Position, T), TryRecvError> {
+        match self.inner.try_recv() {
+            TryRecv::Message(message) => Ok(message),
+            TryRecv::Empty => Err(TryRecvError::Empty),
+            TryRecv::Disconnected => Err(TryRecvError::Disconnected),
+        }
+    }
+}
+
+impl<T> Iterator for PositionedReceiver<T> {
+    type Item = (Position, T);
+
+    fn next(&mut self) -> Option<(Position, T)> {
+        self.recv()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::{channel, TryRecvError};
+    use std::thread;
+    use {Position, PositionedReceiver};
+
+    #[test]
+    fn it_marks_positions_of_received_messages() {
+        let (sender, receiver) = channel();
+        thread::spawn(move || {
+            for message in 1..4 {
+                sender.send(message).unwrap();
+            }
+        });
+
+        let result: Vec<_> = PositionedReceiver::new(receiver).collect();
+
+        assert_eq!(result, vec![(Position::First, 1), (Position::Middle, 2), (Position::Last, 3)]);
+    }
+
+    #[test]
+    fn it_holds_back_a_message_until_its_position_is_known() {
+        let (sender, receiver) = channel();
+        let mut receiver = PositionedReceiver::new(receiver);
+
+        sender.send(1).unwrap();
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+
+        drop(sender);
+        assert_eq!(receiver.try_recv(), Ok((Position::Only, 1)));
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Disconnected));
+    }
+}
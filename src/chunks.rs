@@ -0,0 +1,277 @@
+use std::fmt;
+use std::iter::{Fuse, FusedIterator};
+use std::ops::Deref;
+
+use inline::InlineVec;
+use Position;
+
+/// A chunk of items yielded by `chunks_exact_with_position` and
+/// `rchunks_with_position`, which dereferences to a slice of its items. With
+/// the `smallvec` feature, chunks of up to four items are not allocated on the
+/// heap.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Chunk<T> {
+    items: InlineVec<T>,
+}
+
+impl<T> Chunk<T> {
+    /// Return the items of the chunk as a vector.
+    pub fn into_vec(self) -> Vec<T> {
+        self.items.into_iter().collect()
+    }
+}
+
+impl<T> Deref for Chunk<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<T> fmt::Debug for Chunk<T> where T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.items[..], f)
+    }
+}
+
+/// An iterator adapter that yields full chunks of `n` items, labelled with
+/// their position among the full chunks, created by
+/// `WithPosition::chunks_exact_with_position`. Like `slice::chunks_exact`, the
+/// items which do not fill a whole chunk at the end are not yielded, but can
+/// be retrieved with `into_remainder`. One chunk is read ahead.
+///
+/// ```
+/// use with_position::{Position, WithPosition};
+///
+/// let mut chunks = (1..=7).chunks_exact_with_position(3);
+///
+/// let (position, chunk) = chunks.next().unwrap();
+/// assert_eq!((position, &chunk[..]), (Position::First, &[1, 2, 3][..]));
+/// let (position, chunk) = chunks.next().unwrap();
+/// assert_eq!((position, &chunk[..]), (Position::Last, &[4, 5, 6][..]));
+/// assert!(chunks.next().is_none());
+/// assert_eq!(chunks.into_remainder(), vec![7]);
+/// ```
+pub struct ChunksExactWithPosition<I> where I: Iterator {
+    iter: Fuse<I>,
+    n: usize,
+    next: Option<Chunk<I::Item>>,
+    remainder: Vec<I::Item>,
+    started: bool,
+    did_iter: bool,
+}
+
+impl<I> ChunksExactWithPosition<I> where I: Iterator {
+    pub(crate) fn new(iter: I, n: usize) -> ChunksExactWithPosition<I> {
+        assert!(n != 0, "chunk size must be non-zero");
+        ChunksExactWithPosition { iter: iter.fuse(), n, next: None, remainder: Vec::new(), started: false, did_iter: false }
+    }
+
+    /// Return the items at the end which do not fill a whole chunk, skipping
+    /// the remaining full chunks if the iterator has not been exhausted yet.
+    pub fn into_remainder(mut self) -> Vec<I::Item> {
+        while self.read_chunk().is_some() {}
+        self.remainder
+    }
+
+    fn read_chunk(&mut self) -> Option<Chunk<I::Item>> {
+        let items: InlineVec<_> = self.iter.by_ref().take(self.n).collect();
+        if items.len() == self.n {
+            Some(Chunk { items })
+        } else {
+            if !items.is_empty() {
+                self.remainder = items.into_iter().collect();
+            }
+            None
+        }
+    }
+}
+
+impl<I> Iterator for ChunksExactWithPosition<I> where I: Iterator {
+    type Item = (Position, Chunk<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            self.next = self.read_chunk();
+        }
+        let chunk = self.next.take()?;
+        self.next = self.read_chunk();
+
+        let is_first = !self.did_iter;
+        self.did_iter = true;
+        Some((Position::from_flags(is_first, self.next.is_none()), chunk))
+    }
+}
+
+impl<I> FusedIterator for ChunksExactWithPosition<I> where I: Iterator {}
+
+/// An iterator adapter that yields full chunks of `N` items as arrays,
+/// labelled with their position among the full chunks, created by
+/// `WithPosition::array_chunks_with_position`. This is like
+/// `ChunksExactWithPosition`, but the chunks are not allocated on the heap.
+///
+/// ```
+/// use with_position::{Position, WithPosition};
+///
+/// let mut records = vec![1u8, 2, 3, 4, 5].into_iter().array_chunks_with_position::<2>();
+///
+/// assert_eq!(records.next(), Some((Position::First, [1, 2])));
+/// assert_eq!(records.next(), Some((Position::Last, [3, 4])));
+/// assert_eq!(records.next(), None);
+/// assert_eq!(records.into_remainder(), vec![5]);
+/// ```
+pub struct ArrayChunksWithPosition<I, const N: usize> where I: Iterator {
+    iter: Fuse<I>,
+    next: Option<[I::Item; N]>,
+    remainder: Vec<I::Item>,
+    started: bool,
+    did_iter: bool,
+}
+
+impl<I, const N: usize> ArrayChunksWithPosition<I, N> where I: Iterator {
+    pub(crate) fn new(iter: I) -> ArrayChunksWithPosition<I, N> {
+        assert!(N != 0, "chunk size must be non-zero");
+        ArrayChunksWithPosition { iter: iter.fuse(), next: None, remainder: Vec::new(), started: false, did_iter: false }
+    }
+
+    /// Return the items at the end which do not fill a whole chunk, skipping
+    /// the remaining full chunks if the iterator has not been exhausted yet.
+    pub fn into_remainder(mut self) -> Vec<I::Item> {
+        while self.read_chunk().is_some() {}
+        self.remainder
+    }
+
+    fn read_chunk(&mut self) -> Option<[I::Item; N]> {
+        let items: [Option<I::Item>; N] = std::array::from_fn(|_| self.iter.next());
+        // The source is fused, so the chunk is full if its last item is there.
+        if items[N - 1].is_some() {
+            Some(items.map(Option::unwrap))
+        } else {
+            let remainder: Vec<_> = IntoIterator::into_iter(items).flatten().collect();
+            if !remainder.is_empty() {
+                self.remainder = remainder;
+            }
+            None
+        }
+    }
+}
+
+impl<I, const N: usize> Iterator for ArrayChunksWithPosition<I, N> where I: Iterator {
+    type Item = (Position, [I::Item; N]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            self.next = self.read_chunk();
+        }
+        let chunk = self.next.take()?;
+        self.next = self.read_chunk();
+
+        let is_first = !self.did_iter;
+        self.did_iter = true;
+        Some((Position::from_flags(is_first, self.next.is_none()), chunk))
+    }
+}
+
+impl<I, const N: usize> FusedIterator for ArrayChunksWithPosition<I, N> where I: Iterator {}
+
+/// An iterator adapter that yields chunks of `n` items starting from the end
+/// of the source, like `slice::rchunks`, created by
+/// `WithPosition::rchunks_with_position`. The items within each chunk keep
+/// their order, and the chunk at the front of the source is the short one.
+/// Positions are relative to the order in which the chunks are yielded, and
+/// are computed from the length of the source rather than by reading ahead.
+///
+/// ```
+/// use with_position::{Position, WithPosition};
+///
+/// let pages: Vec<_> = (1..6).rchunks_with_position(2).map(|(position, page)| (position, page.into_vec())).collect();
+///
+/// assert_eq!(pages, vec![(Position::First, vec![4, 5]), (Position::Middle, vec![2, 3]), (Position::Last, vec![1])]);
+/// ```
+pub struct RChunksWithPosition<I> {
+    iter: I,
+    n: usize,
+    len: usize,
+    index: usize,
+}
+
+impl<I> RChunksWithPosition<I> where I: DoubleEndedIterator + ExactSizeIterator {
+    pub(crate) fn new(iter: I, n: usize) -> RChunksWithPosition<I> {
+        assert!(n != 0, "chunk size must be non-zero");
+        let len = iter.len().div_ceil(n);
+        RChunksWithPosition { iter, n, len, index: 0 }
+    }
+}
+
+impl<I> Iterator for RChunksWithPosition<I> where I: DoubleEndedIterator + ExactSizeIterator {
+    type Item = (Position, Chunk<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut items: InlineVec<_> = self.iter.by_ref().rev().take(self.n).collect();
+        if items.is_empty() {
+            return None;
+        }
+        items.reverse();
+
+        let position = Position::from_index(self.index, self.len);
+        self.index += 1;
+        Some((position, Chunk { items }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<I> ExactSizeIterator for RChunksWithPosition<I> where I: DoubleEndedIterator + ExactSizeIterator {}
+
+impl<I> FusedIterator for RChunksWithPosition<I> where I: DoubleEndedIterator + ExactSizeIterator + FusedIterator {}
+
+#[cfg(test)]
+mod tests {
+    use {Chunk, Position, WithPosition};
+
+    fn to_vecs<I, T>(chunks: I) -> Vec<(Position, Vec<T>)> where I: Iterator<Item = (Position, Chunk<T>)> {
+        chunks.map(|(position, chunk)| (position, chunk.into_vec())).collect()
+    }
+
+    #[test]
+    fn it_yields_only_full_chunks() {
+        let mut chunks = vec!["a", "b", "c", "d"].into_iter().chunks_exact_with_position(2);
+
+        assert_eq!(to_vecs(chunks.by_ref()), vec![(Position::First, vec!["a", "b"]), (Position::Last, vec!["c", "d"])]);
+        assert!(chunks.into_remainder().is_empty());
+    }
+
+    #[test]
+    fn it_returns_the_remainder_when_there_is_no_full_chunk() {
+        let mut chunks = (1..3).chunks_exact_with_position(3);
+
+        assert!(chunks.next().is_none());
+        assert_eq!(chunks.into_remainder(), vec![1, 2]);
+        assert_eq!((1..8).chunks_exact_with_position(3).into_remainder(), vec![7]);
+    }
+
+    #[test]
+    fn it_yields_full_chunks_as_arrays() {
+        let result: Vec<_> = "abcdefg".chars().array_chunks_with_position::<3>().collect();
+        assert_eq!(result, vec![(Position::First, ['a', 'b', 'c']), (Position::Last, ['d', 'e', 'f'])]);
+
+        let mut single = (1..3).array_chunks_with_position::<2>();
+        assert_eq!(single.next(), Some((Position::Only, [1, 2])));
+        assert!(single.into_remainder().is_empty());
+    }
+
+    #[test]
+    fn it_chunks_from_the_end() {
+        let chunks = vec!["a", "b", "c", "d"].into_iter().rchunks_with_position(3);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(to_vecs(chunks), vec![(Position::First, vec!["b", "c", "d"]), (Position::Last, vec!["a"])]);
+        assert_eq!(to_vecs((1..3).rchunks_with_position(2)), vec![(Position::Only, vec![1, 2])]);
+    }
+}
@@ -0,0 +1,80 @@
+use std::iter::{Fuse, FusedIterator};
+
+use Position;
+
+/// An iterator adapter that merges adjacent items with a closure and yields the
+/// merged items with their position among them, created by
+/// `WithPosition::coalesce_positioned`.
+pub struct CoalescePositioned<I, F> where I: Iterator {
+    iter: Fuse<I>,
+    f: F,
+    carry: Option<I::Item>,
+    next: Option<I::Item>,
+    did_iter: bool,
+}
+
+impl<I, F> CoalescePositioned<I, F> where I: Iterator, F: FnMut(I::Item, I::Item) -> Result<I::Item, (I::Item, I::Item)> {
+    pub(crate) fn new(iter: I, f: F) -> CoalescePositioned<I, F> {
+        CoalescePositioned { iter: iter.fuse(), f, carry: None, next: None, did_iter: false }
+    }
+
+    fn pull(&mut self) -> Option<I::Item> {
+        let mut merged = match self.carry.take() {
+            Some(item) => item,
+            None => self.iter.next()?,
+        };
+        for item in &mut self.iter {
+            match (self.f)(merged, item) {
+                Ok(item) => merged = item,
+                Err((previous, next)) => {
+                    self.carry = Some(next);
+                    return Some(previous);
+                }
+            }
+        }
+        Some(merged)
+    }
+}
+
+impl<I, F> Iterator for CoalescePositioned<I, F> where I: Iterator, F: FnMut(I::Item, I::Item) -> Result<I::Item, (I::Item, I::Item)> {
+    type Item = (Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = match self.next.take() {
+            Some(item) => item,
+            None if self.did_iter => return None,
+            None => self.pull()?,
+        };
+        self.next = self.pull();
+
+        let is_first = !self.did_iter;
+        self.did_iter = true;
+        Some((Position::from_flags(is_first, self.next.is_none()), item))
+    }
+}
+
+impl<I, F> FusedIterator for CoalescePositioned<I, F> where I: Iterator, F: FnMut(I::Item, I::Item) -> Result<I::Item, (I::Item, I::Item)> {}
+
+#[cfg(test)]
+mod tests {
+    use {Position, WithPosition};
+
+    #[test]
+    fn it_assigns_positions_over_merged_items() {
+        let result: Vec<_> = vec![1, 2, 10, 11, 12, 20, 21].into_iter()
+            .map(|n| (n, n))
+            .coalesce_positioned(|(start, end), (next_start, next_end)| {
+                if next_start == end + 1 { Ok((start, next_end)) } else { Err(((start, end), (next_start, next_end))) }
+            })
+            .collect();
+
+        assert_eq!(result, vec![(Position::First, (1, 2)), (Position::Middle, (10, 12)), (Position::Last, (20, 21))]);
+    }
+
+    #[test]
+    fn it_marks_a_fully_merged_iteration_as_only() {
+        let result: Vec<_> = vec![1, 2, 3].into_iter().coalesce_positioned(|a, b| Ok(a + b)).collect();
+
+        assert_eq!(result, vec![(Position::Only, 6)]);
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::{btree_map, BTreeMap, BinaryHeap, LinkedList, VecDeque};
+use std::iter::Rev;
+use std::vec;
+
+use {ExactPositionIterator, Position};
+
+/// A double ended queue which items can be pushed to and popped from at both
+/// ends. This is implemented for `VecDeque` and `LinkedList`.
+pub trait Deque {
+    type Item;
+
+    fn push_front(&mut self, item: Self::Item);
+    fn push_back(&mut self, item: Self::Item);
+    fn pop_front(&mut self) -> Option<Self::Item>;
+    fn pop_back(&mut self) -> Option<Self::Item>;
+    fn is_empty(&self) -> bool;
+}
+
+impl<T> Deque for VecDeque<T> {
+    type Item = T;
+
+    fn push_front(&mut self, item: T) { VecDeque::push_front(self, item) }
+    fn push_back(&mut self, item: T) { VecDeque::push_back(self, item) }
+    fn pop_front(&mut self) -> Option<T> { VecDeque::pop_front(self) }
+    fn pop_back(&mut self) -> Option<T> { VecDeque::pop_back(self) }
+    fn is_empty(&self) -> bool { VecDeque::is_empty(self) }
+}
+
+impl<T> Deque for LinkedList<T> {
+    type Item = T;
+
+    fn push_front(&mut self, item: T) { LinkedList::push_front(self, item) }
+    fn push_back(&mut self, item: T) { LinkedList::push_back(self, item) }
+    fn pop_front(&mut self) -> Option<T> { LinkedList::pop_front(self) }
+    fn pop_back(&mut self) -> Option<T> { LinkedList::pop_back(self) }
+    fn is_empty(&self) -> bool { LinkedList::is_empty(self) }
+}
+
+/// Extension trait for double ended queues which adds the `pop_with_position`
+/// method.
+pub trait DequeWithPosition: Deque + Sized {
+    /// Pop items off the front of the queue (or the back, when iterating in
+    /// reverse), yielding a tuple of `(Position, item)`. Items can be pushed
+    /// onto the queue while draining it through the returned iterator, and an
+    /// item is only marked as last once the queue is empty after popping it.
+    fn pop_with_position(&mut self) -> DequeDrain<'_, Self> {
+        DequeDrain { deque: self, front_started: false, back_started: false }
+    }
+}
+
+impl<D> DequeWithPosition for D where D: Deque {}
+
+/// An iterator which pops positioned items off a double ended queue, created by
+/// `DequeWithPosition::pop_with_position`.
+pub struct DequeDrain<'a, D: 'a> {
+    deque: &'a mut D,
+    front_started: bool,
+    back_started: bool,
+}
+
+impl<'a, D> DequeDrain<'a, D> where D: Deque {
+    /// Push an item onto the front of the queue. It will be yielded next.
+    pub fn push_front(&mut self, item: D::Item) {
+        self.deque.push_front(item);
+    }
+
+    /// Push an item onto the back of the queue.
+    pub fn push_back(&mut self, item: D::Item) {
+        self.deque.push_back(item);
+    }
+}
+
+impl<'a, D> Iterator for DequeDrain<'a, D> where D: Deque {
+    type Item = (Position, D::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.deque.pop_front()?;
+        let is_first = !self.front_started;
+        let is_last = self.deque.is_empty() && !self.back_started;
+        self.front_started = true;
+        Some((Position::from_flags(is_first, is_last), item))
+    }
+}
+
+impl<'a, D> DoubleEndedIterator for DequeDrain<'a, D> where D: Deque {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let item = self.deque.pop_back()?;
+        let is_first = self.deque.is_empty() && !self.front_started;
+        let is_last = !self.back_started;
+        self.back_started = true;
+        Some((Position::from_flags(is_first, is_last), item))
+    }
+}
+
+/// Extension trait for `BTreeMap` which adds positioned iteration methods.
+/// Positions are computed from the length of the map, so no lookahead is
+/// needed.
+pub trait BTreeMapWithPosition<K, V> {
+    /// Yield a tuple of `(Position, (&key, &value))` for each entry in the map.
+    fn entries_with_position(&self) -> ExactPositionIterator<btree_map::Iter<'_, K, V>>;
+
+    /// Yield a tuple of `(Position, &key)` for each key in the map.
+    fn keys_with_position(&self) -> ExactPositionIterator<btree_map::Keys<'_, K, V>>;
+
+    /// Yield a tuple of `(Position, &value)` for each value in the map.
+    fn values_with_position(&self) -> ExactPositionIterator<btree_map::Values<'_, K, V>>;
+}
+
+impl<K, V> BTreeMapWithPosition<K, V> for BTreeMap<K, V> {
+    fn entries_with_position(&self) -> ExactPositionIterator<btree_map::Iter<'_, K, V>> {
+        ExactPositionIterator::new(self.iter())
+    }
+
+    fn keys_with_position(&self) -> ExactPositionIterator<btree_map::Keys<'_, K, V>> {
+        ExactPositionIterator::new(self.keys())
+    }
+
+    fn values_with_position(&self) -> ExactPositionIterator<btree_map::Values<'_, K, V>> {
+        ExactPositionIterator::new(self.values())
+    }
+}
+
+/// Extension trait for `BinaryHeap` which adds the `into_sorted_with_position`
+/// method.
+pub trait BinaryHeapWithPosition<T> {
+    /// Consume the heap, yielding a tuple of `(Position, item)` in the order the
+    /// items would be popped off the heap, that is from greatest to least.
+    fn into_sorted_with_position(self) -> ExactPositionIterator<Rev<vec::IntoIter<T>>>;
+}
+
+impl<T> BinaryHeapWithPosition<T> for BinaryHeap<T> where T: Ord {
+    fn into_sorted_with_position(self) -> ExactPositionIterator<Rev<vec::IntoIter<T>>> {
+        ExactPositionIterator::new(self.into_sorted_vec().into_iter().rev())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BinaryHeap, LinkedList, VecDeque};
+    use {BTreeMapWithPosition, BinaryHeapWithPosition, DequeWithPosition, Position};
+
+    #[test]
+    fn it_marks_positions_while_draining_a_queue() {
+        let mut queue: VecDeque<_> = vec![1, 2].into_iter().collect();
+        let mut result = Vec::new();
+
+        {
+            let mut drain = queue.pop_with_position();
+            while let Some((position, item)) = drain.next() {
+                if item == 1 {
+                    drain.push_back(3);
+                }
+                result.push((position, item));
+            }
+        }
+
+        assert_eq!(result, vec![(Position::First, 1), (Position::Middle, 2), (Position::Last, 3)]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn it_marks_positions_from_both_ends() {
+        let mut list: LinkedList<_> = vec![1, 2, 3].into_iter().collect();
+        let mut drain = list.pop_with_position();
+
+        assert_eq!(drain.next_back(), Some((Position::Last, 3)));
+        assert_eq!(drain.next(), Some((Position::First, 1)));
+        assert_eq!(drain.next(), Some((Position::Middle, 2)));
+        assert_eq!(drain.next(), None);
+    }
+
+    #[test]
+    fn it_marks_only_position_in_a_queue() {
+        let mut queue: VecDeque<_> = vec![1].into_iter().collect();
+        let result: Vec<_> = queue.pop_with_position().collect();
+
+        assert_eq!(result, vec![(Position::Only, 1)]);
+    }
+
+    #[test]
+    fn it_marks_positions_of_map_entries() {
+        let mut map = BTreeMap::new();
+        map.insert("b", 2);
+        map.insert("a", 1);
+        map.insert("c", 3);
+
+        let mut rendered = String::new();
+        for (position, (key, value)) in map.entries_with_position() {
+            rendered.push_str(&format!("{}={}", key, value));
+            if !position.is_last() {
+                rendered.push_str(", ");
+            }
+        }
+
+        assert_eq!(rendered, "a=1, b=2, c=3");
+    }
+
+    #[test]
+    fn it_marks_positions_of_map_keys_and_values() {
+        let mut map = BTreeMap::new();
+        map.insert(1, "one");
+        map.insert(2, "two");
+
+        assert_eq!(map.keys_with_position().collect::<Vec<_>>(), vec![(Position::First, &1), (Position::Last, &2)]);
+        assert_eq!(map.values_with_position().next_back(), Some((Position::Last, &"two")));
+    }
+
+    #[test]
+    fn it_marks_positions_in_heap_order() {
+        let heap: BinaryHeap<_> = vec![2, 5, 1, 3].into_iter().collect();
+
+        let result: Vec<_> = heap.into_sorted_with_position().collect();
+
+        assert_eq!(result, vec![(Position::First, 5), (Position::Middle, 3), (Position::Middle, 2), (Position::Last, 1)]);
+    }
+}
@@ -0,0 +1,96 @@
+use std::iter::FusedIterator;
+use std::ops::{Coroutine, CoroutineState};
+use std::pin::Pin;
+
+use {PositionIterator, WithPosition};
+
+/// An iterator over the values yielded by a coroutine, created by
+/// `coroutine_with_position` and `coroutine_with_position_resume`. It ends
+/// when the coroutine returns, and the closure is called for the argument
+/// of every resumption.
+pub struct CoroutineIter<G, F> {
+    coroutine: G,
+    resume: F,
+    done: bool,
+}
+
+impl<G, F, R> Iterator for CoroutineIter<G, F> where G: Coroutine<R, Return = ()> + Unpin, F: FnMut() -> R {
+    type Item = G::Yield;
+
+    fn next(&mut self) -> Option<G::Yield> {
+        if self.done {
+            return None;
+        }
+        match Pin::new(&mut self.coroutine).resume((self.resume)()) {
+            CoroutineState::Yielded(item) => Some(item),
+            CoroutineState::Complete(()) => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+impl<G, F, R> FusedIterator for CoroutineIter<G, F> where G: Coroutine<R, Return = ()> + Unpin, F: FnMut() -> R {}
+
+/// Yield a tuple of `(Position, value)` for each value yielded by the
+/// coroutine, without collecting them first. Since finding out whether a
+/// value is the last one means resuming the coroutine again, it always runs
+/// one value ahead of the consumer.
+///
+/// This requires the `coroutines` feature and a nightly compiler.
+///
+/// ```
+/// #![feature(coroutines, stmt_expr_attributes)]
+/// # extern crate with_position;
+/// use with_position::{coroutine_with_position, Position};
+///
+/// # fn main() {
+/// let tokens: Vec<_> = coroutine_with_position(#[coroutine] || {
+///     yield "(";
+///     yield ")";
+/// }).collect();
+///
+/// assert_eq!(tokens, vec![(Position::First, "("), (Position::Last, ")")]);
+/// # }
+/// ```
+pub fn coroutine_with_position<G>(coroutine: G) -> PositionIterator<CoroutineIter<G, fn()>> where G: Coroutine<Return = ()> + Unpin {
+    CoroutineIter { coroutine, resume: (|| ()) as fn(), done: false }.with_position()
+}
+
+/// Like `coroutine_with_position`, but resumes the coroutine with the
+/// argument returned by the closure. Because of the look ahead, the argument
+/// for a value is requested before the previous value has been consumed.
+pub fn coroutine_with_position_resume<G, F, R>(coroutine: G, resume: F) -> PositionIterator<CoroutineIter<G, F>>
+    where G: Coroutine<R, Return = ()> + Unpin, F: FnMut() -> R
+{
+    CoroutineIter { coroutine, resume, done: false }.with_position()
+}
+
+#[cfg(test)]
+mod tests {
+    use {coroutine_with_position, coroutine_with_position_resume, Position};
+
+    #[test]
+    fn it_labels_the_values_of_a_coroutine() {
+        let result: Vec<_> = coroutine_with_position(#[coroutine] || {
+            for n in 1..4 {
+                yield n;
+            }
+        }).collect();
+
+        assert_eq!(result, vec![(Position::First, 1), (Position::Middle, 2), (Position::Last, 3)]);
+    }
+
+    #[test]
+    fn it_resumes_the_coroutine_with_arguments() {
+        let mut next = 0;
+        let result: Vec<_> = coroutine_with_position_resume(#[coroutine] |mut n: u32| {
+            while n < 3 {
+                n = yield n * 10;
+            }
+        }, || { next += 1; next }).collect();
+
+        assert_eq!(result, vec![(Position::First, 10), (Position::Last, 20)]);
+    }
+}
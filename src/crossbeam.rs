@@ -0,0 +1,103 @@
+use crossbeam_channel::{Receiver, TryRecvError};
+
+use channel::{LookAhead, Recv, TryRecv};
+use Position;
+
+impl<T> Recv for Receiver<T> {
+    type Message = T;
+
+    fn recv_message(&self) -> Option<T> {
+        self.recv().ok()
+    }
+
+    fn try_recv_message(&self) -> TryRecv<T> {
+        match self.try_recv() {
+            Ok(message) => TryRecv::Message(message),
+            Err(TryRecvError::Empty) => TryRecv::Empty,
+            Err(TryRecvError::Disconnected) => TryRecv::Disconnected,
+        }
+    }
+}
+
+/// A wrapper around a crossbeam channel receiver which yields tuples of
+/// `(Position, message)`. One message is held back until the next one arrives,
+/// so the last message is only yielded once all senders have disconnected.
+pub struct PositionedCrossbeamReceiver<T> {
+    inner: LookAhead<Receiver<T>>,
+}
+
+impl<T> PositionedCrossbeamReceiver<T> {
+    pub fn new(receiver: Receiver<T>) -> PositionedCrossbeamReceiver<T> {
+        PositionedCrossbeamReceiver { inner: LookAhead::new(receiver) }
+    }
+
+    /// The wrapped receiver, which can be used in `select!` to wait until a
+    /// message is ready, before calling `try_next_positioned`.
+    pub fn receiver(&self) -> &Receiver<T> {
+        &self.inner.receiver
+    }
+
+    /// Block until the next positioned message can be yielded, returning `None`
+    /// once the channel is disconnected and all messages have been yielded.
+    pub fn recv(&mut self) -> Option<(Position, T)> {
+        self.inner.recv()
+    }
+
+    /// Try to yield the next positioned message without blocking. Since the
+    /// position of a message is only known once the next message has arrived
+    /// or the channel has disconnected, this returns `TryRecvError::Empty` while
+    /// a message is held back waiting for either of those to happen.
+    pub fn try_next_positioned(&mut self) -> Result<(Position, T), TryRecvError> {
+        match self.inner.try_recv() {
+            TryRecv::Message(message) => Ok(message),
+            TryRecv::Empty => Err(TryRecvError::Empty),
+            TryRecv::Disconnected => Err(TryRecvError::Disconnected),
+        }
+    }
+}
+
+impl<T> Iterator for PositionedCrossbeamReceiver<T> {
+    type Item = (Position, T);
+
+    fn next(&mut self) -> Option<(Position, T)> {
+        self.recv()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use crossbeam_channel::{bounded, TryRecvError};
+    use {Position, PositionedCrossbeamReceiver};
+
+    #[test]
+    fn it_marks_positions_of_received_messages() {
+        let (sender, receiver) = bounded(1);
+        thread::spawn(move || {
+            for message in 1..4 {
+                sender.send(message).unwrap();
+            }
+        });
+
+        let result: Vec<_> = PositionedCrossbeamReceiver::new(receiver).collect();
+
+        assert_eq!(result, vec![(Position::First, 1), (Position::Middle, 2), (Position::Last, 3)]);
+    }
+
+    #[test]
+    fn it_holds_back_a_message_until_its_position_is_known() {
+        let (sender, receiver) = bounded(2);
+        let mut receiver = PositionedCrossbeamReceiver::new(receiver);
+
+        sender.send(1).unwrap();
+        assert_eq!(receiver.try_next_positioned(), Err(TryRecvError::Empty));
+
+        sender.send(2).unwrap();
+        assert_eq!(receiver.try_next_positioned(), Ok((Position::First, 1)));
+
+        drop(sender);
+        assert_eq!(receiver.try_next_positioned(), Ok((Position::Last, 2)));
+        assert_eq!(receiver.try_next_positioned(), Err(TryRecvError::Disconnected));
+    }
+}
@@ -0,0 +1,53 @@
+use std::io::Read;
+
+use csv::{DeserializeRecordsIter, Reader, StringRecordsIter};
+use serde::de::DeserializeOwned;
+
+use {PositionIterator, WithPosition};
+
+/// Extension trait for CSV readers which adds positioned record iteration.
+/// Only one record is read ahead to find the last record, so the file is not
+/// buffered.
+pub trait CsvReaderWithPosition<R> where R: Read {
+    /// Iterate over the records as `StringRecord`s, yielding a tuple of
+    /// `(Position, Result<StringRecord>)`.
+    fn records_with_position(&mut self) -> PositionIterator<StringRecordsIter<'_, R>>;
+
+    /// Iterate over the records deserialized into `D`, yielding a tuple of
+    /// `(Position, Result<D>)`.
+    fn deserialize_with_position<D>(&mut self) -> PositionIterator<DeserializeRecordsIter<'_, R, D>> where D: DeserializeOwned;
+}
+
+impl<R> CsvReaderWithPosition<R> for Reader<R> where R: Read {
+    fn records_with_position(&mut self) -> PositionIterator<StringRecordsIter<'_, R>> {
+        self.records().with_position()
+    }
+
+    fn deserialize_with_position<D>(&mut self) -> PositionIterator<DeserializeRecordsIter<'_, R, D>> where D: DeserializeOwned {
+        self.deserialize().with_position()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use csv::Reader;
+    use {CsvReaderWithPosition, Position};
+
+    #[test]
+    fn it_marks_positions_of_records() {
+        let mut reader = Reader::from_reader("name,count\na,1\nb,2\n".as_bytes());
+
+        let result: Vec<_> = reader.records_with_position().map(|(position, record)| (position, record.unwrap()[0].to_string())).collect();
+
+        assert_eq!(result, vec![(Position::First, String::from("a")), (Position::Last, String::from("b"))]);
+    }
+
+    #[test]
+    fn it_marks_positions_of_deserialized_rows() {
+        let mut reader = Reader::from_reader("name,count\na,1\n".as_bytes());
+
+        let result: Vec<_> = reader.deserialize_with_position::<(String, u32)>().map(|(position, row)| (position, row.unwrap())).collect();
+
+        assert_eq!(result, vec![(Position::Only, (String::from("a"), 1))]);
+    }
+}
@@ -0,0 +1,56 @@
+use {Position, PositionIterator, WithPosition};
+
+/// An iterator adapter that repeats the source forever, yielding a tuple of
+/// `(cycle, Position, item)` where the position is relative to each pass over
+/// the source, created by `WithPosition::cycle_with_position`.
+pub struct CycleWithPosition<I> where I: Iterator {
+    source: I,
+    iter: PositionIterator<I>,
+    cycle: usize,
+}
+
+impl<I> CycleWithPosition<I> where I: Iterator + Clone {
+    pub(crate) fn new(source: I) -> CycleWithPosition<I> {
+        CycleWithPosition { iter: source.clone().with_position(), source, cycle: 0 }
+    }
+}
+
+impl<I> Iterator for CycleWithPosition<I> where I: Iterator + Clone {
+    type Item = (usize, Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((position, item)) = self.iter.next() {
+            return Some((self.cycle, position, item));
+        }
+        // An empty source never yields anything, rather than looping forever.
+        self.iter = self.source.clone().with_position();
+        let (position, item) = self.iter.next()?;
+        self.cycle += 1;
+        Some((self.cycle, position, item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+    use {Position, WithPosition};
+
+    #[test]
+    fn it_marks_positions_and_cycles_of_each_pass() {
+        let result: Vec<_> = vec!['a', 'b'].into_iter().cycle_with_position().take(5).collect();
+
+        assert_eq!(result, vec![
+            (0, Position::First, 'a'),
+            (0, Position::Last, 'b'),
+            (1, Position::First, 'a'),
+            (1, Position::Last, 'b'),
+            (2, Position::First, 'a'),
+        ]);
+    }
+
+    #[test]
+    fn it_yields_nothing_for_an_empty_source() {
+        assert_eq!(iter::empty::<i32>().cycle_with_position().next(), None);
+        assert_eq!(iter::once(1).cycle_with_position().nth(3), Some((3, Position::Only, 1)));
+    }
+}
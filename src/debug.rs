@@ -0,0 +1,138 @@
+use std::fmt;
+use std::iter::FusedIterator;
+
+use {Position, PositionIterator, WithPosition};
+
+/// The number of positions kept in the trace.
+const TRACE_LIMIT: usize = 64;
+
+/// An iterator adapter that yields the items unchanged, while recording a
+/// trace of their positions such as `F M M L`, created by
+/// `WithPosition::debug_positions`. Only the positions of the most recent 64
+/// items are kept, so it can wrap long or endless iterators.
+/// With `print_on_drop` the trace is printed to standard error together with
+/// the label and the number of items when the adapter is dropped, so that it
+/// shows how far a consumer actually got.
+///
+/// ```
+/// use with_position::WithPosition;
+///
+/// let mut iter = vec![1, 2, 3].into_iter().debug_positions("numbers");
+/// iter.next();
+/// iter.next();
+///
+/// assert_eq!(iter.trace(), "F M");
+/// assert_eq!(iter.to_string(), "numbers: F M (2 items)");
+/// ```
+pub struct DebugPositions<I> where I: Iterator {
+    iter: PositionIterator<I>,
+    label: String,
+    trace: String,
+    count: usize,
+    print_on_drop: bool,
+}
+
+impl<I> DebugPositions<I> where I: Iterator {
+    pub(crate) fn new(iter: I, label: &str) -> DebugPositions<I> {
+        DebugPositions { iter: iter.with_position(), label: label.to_string(), trace: String::new(), count: 0, print_on_drop: false }
+    }
+
+    /// Print the trace to standard error when the adapter is dropped.
+    pub fn print_on_drop(mut self) -> DebugPositions<I> {
+        self.print_on_drop = true;
+        self
+    }
+
+    /// Return the positions of the most recent items yielded, up to 64 of
+    /// them, each abbreviated to its initial and separated by spaces.
+    pub fn trace(&self) -> &str {
+        &self.trace
+    }
+
+    /// Return the number of items yielded so far.
+    pub fn items_seen(&self) -> usize {
+        self.count
+    }
+}
+
+impl<I> Iterator for DebugPositions<I> where I: Iterator {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let (position, item) = self.iter.next()?;
+        if self.count >= TRACE_LIMIT {
+            self.trace.drain(..2);
+        }
+        if !self.trace.is_empty() {
+            self.trace.push(' ');
+        }
+        self.trace.push(match position {
+            Position::First => 'F',
+            Position::Middle => 'M',
+            Position::Last => 'L',
+            Position::Only => 'O',
+        });
+        self.count += 1;
+        Some(item)
+    }
+}
+
+impl<I> FusedIterator for DebugPositions<I> where I: Iterator {}
+
+impl<I> fmt::Display for DebugPositions<I> where I: Iterator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let noun = if self.count == 1 { "item" } else { "items" };
+        if self.count == 0 {
+            write!(f, "{}: (0 {})", self.label, noun)
+        } else if self.count > TRACE_LIMIT {
+            write!(f, "{}: ... {} ({} {})", self.label, self.trace, self.count, noun)
+        } else {
+            write!(f, "{}: {} ({} {})", self.label, self.trace, self.count, noun)
+        }
+    }
+}
+
+impl<I> Drop for DebugPositions<I> where I: Iterator {
+    fn drop(&mut self) {
+        if self.print_on_drop {
+            eprintln!("{}", self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TRACE_LIMIT;
+    use WithPosition;
+
+    #[test]
+    fn it_records_the_positions_of_yielded_items() {
+        let mut iter = vec!["a", "b", "c", "d"].into_iter().debug_positions("letters");
+
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec!["a", "b", "c", "d"]);
+        assert_eq!(iter.trace(), "F M M L");
+        assert_eq!(iter.items_seen(), 4);
+        assert_eq!(iter.to_string(), "letters: F M M L (4 items)");
+    }
+
+    #[test]
+    fn it_records_a_single_item_and_no_items() {
+        let mut single = Some(1).into_iter().debug_positions("single");
+        single.next();
+        assert_eq!(single.to_string(), "single: O (1 item)");
+
+        let empty = None::<i32>.into_iter().debug_positions("empty");
+        assert_eq!(empty.to_string(), "empty: (0 items)");
+    }
+
+    #[test]
+    fn it_keeps_only_the_most_recent_positions() {
+        let mut iter = (0..TRACE_LIMIT + 2).debug_positions("numbers");
+        iter.by_ref().for_each(drop);
+
+        assert_eq!(iter.items_seen(), TRACE_LIMIT + 2);
+        assert_eq!(iter.trace().len(), TRACE_LIMIT * 2 - 1);
+        assert!(iter.trace().starts_with("M M") && iter.trace().ends_with("M L"));
+        assert!(iter.to_string().starts_with("numbers: ... M M"));
+    }
+}
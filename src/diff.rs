@@ -0,0 +1,131 @@
+use std::vec;
+
+use {DiffLine, ExactPositionIterator, Position};
+
+/// A change between two sequences, created by `diff_with_position`. Every
+/// change carries the position of its item in the sequence or sequences it
+/// comes from.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Change<T> {
+    /// An item in both sequences, with its position in the old and the new
+    /// sequence.
+    Equal { old: Position, new: Position, item: T },
+    /// An item only in the old sequence.
+    Removed { old: Position, item: T },
+    /// An item only in the new sequence.
+    Added { new: Position, item: T },
+}
+
+impl<T> Change<T> {
+    /// The item which is kept, removed or added.
+    pub fn item(&self) -> &T {
+        match *self {
+            Change::Equal { ref item, .. } | Change::Removed { ref item, .. } | Change::Added { ref item, .. } => item,
+        }
+    }
+}
+
+/// Turns a change into a line of a hunk, dropping its positions, so changes can
+/// be collected into a `Hunk` and written with `write_unified_diff`.
+impl<T> From<Change<T>> for DiffLine<T> {
+    fn from(change: Change<T>) -> DiffLine<T> {
+        match change {
+            Change::Equal { item, .. } => DiffLine::Context(item),
+            Change::Removed { item, .. } => DiffLine::Removed(item),
+            Change::Added { item, .. } => DiffLine::Added(item),
+        }
+    }
+}
+
+/// Compute the changes from `old` to `new` from a longest common subsequence,
+/// yielding a tuple of `(Position, Change)` where the position is the position
+/// of the change among all changes, so a renderer knows when it is emitting
+/// the final line. Removals are yielded before additions at the same place.
+/// Equal items are taken from `old`.
+///
+/// Both sequences are collected, and the diff takes time and memory
+/// proportional to the product of their lengths.
+///
+/// ```
+/// use with_position::{diff_with_position, Change, Position};
+///
+/// let changes: Vec<_> = diff_with_position(vec!["a", "b"], vec!["a", "c"]).collect();
+///
+/// assert_eq!(changes, vec![
+///     (Position::First, Change::Equal { old: Position::First, new: Position::First, item: "a" }),
+///     (Position::Middle, Change::Removed { old: Position::Last, item: "b" }),
+///     (Position::Last, Change::Added { new: Position::Last, item: "c" }),
+/// ]);
+/// ```
+pub fn diff_with_position<A, B, T>(old: A, new: B) -> ExactPositionIterator<vec::IntoIter<Change<T>>> where A: IntoIterator<Item = T>, B: IntoIterator<Item = T>, T: PartialEq {
+    let old: Vec<T> = old.into_iter().collect();
+    let new: Vec<T> = new.into_iter().collect();
+    let (old_len, new_len) = (old.len(), new.len());
+
+    // The length of the longest common subsequence of `old[i..]` and
+    // `new[j..]`, at `i * (new_len + 1) + j`.
+    let width = new_len + 1;
+    let mut lengths = vec![0usize; (old_len + 1) * width];
+    for i in (0..old_len).rev() {
+        for j in (0..new_len).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::with_capacity(old_len.max(new_len));
+    let (mut old_items, mut new_items) = (old.into_iter(), new.into_iter());
+    let (mut i, mut j) = (0, 0);
+    while i < old_len || j < new_len {
+        let is_equal = i < old_len && j < new_len && old_items.as_slice()[0] == new_items.as_slice()[0];
+        if is_equal {
+            new_items.next();
+            changes.push(Change::Equal { old: Position::from_index(i, old_len), new: Position::from_index(j, new_len), item: old_items.next().unwrap() });
+            i += 1;
+            j += 1;
+        } else if j == new_len || (i < old_len && lengths[(i + 1) * width + j] >= lengths[i * width + j + 1]) {
+            changes.push(Change::Removed { old: Position::from_index(i, old_len), item: old_items.next().unwrap() });
+            i += 1;
+        } else {
+            changes.push(Change::Added { new: Position::from_index(j, new_len), item: new_items.next().unwrap() });
+            j += 1;
+        }
+    }
+    ExactPositionIterator::new(changes.into_iter())
+}
+
+#[cfg(test)]
+mod tests {
+    use {diff_with_position, Change, DiffLine, Position};
+
+    #[test]
+    fn it_diffs_sequences_with_positions() {
+        let changes: Vec<_> = diff_with_position("abcd".chars(), "acde".chars()).collect();
+
+        assert_eq!(changes, vec![
+            (Position::First, Change::Equal { old: Position::First, new: Position::First, item: 'a' }),
+            (Position::Middle, Change::Removed { old: Position::Middle, item: 'b' }),
+            (Position::Middle, Change::Equal { old: Position::Middle, new: Position::Middle, item: 'c' }),
+            (Position::Middle, Change::Equal { old: Position::Last, new: Position::Middle, item: 'd' }),
+            (Position::Last, Change::Added { new: Position::Last, item: 'e' }),
+        ]);
+    }
+
+    #[test]
+    fn it_diffs_against_an_empty_sequence() {
+        let changes: Vec<_> = diff_with_position(vec![1], Vec::new()).collect();
+
+        assert_eq!(changes, vec![(Position::Only, Change::Removed { old: Position::Only, item: 1 })]);
+        assert_eq!(diff_with_position(Vec::<u8>::new(), Vec::new()).next(), None);
+    }
+
+    #[test]
+    fn it_turns_changes_into_hunk_lines() {
+        let lines: Vec<DiffLine<_>> = diff_with_position("ab".chars(), "bc".chars()).map(|(_, change)| change.into()).collect();
+
+        assert_eq!(lines, vec![DiffLine::Removed('a'), DiffLine::Context('b'), DiffLine::Added('c')]);
+    }
+}
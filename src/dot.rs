@@ -0,0 +1,96 @@
+use std::fmt::{self, Debug, Write};
+
+use Position;
+
+/// A Graphviz graph of positioned sequences, for visualizing the output of
+/// an adapter chain while debugging. Every sequence is drawn as a cluster of
+/// nodes, labelled with the `Debug` output of the items, filled with a color
+/// for their position, and connected in iteration order. Nested iterations
+/// can be drawn by adding one sequence per group, with the position of the
+/// group in its label. The graph is written in the DOT language by its
+/// `Display` implementation.
+///
+/// ```
+/// use with_position::{DotGraph, WithPosition};
+///
+/// let mut graph = DotGraph::new();
+/// graph.sequence("numbers", vec![1, 2].into_iter().with_position());
+/// let dot = graph.to_string();
+///
+/// assert!(dot.starts_with("digraph positions {"));
+/// assert!(dot.contains("n0_0 [label=\"1\", fillcolor=palegreen];"));
+/// assert!(dot.contains("n0_0 -> n0_1;"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DotGraph {
+    clusters: Vec<String>,
+}
+
+impl DotGraph {
+    pub fn new() -> DotGraph {
+        DotGraph::default()
+    }
+
+    /// Add a sequence of `(Position, item)` tuples as a cluster with the label.
+    pub fn sequence<I, T>(&mut self, label: &str, items: I) -> &mut DotGraph where I: IntoIterator<Item = (Position, T)>, T: Debug {
+        let cluster = self.clusters.len();
+        let mut body = String::new();
+        for (index, (position, item)) in items.into_iter().enumerate() {
+            let node_label = escape(&format!("{:?}", item));
+            writeln!(body, "        n{}_{} [label=\"{}\", fillcolor={}];", cluster, index, node_label, fill_color(position)).unwrap();
+            if index > 0 {
+                writeln!(body, "        n{}_{} -> n{}_{};", cluster, index - 1, cluster, index).unwrap();
+            }
+        }
+        self.clusters.push(format!("    subgraph cluster_{} {{\n        label=\"{}\";\n{}    }}\n", cluster, escape(label), body));
+        self
+    }
+}
+
+impl fmt::Display for DotGraph {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "digraph positions {{")?;
+        writeln!(f, "    node [style=filled];")?;
+        for cluster in &self.clusters {
+            f.write_str(cluster)?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+fn fill_color(position: Position) -> &'static str {
+    match position {
+        Position::First => "palegreen",
+        Position::Middle => "white",
+        Position::Last => "lightcoral",
+        Position::Only => "gold",
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use {DotGraph, WithPosition};
+
+    #[test]
+    fn it_draws_each_sequence_as_a_cluster() {
+        let mut graph = DotGraph::new();
+        graph.sequence("group \"a\"", vec!["x"].into_iter().with_position()).sequence("b", Vec::<(_, u8)>::new());
+
+        assert_eq!(graph.to_string(), concat!(
+            "digraph positions {\n",
+            "    node [style=filled];\n",
+            "    subgraph cluster_0 {\n",
+            "        label=\"group \\\"a\\\"\";\n",
+            "        n0_0 [label=\"\\\"x\\\"\", fillcolor=gold];\n",
+            "    }\n",
+            "    subgraph cluster_1 {\n",
+            "        label=\"b\";\n",
+            "    }\n",
+            "}\n",
+        ));
+    }
+}
@@ -0,0 +1,86 @@
+use std::error::Error;
+use std::fmt;
+
+use {Position, PositionIterator};
+
+/// An error together with the position and the index of the item for which it
+/// occurred, as returned by `WithPosition::try_for_each_with_position` and
+/// `WithPosition::try_map_with_position`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PositionedError<E> {
+    pub position: Position,
+    pub index: usize,
+    pub source: E,
+}
+
+impl<E> PositionedError<E> {
+    /// Return the wrapped error, discarding the position and index.
+    pub fn into_source(self) -> E {
+        self.source
+    }
+}
+
+impl<E> fmt::Display for PositionedError<E> where E: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "item {} ({:?}): {}", self.index, self.position, self.source)
+    }
+}
+
+impl<E> Error for PositionedError<E> where E: Error + 'static {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// An iterator adapter which maps each item together with its position using a
+/// fallible closure, attaching the position and index to any error. Created by
+/// `WithPosition::try_map_with_position`.
+pub struct TryMapWithPosition<I, F> where I: Iterator {
+    iter: PositionIterator<I>,
+    f: F,
+    index: usize,
+}
+
+impl<I, F> TryMapWithPosition<I, F> where I: Iterator {
+    pub(crate) fn new(iter: PositionIterator<I>, f: F) -> TryMapWithPosition<I, F> {
+        TryMapWithPosition { iter, f, index: 0 }
+    }
+}
+
+impl<I, F, T, E> Iterator for TryMapWithPosition<I, F> where I: Iterator, F: FnMut(Position, I::Item) -> Result<T, E> {
+    type Item = Result<T, PositionedError<E>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (position, item) = self.iter.next()?;
+        let index = self.index;
+        self.index += 1;
+        Some((self.f)(position, item).map_err(|source| PositionedError { position, index, source }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::ParseIntError;
+    use {Position, PositionedError, WithPosition};
+
+    #[test]
+    fn it_attaches_the_position_and_index_of_a_failing_item() {
+        let result = vec!["1", "2", "x", "4"].into_iter().try_for_each_with_position(|_, item| {
+            item.parse::<i32>().map(|_| ())
+        });
+
+        let error = result.unwrap_err();
+        assert_eq!((error.position, error.index), (Position::Middle, 2));
+        assert_eq!(error.to_string(), "item 2 (Middle): invalid digit found in string");
+    }
+
+    #[test]
+    fn it_maps_items_with_a_fallible_closure() {
+        let result: Vec<Result<i32, PositionedError<ParseIntError>>> = vec!["1", "x"].into_iter()
+            .try_map_with_position(|_, item| item.parse())
+            .collect();
+
+        assert_eq!(result[0], Ok(1));
+        assert_eq!(result[1].as_ref().unwrap_err().position, Position::Last);
+    }
+}
@@ -0,0 +1,48 @@
+use std::iter::FusedIterator;
+
+use Position;
+
+/// An iterator adapter over an exact size iterator which computes positions
+/// from the index of each item and the total length, rather than peeking
+/// ahead.
+pub struct ExactPositionIterator<I> {
+    iter: I,
+    len: usize,
+    front: usize,
+    back: usize,
+}
+
+impl<I> ExactPositionIterator<I> where I: ExactSizeIterator {
+    pub(crate) fn new(iter: I) -> ExactPositionIterator<I> {
+        let len = iter.len();
+        ExactPositionIterator { iter, len, front: 0, back: 0 }
+    }
+}
+
+impl<I> Iterator for ExactPositionIterator<I> where I: ExactSizeIterator {
+    type Item = (Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        let position = Position::from_index(self.front, self.len);
+        self.front += 1;
+        Some((position, item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> DoubleEndedIterator for ExactPositionIterator<I> where I: ExactSizeIterator + DoubleEndedIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next_back()?;
+        self.back += 1;
+        let position = Position::from_index(self.len - self.back, self.len);
+        Some((position, item))
+    }
+}
+
+impl<I> ExactSizeIterator for ExactPositionIterator<I> where I: ExactSizeIterator {}
+
+impl<I> FusedIterator for ExactPositionIterator<I> where I: ExactSizeIterator + FusedIterator {}
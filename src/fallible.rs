@@ -0,0 +1,85 @@
+use fallible_iterator::FallibleIterator;
+
+use Position;
+
+/// Extension trait for fallible iterators which adds the `with_position`
+/// method.
+pub trait FallibleWithPosition: FallibleIterator + Sized {
+    /// Yield a tuple of `(Position, item)` where position indicates whether this
+    /// is the first, middle or last item.
+    ///
+    /// If looking ahead to find out whether an item is the last one returns an
+    /// error, the item is yielded as a non-last item, and the error is returned
+    /// by the following call to `next`.
+    fn with_position(self) -> PositionFallibleIterator<Self> {
+        PositionFallibleIterator { iter: self, peeked: None, did_iter: false }
+    }
+}
+
+impl<I> FallibleWithPosition for I where I: FallibleIterator {}
+
+/// A fallible iterator adapter that yields tuples where the first element is a
+/// Position and the second is the item.
+pub struct PositionFallibleIterator<I> where I: FallibleIterator {
+    iter: I,
+    peeked: Option<Result<Option<I::Item>, I::Error>>,
+    did_iter: bool,
+}
+
+impl<I> FallibleIterator for PositionFallibleIterator<I> where I: FallibleIterator {
+    type Item = (Position, I::Item);
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item>, Self::Error> {
+        let current = match self.peeked.take() {
+            Some(peeked) => peeked?,
+            None => self.iter.next()?,
+        };
+        let current = match current {
+            Some(current) => current,
+            None => return Ok(None),
+        };
+
+        let next = self.iter.next();
+        let is_last = matches!(next, Ok(None));
+        self.peeked = Some(next);
+
+        let is_first = !self.did_iter;
+        self.did_iter = true;
+        Ok(Some((Position::from_flags(is_first, is_last), current)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let peeked = match self.peeked {
+            Some(Ok(Some(_))) => 1,
+            Some(Ok(None)) => return (0, Some(0)),
+            Some(Err(_)) | None => 0,
+        };
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_add(peeked), upper.and_then(|upper| upper.checked_add(peeked)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fallible_iterator::{convert, FallibleIterator};
+    use {FallibleWithPosition, Position};
+
+    #[test]
+    fn it_marks_positions_of_fallible_items() {
+        let result: Vec<_> = convert(vec![Ok::<_, ()>(1), Ok(2)].into_iter()).with_position().collect().unwrap();
+
+        assert_eq!(result, vec![(Position::First, 1), (Position::Last, 2)]);
+    }
+
+    #[test]
+    fn it_yields_the_item_before_an_error_lookahead() {
+        let mut iter = convert(vec![Ok(1), Err("broken"), Ok(3)].into_iter()).with_position();
+
+        assert_eq!(iter.next(), Ok(Some((Position::First, 1))));
+        assert_eq!(iter.size_hint(), (1, Some(1)));
+        assert_eq!(iter.next(), Err("broken"));
+        assert_eq!(iter.next(), Ok(Some((Position::Last, 3))));
+        assert_eq!(iter.next(), Ok(None));
+    }
+}
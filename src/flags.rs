@@ -0,0 +1,87 @@
+use std::iter::{FusedIterator, Peekable};
+
+/// An iterator adapter that yields tuples of `(is_last, item)`, created by
+/// `WithPosition::with_is_last`.
+pub struct WithIsLast<I> where I: Iterator {
+    iter: Peekable<I>,
+}
+
+impl<I> WithIsLast<I> where I: Iterator {
+    pub(crate) fn new(iter: I) -> WithIsLast<I> {
+        WithIsLast { iter: iter.peekable() }
+    }
+}
+
+impl<I> Iterator for WithIsLast<I> where I: Iterator {
+    type Item = (bool, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        Some((self.iter.peek().is_none(), item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for WithIsLast<I> where I: ExactSizeIterator {}
+
+impl<I> FusedIterator for WithIsLast<I> where I: FusedIterator {}
+
+/// An iterator adapter that yields tuples of `(is_first, item)`, created by
+/// `WithPosition::with_is_first`. Unlike the other adapters it never looks
+/// ahead, it only remembers whether it has yielded an item yet.
+pub struct WithIsFirst<I> {
+    iter: I,
+    started: bool,
+}
+
+impl<I> WithIsFirst<I> where I: Iterator {
+    pub(crate) fn new(iter: I) -> WithIsFirst<I> {
+        WithIsFirst { iter, started: false }
+    }
+}
+
+impl<I> Iterator for WithIsFirst<I> where I: Iterator {
+    type Item = (bool, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        let is_first = !self.started;
+        self.started = true;
+        Some((is_first, item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for WithIsFirst<I> where I: ExactSizeIterator {}
+
+impl<I> FusedIterator for WithIsFirst<I> where I: FusedIterator {}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use WithPosition;
+
+    #[test]
+    fn it_flags_the_last_item() {
+        let result: Vec<_> = vec![1, 2, 3].into_iter().with_is_last().collect();
+
+        assert_eq!(result, vec![(false, 1), (false, 2), (true, 3)]);
+        assert_eq!(Some(1).into_iter().with_is_last().collect::<Vec<_>>(), vec![(true, 1)]);
+    }
+
+    #[test]
+    fn it_flags_the_first_item_without_looking_ahead() {
+        let pulled = Cell::new(0);
+        let mut iter = (1..4).inspect(|_| pulled.set(pulled.get() + 1)).with_is_first();
+
+        assert_eq!(iter.next(), Some((true, 1)));
+        assert_eq!(iter.next(), Some((false, 2)));
+        assert_eq!(pulled.get(), 2);
+    }
+}
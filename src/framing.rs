@@ -0,0 +1,142 @@
+use std::convert::TryFrom;
+use std::io;
+
+use Position;
+
+/// Writes positioned payloads as binary frames, each made of a flags byte,
+/// the length of the payload as a big-endian `u32` and the payload itself.
+/// The end of stream flag is only set on the frame of the last payload, so a
+/// reader knows when to stop without a separate trailer. When there are no
+/// payloads a single empty frame with the flag is written instead.
+///
+/// ```
+/// use with_position::{FrameWriter, WithPosition};
+///
+/// let mut output = Vec::new();
+/// FrameWriter::new().write_frames(&mut output, vec!["ab", "c"].into_iter().with_position()).unwrap();
+///
+/// assert_eq!(output, b"\x00\x00\x00\x00\x02ab\x01\x00\x00\x00\x01c");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct FrameWriter {
+    flags: u8,
+    end_of_stream: u8,
+}
+
+impl FrameWriter {
+    pub fn new() -> FrameWriter {
+        FrameWriter { flags: 0, end_of_stream: 0x01 }
+    }
+
+    /// Set the flags written on every frame. Defaults to no flags.
+    pub fn flags(mut self, flags: u8) -> FrameWriter {
+        self.flags = flags;
+        self
+    }
+
+    /// Set the flag bits which mark the last frame. Defaults to `0x01`.
+    pub fn end_of_stream(mut self, end_of_stream: u8) -> FrameWriter {
+        self.end_of_stream = end_of_stream;
+        self
+    }
+
+    /// Write a frame for every payload to an `io::Write`.
+    ///
+    /// Fails with `InvalidInput` if a payload is longer than `u32::MAX`
+    /// bytes, before anything of its frame is written.
+    pub fn write_frames<W, I, T>(&self, out: &mut W, iter: I) -> io::Result<()>
+        where W: io::Write + ?Sized, I: IntoIterator<Item = (Position, T)>, T: AsRef<[u8]>
+    {
+        let mut wrote_any = false;
+        for (position, payload) in iter {
+            self.write_frame(out, payload.as_ref(), position.is_last())?;
+            wrote_any = true;
+        }
+        if !wrote_any {
+            self.write_frame(out, &[], true)?;
+        }
+        Ok(())
+    }
+
+    fn write_frame<W>(&self, out: &mut W, payload: &[u8], is_last: bool) -> io::Result<()> where W: io::Write + ?Sized {
+        let len = u32::try_from(payload.len()).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "frame payload is too long"))?;
+        let flags = if is_last { self.flags | self.end_of_stream } else { self.flags };
+        out.write_all(&[flags])?;
+        out.write_all(&len.to_be_bytes())?;
+        out.write_all(payload)
+    }
+}
+
+impl Default for FrameWriter {
+    fn default() -> FrameWriter {
+        FrameWriter::new()
+    }
+}
+
+/// Write positioned chunks in the HTTP/1.1 chunked transfer coding, as a
+/// line with the size of the chunk in hexadecimal followed by its data, and
+/// write the terminating zero length chunk right after the last chunk, or on
+/// its own when there are no chunks. Empty chunks are skipped, since they
+/// would end the body early.
+///
+/// ```
+/// use with_position::{write_chunked, WithPosition};
+///
+/// let mut output = Vec::new();
+/// write_chunked(&mut output, vec!["Hello, ", "world!"].into_iter().with_position()).unwrap();
+///
+/// assert_eq!(output, b"7\r\nHello, \r\n6\r\nworld!\r\n0\r\n\r\n");
+/// ```
+pub fn write_chunked<W, I, T>(out: &mut W, iter: I) -> io::Result<()>
+    where W: io::Write + ?Sized, I: IntoIterator<Item = (Position, T)>, T: AsRef<[u8]>
+{
+    for (position, chunk) in iter {
+        let chunk = chunk.as_ref();
+        if !chunk.is_empty() {
+            write!(out, "{:X}\r\n", chunk.len())?;
+            out.write_all(chunk)?;
+            out.write_all(b"\r\n")?;
+        }
+        if position.is_last() {
+            return out.write_all(b"0\r\n\r\n");
+        }
+    }
+    out.write_all(b"0\r\n\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+    use {write_chunked, FrameWriter, WithPosition};
+
+    #[test]
+    fn it_sets_the_end_of_stream_flag_on_the_only_frame() {
+        let mut output = Vec::new();
+
+        FrameWriter::new().flags(0x10).end_of_stream(0x80).write_frames(&mut output, iter::once(vec![7u8]).with_position()).unwrap();
+
+        assert_eq!(output, [0x90, 0, 0, 0, 1, 7]);
+    }
+
+    #[test]
+    fn it_writes_an_empty_frame_without_payloads() {
+        let mut output = Vec::new();
+
+        FrameWriter::new().write_frames(&mut output, iter::empty::<&[u8]>().with_position()).unwrap();
+
+        assert_eq!(output, [0x01, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn it_terminates_chunked_output_exactly_once() {
+        let mut output = Vec::new();
+        write_chunked(&mut output, vec![&b""[..], &[0xab; 26][..]].into_iter().with_position()).unwrap();
+        assert_eq!(&output[..4], b"1A\r\n");
+        assert!(output.ends_with(b"\r\n0\r\n\r\n"));
+        assert_eq!(output.len(), 4 + 26 + 2 + 5);
+
+        let mut output = Vec::new();
+        write_chunked(&mut output, iter::empty::<&str>().with_position()).unwrap();
+        assert_eq!(output, b"0\r\n\r\n");
+    }
+}
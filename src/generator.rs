@@ -0,0 +1,91 @@
+use std::future::Future;
+use std::iter::FusedIterator;
+
+use genawaiter::rc::{Co, Gen};
+use genawaiter::GeneratorState;
+
+#[cfg(feature = "futures")]
+use {PositionStream, StreamWithPositionExt};
+use {PositionIterator, WithPosition};
+
+/// An iterator over the values yielded by a `genawaiter` generator, created
+/// by `gen_with_position`.
+pub struct GenIter<Y, F> where F: Future<Output = ()> {
+    generator: Gen<Y, (), F>,
+    done: bool,
+}
+
+impl<Y, F> Iterator for GenIter<Y, F> where F: Future<Output = ()> {
+    type Item = Y;
+
+    fn next(&mut self) -> Option<Y> {
+        if self.done {
+            return None;
+        }
+        match self.generator.resume() {
+            GeneratorState::Yielded(item) => Some(item),
+            GeneratorState::Complete(()) => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+impl<Y, F> FusedIterator for GenIter<Y, F> where F: Future<Output = ()> {}
+
+/// Run the producer as a `genawaiter` generator, and yield a tuple of
+/// `(Position, value)` for each value it yields. This allows writing the
+/// producer as straight line code on stable Rust. Since finding out whether a
+/// value is the last one means resuming the generator again, it always runs
+/// one value ahead of the consumer.
+///
+/// ```edition2018
+/// use with_position::{gen_with_position, Position};
+///
+/// let tokens: Vec<_> = gen_with_position(|co| async move {
+///     co.yield_("(").await;
+///     co.yield_(")").await;
+/// }).collect();
+///
+/// assert_eq!(tokens, vec![(Position::First, "("), (Position::Last, ")")]);
+/// ```
+pub fn gen_with_position<Y, P, F>(producer: P) -> PositionIterator<GenIter<Y, F>> where P: FnOnce(Co<Y>) -> F, F: Future<Output = ()> {
+    GenIter { generator: Gen::new(producer), done: false }.with_position()
+}
+
+/// Like `gen_with_position`, but returns a stream, so that the producer can
+/// also await other futures between the values it yields.
+///
+/// ```edition2018
+/// use futures::executor::block_on;
+/// use futures::StreamExt;
+/// use with_position::{gen_stream_with_position, Position};
+///
+/// let stream = gen_stream_with_position(|co| async move {
+///     co.yield_(1).await;
+///     co.yield_(2).await;
+/// });
+///
+/// assert_eq!(block_on(stream.collect::<Vec<_>>()), vec![(Position::First, 1), (Position::Last, 2)]);
+/// ```
+#[cfg(feature = "futures")]
+pub fn gen_stream_with_position<Y, P, F>(producer: P) -> PositionStream<Gen<Y, (), F>> where P: FnOnce(Co<Y>) -> F, F: Future<Output = ()> {
+    Gen::new(producer).with_position()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use genawaiter::rc::Co;
+    use gen_with_position;
+
+    #[test]
+    fn it_yields_nothing_for_an_empty_generator() {
+        let mut iter = gen_with_position(|_: Co<u8>| future::ready(()));
+
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+}
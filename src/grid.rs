@@ -0,0 +1,197 @@
+use std::iter::FusedIterator;
+
+use {Position, PositionIterator, WithPosition};
+
+/// A corner of a grid.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// A side of a grid.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Side {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+/// Where a cell lies in a grid: in a corner, along an edge or in the interior.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GridPosition {
+    Corner(Corner),
+    Edge(Side),
+    Interior,
+}
+
+/// The position of a cell in a grid, given by the position of its row among
+/// the rows and the position of its column within the row.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct GridCell {
+    pub row: Position,
+    pub column: Position,
+}
+
+impl GridCell {
+    /// Classify the cell as a corner, edge or interior cell. Cells of a grid
+    /// with a single row or column lie on two opposite sides at once, in which
+    /// case top is preferred over bottom and left over right.
+    pub fn grid_position(&self) -> GridPosition {
+        let vertical = if self.row.is_first() {
+            Some(Side::Top)
+        } else if self.row.is_last() {
+            Some(Side::Bottom)
+        } else {
+            None
+        };
+        let horizontal = if self.column.is_first() {
+            Some(Side::Left)
+        } else if self.column.is_last() {
+            Some(Side::Right)
+        } else {
+            None
+        };
+        match (vertical, horizontal) {
+            (Some(Side::Top), Some(Side::Left)) => GridPosition::Corner(Corner::TopLeft),
+            (Some(Side::Top), Some(_)) => GridPosition::Corner(Corner::TopRight),
+            (Some(_), Some(Side::Left)) => GridPosition::Corner(Corner::BottomLeft),
+            (Some(_), Some(_)) => GridPosition::Corner(Corner::BottomRight),
+            (Some(side), None) | (None, Some(side)) => GridPosition::Edge(side),
+            (None, None) => GridPosition::Interior,
+        }
+    }
+}
+
+/// Extension trait for exact size iterators which adds the
+/// `with_grid_position` method.
+pub trait GridWithPosition: ExactSizeIterator + Sized {
+    /// Treat the items as the cells of a grid in row-major order, with `columns`
+    /// cells per row, and yield a tuple of `(GridCell, item)` for each of them.
+    /// If the number of items is not a multiple of `columns`, the last row is
+    /// shorter, and the column position of its last cell is `Last`.
+    ///
+    /// Panics if `columns` is zero.
+    fn with_grid_position(self, columns: usize) -> GridPositionIterator<Self> {
+        assert!(columns != 0, "columns must be non-zero");
+        let len = self.len();
+        GridPositionIterator { iter: self, columns, len, index: 0 }
+    }
+}
+
+impl<I> GridWithPosition for I where I: ExactSizeIterator {}
+
+/// An iterator adapter that yields the items of a flat iterator together with
+/// their position in a grid, created by `GridWithPosition::with_grid_position`.
+pub struct GridPositionIterator<I> {
+    iter: I,
+    columns: usize,
+    len: usize,
+    index: usize,
+}
+
+impl<I> Iterator for GridPositionIterator<I> where I: Iterator {
+    type Item = (GridCell, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        let (row, column) = (self.index / self.columns, self.index % self.columns);
+        let rows = self.len.div_ceil(self.columns);
+        let row_len = if row + 1 == rows { self.len - row * self.columns } else { self.columns };
+        self.index += 1;
+        let cell = GridCell { row: Position::from_index(row, rows), column: Position::from_index(column, row_len) };
+        Some((cell, item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for GridPositionIterator<I> where I: ExactSizeIterator {}
+
+impl<I> FusedIterator for GridPositionIterator<I> where I: FusedIterator {}
+
+/// Extension trait for iterators over rows, where each row is itself
+/// iterable, which adds the `cells_with_position` method.
+pub trait RowsWithPosition: Iterator + Sized where Self::Item: IntoIterator {
+    /// Yield a tuple of `(GridCell, cell)` for every cell of every row, with
+    /// the position of the row among the rows and the position of the cell
+    /// within its row. Rows may have different lengths, and empty rows are
+    /// skipped, although they still count towards the positions of the rows.
+    fn cells_with_position(self) -> CellPositions<Self> {
+        CellPositions { rows: self.with_position(), row: None }
+    }
+}
+
+impl<I> RowsWithPosition for I where I: Iterator, I::Item: IntoIterator {}
+
+/// An iterator adapter that yields the cells of an iterator over rows together
+/// with their row and column positions, created by
+/// `RowsWithPosition::cells_with_position`.
+pub struct CellPositions<I> where I: Iterator, I::Item: IntoIterator {
+    rows: PositionIterator<I>,
+    row: Option<(Position, PositionIterator<<I::Item as IntoIterator>::IntoIter>)>,
+}
+
+impl<I> Iterator for CellPositions<I> where I: Iterator, I::Item: IntoIterator {
+    type Item = (GridCell, <I::Item as IntoIterator>::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((row, ref mut cells)) = self.row {
+                if let Some((column, cell)) = cells.next() {
+                    return Some((GridCell { row, column }, cell));
+                }
+            }
+            let (position, row) = self.rows.next()?;
+            self.row = Some((position, row.into_iter().with_position()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Corner, GridCell, GridPosition, GridWithPosition, Position, RowsWithPosition, Side};
+
+    #[test]
+    fn it_classifies_the_cells_of_a_grid() {
+        let cells: Vec<_> = (0..9).with_grid_position(3).map(|(cell, _)| cell.grid_position()).collect();
+
+        assert_eq!(cells, vec![
+            GridPosition::Corner(Corner::TopLeft),
+            GridPosition::Edge(Side::Top),
+            GridPosition::Corner(Corner::TopRight),
+            GridPosition::Edge(Side::Left),
+            GridPosition::Interior,
+            GridPosition::Edge(Side::Right),
+            GridPosition::Corner(Corner::BottomLeft),
+            GridPosition::Edge(Side::Bottom),
+            GridPosition::Corner(Corner::BottomRight),
+        ]);
+    }
+
+    #[test]
+    fn it_ends_a_short_last_row_with_the_last_column() {
+        let cells: Vec<_> = (0..5).with_grid_position(3).map(|(cell, _)| (cell.row, cell.column)).collect();
+
+        assert_eq!(cells[3], (Position::Last, Position::First));
+        assert_eq!(cells[4], (Position::Last, Position::Last));
+    }
+
+    #[test]
+    fn it_marks_row_and_column_positions_of_cells() {
+        let rows = vec![vec!["a", "b"], vec![], vec!["c"]];
+
+        let cells: Vec<_> = rows.into_iter().cells_with_position().collect();
+
+        assert_eq!(cells, vec![
+            (GridCell { row: Position::First, column: Position::First }, "a"),
+            (GridCell { row: Position::First, column: Position::Last }, "b"),
+            (GridCell { row: Position::Last, column: Position::Only }, "c"),
+        ]);
+    }
+}
@@ -0,0 +1,77 @@
+use itertools::{ChunkBy, Group, Groups};
+
+use {Position, PositionIterator, WithPosition};
+
+/// Extension trait for itertools' `ChunkBy` which adds positioned iteration
+/// over the groups.
+pub trait ChunkByWithPosition<'a, K, I, F> where I: Iterator, I::Item: 'a, F: FnMut(&I::Item) -> K, K: PartialEq {
+    /// Iterate over the groups, yielding a tuple of `(Position, K, group)`,
+    /// where the position is the group's position among all groups and the
+    /// group yields its elements with their position inside the group.
+    ///
+    /// Finding out whether a group is the last one means reading the next
+    /// group's first element, so the rest of a group is buffered by itertools
+    /// if it is consumed after the next group has been yielded.
+    ///
+    /// ```
+    /// extern crate itertools;
+    /// # extern crate with_position;
+    /// use itertools::Itertools;
+    /// use with_position::{ChunkByWithPosition, Position};
+    ///
+    /// # fn main() {
+    /// let chunks = vec![1, 1, 2].into_iter().chunk_by(|&n| n);
+    /// let groups: Vec<_> = chunks.groups_with_position().map(|(position, key, group)| (position, key, group.collect::<Vec<_>>())).collect();
+    ///
+    /// assert_eq!(groups, vec![
+    ///     (Position::First, 1, vec![(Position::First, 1), (Position::Last, 1)]),
+    ///     (Position::Last, 2, vec![(Position::Only, 2)]),
+    /// ]);
+    /// # }
+    /// ```
+    fn groups_with_position(&'a self) -> PositionedGroups<'a, K, I, F>;
+}
+
+impl<'a, K, I, F> ChunkByWithPosition<'a, K, I, F> for ChunkBy<K, I, F> where I: Iterator, I::Item: 'a, F: FnMut(&I::Item) -> K, K: PartialEq {
+    fn groups_with_position(&'a self) -> PositionedGroups<'a, K, I, F> {
+        PositionedGroups { iter: self.into_iter().with_position() }
+    }
+}
+
+/// An iterator over the groups of a `ChunkBy` with their positions, created
+/// by `ChunkByWithPosition::groups_with_position`.
+pub struct PositionedGroups<'a, K, I, F> where I: Iterator, I::Item: 'a, F: FnMut(&I::Item) -> K, K: PartialEq {
+    iter: PositionIterator<Groups<'a, K, I, F>>,
+}
+
+impl<'a, K, I, F> Iterator for PositionedGroups<'a, K, I, F> where I: Iterator, I::Item: 'a, F: FnMut(&I::Item) -> K, K: PartialEq {
+    type Item = (Position, K, PositionIterator<Group<'a, K, I, F>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (position, (key, group)) = self.iter.next()?;
+        Some((position, key, group.with_position()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use {ChunkByWithPosition, Position};
+
+    #[test]
+    fn it_yields_groups_consumed_out_of_order() {
+        let chunks = "aab".chars().chunk_by(|&c| c);
+        let groups: Vec<_> = chunks.groups_with_position().collect();
+
+        let result: Vec<_> = groups.into_iter().map(|(position, key, group)| (position, key, group.count())).collect();
+
+        assert_eq!(result, vec![(Position::First, 'a', 2), (Position::Last, 'b', 1)]);
+    }
+
+    #[test]
+    fn it_yields_nothing_for_no_groups() {
+        let chunks = Vec::<u32>::new().into_iter().chunk_by(|&n| n);
+
+        assert!(chunks.groups_with_position().next().is_none());
+    }
+}
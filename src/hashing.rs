@@ -0,0 +1,134 @@
+use std::hash::Hasher;
+use std::iter::FusedIterator;
+
+use WithIsLast;
+
+/// An iterator adapter that feeds every item into a hasher and yields the
+/// items unchanged, created by `WithPosition::hash_items` and
+/// `WithPosition::hash_bytes`. The hash is finished as soon as the last item
+/// has been yielded, so a checksum can be written right after the data
+/// without a second pass.
+///
+/// The digest is only as stable as the hasher. The algorithm of the standard
+/// library's `DefaultHasher` may change between Rust releases, so a checksum
+/// which is stored or compared by another program needs a hasher with a fixed
+/// algorithm, such as FNV-1a below.
+///
+/// ```
+/// use std::hash::Hasher;
+/// use with_position::WithPosition;
+///
+/// struct Fnv1a(u64);
+///
+/// impl Hasher for Fnv1a {
+///     fn write(&mut self, bytes: &[u8]) {
+///         for &byte in bytes {
+///             self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x100000001b3);
+///         }
+///     }
+///
+///     fn finish(&self) -> u64 {
+///         self.0
+///     }
+/// }
+///
+/// let mut rows = vec!["a\n", "b\n"].into_iter().hash_bytes(Fnv1a(0xcbf29ce484222325));
+/// let mut output = String::new();
+/// for row in &mut rows {
+///     output.push_str(row);
+/// }
+///
+/// assert_eq!(output, "a\nb\n");
+/// assert_eq!(rows.digest(), Some(0x78ed6781f136a14e));
+/// ```
+pub struct HashItems<I, H, F> where I: Iterator {
+    iter: WithIsLast<I>,
+    hasher: H,
+    feed: F,
+    digest: Option<u64>,
+}
+
+impl<I, H, F> HashItems<I, H, F> where I: Iterator, H: Hasher, F: FnMut(&mut H, &I::Item) {
+    pub(crate) fn new(iter: I, hasher: H, feed: F) -> HashItems<I, H, F> {
+        HashItems { iter: WithIsLast::new(iter), hasher, feed, digest: None }
+    }
+
+    /// Return the hash of all items once the last one has been yielded, or
+    /// once the iterator has been found to be empty, and `None` before that.
+    pub fn digest(&self) -> Option<u64> {
+        self.digest
+    }
+
+    /// Return the hash of the items yielded so far, without waiting for the
+    /// last item.
+    pub fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+}
+
+impl<I, H, F> Iterator for HashItems<I, H, F> where I: Iterator, H: Hasher, F: FnMut(&mut H, &I::Item) {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        match self.iter.next() {
+            Some((is_last, item)) => {
+                (self.feed)(&mut self.hasher, &item);
+                if is_last {
+                    self.digest = Some(self.hasher.finish());
+                }
+                Some(item)
+            }
+            None => {
+                if self.digest.is_none() {
+                    self.digest = Some(self.hasher.finish());
+                }
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, H, F> FusedIterator for HashItems<I, H, F> where I: FusedIterator, H: Hasher, F: FnMut(&mut H, &I::Item) {}
+
+/// The adapter returned by `WithPosition::hash_bytes`.
+pub type HashBytes<I, H> = HashItems<I, H, fn(&mut H, &<I as Iterator>::Item)>;
+
+pub(crate) fn write_bytes<H, T>(hasher: &mut H, item: &T) where H: Hasher, T: AsRef<[u8]> {
+    hasher.write(item.as_ref());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::Hasher;
+    use WithPosition;
+
+    #[test]
+    fn it_finishes_the_digest_when_the_last_item_is_yielded() {
+        let mut iter = vec![1u32, 2].into_iter().hash_items(DefaultHasher::new(), |hasher, &n| hasher.write_u32(n));
+
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.digest(), None);
+        let partial = iter.finish();
+        assert_eq!(iter.next(), Some(2));
+
+        let mut expected = DefaultHasher::new();
+        expected.write_u32(1);
+        expected.write_u32(2);
+        assert_eq!(iter.digest(), Some(expected.finish()));
+        assert_ne!(iter.digest(), Some(partial));
+    }
+
+    #[test]
+    fn it_has_a_digest_for_no_items_once_exhausted() {
+        let mut iter = Vec::<String>::new().into_iter().hash_bytes(DefaultHasher::new());
+
+        assert_eq!(iter.digest(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.digest(), Some(DefaultHasher::new().finish()));
+    }
+}
@@ -0,0 +1,110 @@
+use std::slice;
+
+use {ExactPositionIterator, SliceWithPosition};
+
+/// A view of an undo history, where a number of entries from the start have
+/// been applied and the entries after them can be redone.
+///
+/// The applied entries are positioned from the oldest one, which is `First`,
+/// to the current one, which is `Last`, and the redoable entries are
+/// positioned among themselves, so both lists can be drawn with separators.
+///
+/// ```
+/// use with_position::{Position, UndoHistory};
+///
+/// let edits = ["open", "type", "delete", "paste"];
+/// let history = UndoHistory::new(&edits, 3);
+///
+/// assert_eq!(history.applied().last(), Some((Position::Last, &"delete")));
+/// assert_eq!(history.redoable().collect::<Vec<_>>(), vec![(Position::Only, &"paste")]);
+/// assert!(history.can_undo());
+/// assert!(history.can_redo());
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct UndoHistory<'a, T> {
+    entries: &'a [T],
+    applied: usize,
+}
+
+impl<'a, T> UndoHistory<'a, T> {
+    /// Create a view of the entries where the first `applied` entries have
+    /// been applied. Zero means that everything has been undone.
+    ///
+    /// Panics if `applied` is greater than the number of entries.
+    pub fn new(entries: &'a [T], applied: usize) -> UndoHistory<'a, T> {
+        assert!(applied <= entries.len(), "applied count out of bounds");
+        UndoHistory { entries, applied }
+    }
+
+    /// Return the current entry, which is the most recently applied one, or
+    /// `None` if no entry is applied.
+    pub fn current(&self) -> Option<&'a T> {
+        self.applied.checked_sub(1).map(|index| &self.entries[index])
+    }
+
+    /// Iterate over the applied entries, from the oldest one to the current
+    /// one, yielding a tuple of `(Position, &entry)`.
+    pub fn applied(&self) -> ExactPositionIterator<slice::Iter<'a, T>> {
+        self.entries[..self.applied].iter_with_position()
+    }
+
+    /// Iterate over the entries after the current one, which can be redone,
+    /// yielding a tuple of `(Position, &entry)` with positions among them.
+    pub fn redoable(&self) -> ExactPositionIterator<slice::Iter<'a, T>> {
+        self.entries[self.applied..].iter_with_position()
+    }
+
+    /// Return true if there is an applied entry to undo.
+    pub fn can_undo(&self) -> bool {
+        self.applied > 0
+    }
+
+    /// Return true if there is an entry after the current one to redo.
+    pub fn can_redo(&self) -> bool {
+        self.applied < self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Position, UndoHistory};
+
+    #[test]
+    fn it_undoes_the_oldest_entry() {
+        let history = UndoHistory::new(&[1, 2], 1);
+
+        assert_eq!(history.current(), Some(&1));
+        assert_eq!(history.applied().collect::<Vec<_>>(), vec![(Position::Only, &1)]);
+        assert!(history.can_undo());
+        assert!(history.can_redo());
+    }
+
+    #[test]
+    fn it_represents_a_fully_undone_history() {
+        let history = UndoHistory::new(&[1, 2], 0);
+
+        assert_eq!(history.current(), None);
+        assert_eq!(history.applied().count(), 0);
+        assert_eq!(history.redoable().collect::<Vec<_>>(), vec![(Position::First, &1), (Position::Last, &2)]);
+        assert!(!history.can_undo());
+        assert!(history.can_redo());
+    }
+
+    #[test]
+    #[should_panic(expected = "applied count out of bounds")]
+    fn it_panics_when_more_entries_are_applied_than_exist() {
+        UndoHistory::new(&[1, 2], 3);
+    }
+
+    #[test]
+    fn it_handles_the_newest_and_an_empty_history() {
+        let history = UndoHistory::new(&[1, 2, 3], 3);
+        assert_eq!(history.applied().map(|(position, _)| position).collect::<Vec<_>>(), vec![Position::First, Position::Middle, Position::Last]);
+        assert!(history.can_undo());
+        assert!(!history.can_redo());
+
+        let empty = UndoHistory::<u8>::new(&[], 0);
+        assert_eq!(empty.current(), None);
+        assert!(!empty.can_undo() && !empty.can_redo());
+    }
+}
@@ -0,0 +1,182 @@
+use std::iter::FusedIterator;
+
+use {WithIsFirst, WithIsLast};
+
+/// An iterator adapter that calls a closure once just before the first item is
+/// yielded, created by `WithPosition::on_first`.
+pub struct OnFirst<I, F> {
+    iter: WithIsFirst<I>,
+    f: Option<F>,
+}
+
+impl<I, F> OnFirst<I, F> where I: Iterator, F: FnOnce() {
+    pub(crate) fn new(iter: I, f: F) -> OnFirst<I, F> {
+        OnFirst { iter: WithIsFirst::new(iter), f: Some(f) }
+    }
+}
+
+impl<I, F> Iterator for OnFirst<I, F> where I: Iterator, F: FnOnce() {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let (is_first, item) = self.iter.next()?;
+        if is_first {
+            if let Some(f) = self.f.take() {
+                f();
+            }
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, F> FusedIterator for OnFirst<I, F> where I: FusedIterator, F: FnOnce() {}
+
+/// An iterator adapter that calls a closure once after the last item, when
+/// `next` returns `None`, created by `WithPosition::on_last`. Since the
+/// closure runs only once the consumer has handled the last item and asked
+/// for another one, nothing is read ahead.
+pub struct OnLast<I, F> {
+    iter: I,
+    f: Option<F>,
+    did_iter: bool,
+}
+
+impl<I, F> OnLast<I, F> where I: Iterator, F: FnOnce() {
+    pub(crate) fn new(iter: I, f: F) -> OnLast<I, F> {
+        OnLast { iter, f: Some(f), did_iter: false }
+    }
+}
+
+impl<I, F> Iterator for OnLast<I, F> where I: Iterator, F: FnOnce() {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let next = self.iter.next();
+        match next {
+            Some(_) => self.did_iter = true,
+            None if self.did_iter => {
+                if let Some(f) = self.f.take() {
+                    f();
+                }
+            }
+            None => {}
+        }
+        next
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, F> FusedIterator for OnLast<I, F> where I: FusedIterator, F: FnOnce() {}
+
+/// An iterator adapter that passes a reference to the first item to a closure,
+/// and yields the items unchanged, created by `WithPosition::tap_first`.
+pub struct TapFirst<I, F> {
+    iter: WithIsFirst<I>,
+    f: Option<F>,
+}
+
+impl<I, F> TapFirst<I, F> where I: Iterator, F: FnOnce(&I::Item) {
+    pub(crate) fn new(iter: I, f: F) -> TapFirst<I, F> {
+        TapFirst { iter: WithIsFirst::new(iter), f: Some(f) }
+    }
+}
+
+impl<I, F> Iterator for TapFirst<I, F> where I: Iterator, F: FnOnce(&I::Item) {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let (is_first, item) = self.iter.next()?;
+        if is_first {
+            if let Some(f) = self.f.take() {
+                f(&item);
+            }
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, F> FusedIterator for TapFirst<I, F> where I: FusedIterator, F: FnOnce(&I::Item) {}
+
+/// An iterator adapter that passes a reference to the last item to a closure,
+/// and yields the items unchanged, created by `WithPosition::tap_last`.
+pub struct TapLast<I, F> where I: Iterator {
+    iter: WithIsLast<I>,
+    f: Option<F>,
+}
+
+impl<I, F> TapLast<I, F> where I: Iterator, F: FnOnce(&I::Item) {
+    pub(crate) fn new(iter: I, f: F) -> TapLast<I, F> {
+        TapLast { iter: WithIsLast::new(iter), f: Some(f) }
+    }
+}
+
+impl<I, F> Iterator for TapLast<I, F> where I: Iterator, F: FnOnce(&I::Item) {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let (is_last, item) = self.iter.next()?;
+        if is_last {
+            if let Some(f) = self.f.take() {
+                f(&item);
+            }
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, F> FusedIterator for TapLast<I, F> where I: FusedIterator, F: FnOnce(&I::Item) {}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::iter;
+    use WithPosition;
+
+    #[test]
+    fn it_calls_the_hooks_around_the_items() {
+        let events = RefCell::new(Vec::new());
+
+        for item in vec!["a", "b"].into_iter().on_first(|| events.borrow_mut().push("open")).on_last(|| events.borrow_mut().push("close")) {
+            events.borrow_mut().push(item);
+        }
+
+        assert_eq!(*events.borrow(), vec!["open", "a", "b", "close"]);
+    }
+
+    #[test]
+    fn it_calls_both_hooks_once_for_a_single_item_and_none_for_no_items() {
+        let calls = RefCell::new(0);
+
+        let count = iter::once(1).on_first(|| *calls.borrow_mut() += 1).on_last(|| *calls.borrow_mut() += 1).count();
+        assert_eq!((count, *calls.borrow()), (1, 2));
+
+        iter::empty::<i32>().on_first(|| *calls.borrow_mut() += 1).on_last(|| *calls.borrow_mut() += 1).for_each(drop);
+        assert_eq!(*calls.borrow(), 2);
+    }
+
+    #[test]
+    fn it_taps_the_first_and_last_items() {
+        let first = Cell::new(None);
+        let last = Cell::new(None);
+
+        let sum: i32 = vec![3, 5, 8].into_iter().tap_first(|&n| first.set(Some(n))).tap_last(|&n| last.set(Some(n))).sum();
+
+        assert_eq!(sum, 16);
+        assert_eq!((first.get(), last.get()), (Some(3), Some(8)));
+    }
+}
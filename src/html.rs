@@ -0,0 +1,62 @@
+use std::fmt;
+
+use Position;
+
+impl Position {
+    /// The CSS classes for an item in this position, `["first"]`, `["last"]`,
+    /// both for `Only` and none for `Middle`.
+    pub fn css_classes(self) -> &'static [&'static str] {
+        match self {
+            Position::First => &["first"],
+            Position::Middle => &[],
+            Position::Last => &["last"],
+            Position::Only => &["first", "last"],
+        }
+    }
+
+    /// Return a value which implements `Display` by writing a `class`
+    /// attribute with the CSS classes for this position, preceded by a space,
+    /// such as ` class="first last"`. Nothing is written for `Middle`, so this
+    /// can be placed directly after the tag name, as in `<li{}>`.
+    pub fn class_attr(self) -> ClassAttr {
+        ClassAttr { position: self }
+    }
+}
+
+/// A `Display` adapter which writes the `class` attribute for a position,
+/// created by `Position::class_attr`.
+#[derive(Copy, Clone, Debug)]
+pub struct ClassAttr {
+    position: Position,
+}
+
+impl fmt::Display for ClassAttr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let classes = self.position.css_classes();
+        if classes.is_empty() {
+            return Ok(());
+        }
+        write!(f, " class=\"{}\"", classes.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Position, WithPosition};
+
+    #[test]
+    fn it_returns_css_classes_for_positions() {
+        assert_eq!(Position::First.css_classes(), ["first"]);
+        assert!(Position::Middle.css_classes().is_empty());
+        assert_eq!(Position::Only.css_classes(), ["first", "last"]);
+    }
+
+    #[test]
+    fn it_writes_class_attributes_for_list_items() {
+        let html: String = vec!["a", "b", "c"].into_iter().with_position()
+            .map(|(position, item)| format!("<li{}>{}</li>", position.class_attr(), item))
+            .collect();
+
+        assert_eq!(html, "<li class=\"first\">a</li><li>b</li><li class=\"last\">c</li>");
+    }
+}
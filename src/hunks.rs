@@ -0,0 +1,139 @@
+use std::fmt::{self, Display, Write};
+use std::slice;
+
+use {Position, PositionIterator, WithPosition};
+
+type CurrentHunk<'a, T> = (Position, &'a Hunk<T>, PositionIterator<slice::Iter<'a, DiffLine<T>>>);
+
+/// A line in a diff hunk.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DiffLine<T> {
+    Context(T),
+    Added(T),
+    Removed(T),
+}
+
+impl<T> DiffLine<T> {
+    /// The prefix of the line in a unified diff.
+    pub fn prefix(&self) -> char {
+        match *self {
+            DiffLine::Context(_) => ' ',
+            DiffLine::Added(_) => '+',
+            DiffLine::Removed(_) => '-',
+        }
+    }
+
+    /// The text of the line, without its prefix.
+    pub fn text(&self) -> &T {
+        match *self {
+            DiffLine::Context(ref text) | DiffLine::Added(ref text) | DiffLine::Removed(ref text) => text,
+        }
+    }
+}
+
+/// A hunk of a diff, with the line numbers at which it starts in the old and
+/// the new file, counting from one.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Hunk<T> {
+    pub old_start: usize,
+    pub new_start: usize,
+    pub lines: Vec<DiffLine<T>>,
+}
+
+impl<T> Hunk<T> {
+    /// The number of lines the hunk covers in the old file.
+    pub fn old_len(&self) -> usize {
+        self.lines.iter().filter(|line| !matches!(**line, DiffLine::Added(_))).count()
+    }
+
+    /// The number of lines the hunk covers in the new file.
+    pub fn new_len(&self) -> usize {
+        self.lines.iter().filter(|line| !matches!(**line, DiffLine::Removed(_))).count()
+    }
+
+    /// The header of the hunk in a unified diff, such as `@@ -1,3 +1,4 @@`.
+    pub fn header(&self) -> String {
+        format!("@@ -{},{} +{},{} @@", self.old_start, self.old_len(), self.new_start, self.new_len())
+    }
+}
+
+/// Iterate over the lines of all hunks, yielding a tuple of
+/// `(hunk position, line position, hunk, line)`, where the hunk position is
+/// the position of the hunk among all hunks and the line position is the
+/// position of the line within its hunk. This makes it possible to emit the
+/// header of a hunk before its first line, and a trailing newline after the
+/// last line of the last hunk.
+pub fn hunk_lines<'a, I, T>(hunks: I) -> HunkLines<'a, I::IntoIter, T> where I: IntoIterator<Item = &'a Hunk<T>>, T: 'a {
+    HunkLines { hunks: hunks.into_iter().with_position(), current: None }
+}
+
+/// An iterator over the positioned lines of hunks, created by `hunk_lines`.
+pub struct HunkLines<'a, I, T> where I: Iterator<Item = &'a Hunk<T>>, T: 'a {
+    hunks: PositionIterator<I>,
+    current: Option<CurrentHunk<'a, T>>,
+}
+
+impl<'a, I, T> Iterator for HunkLines<'a, I, T> where I: Iterator<Item = &'a Hunk<T>>, T: 'a {
+    type Item = (Position, Position, &'a Hunk<T>, &'a DiffLine<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((hunk_position, hunk, ref mut lines)) = self.current {
+                if let Some((line_position, line)) = lines.next() {
+                    return Some((hunk_position, line_position, hunk, line));
+                }
+            }
+            let (hunk_position, hunk) = self.hunks.next()?;
+            self.current = Some((hunk_position, hunk, hunk.lines.iter().with_position()));
+        }
+    }
+}
+
+/// Write the hunks as a unified diff, with a header before each hunk and each
+/// line terminated by a newline.
+pub fn write_unified_diff<'a, W, I, T>(output: &mut W, hunks: I) -> fmt::Result
+    where W: Write, I: IntoIterator<Item = &'a Hunk<T>>, T: Display + 'a
+{
+    for (_, line_position, hunk, line) in hunk_lines(hunks) {
+        if line_position.is_first() {
+            writeln!(output, "{}", hunk.header())?;
+        }
+        writeln!(output, "{}{}", line.prefix(), line.text())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {hunk_lines, write_unified_diff, DiffLine, Hunk, Position};
+
+    fn hunks() -> Vec<Hunk<&'static str>> {
+        vec![
+            Hunk { old_start: 1, new_start: 1, lines: vec![DiffLine::Context("a"), DiffLine::Removed("b"), DiffLine::Added("c")] },
+            Hunk { old_start: 10, new_start: 10, lines: vec![DiffLine::Added("d")] },
+        ]
+    }
+
+    #[test]
+    fn it_marks_positions_of_hunks_and_lines() {
+        let hunks = hunks();
+
+        let result: Vec<_> = hunk_lines(&hunks).map(|(hunk, line, _, text)| (hunk, line, *text.text())).collect();
+
+        assert_eq!(result, vec![
+            (Position::First, Position::First, "a"),
+            (Position::First, Position::Middle, "b"),
+            (Position::First, Position::Last, "c"),
+            (Position::Last, Position::Only, "d"),
+        ]);
+    }
+
+    #[test]
+    fn it_writes_a_unified_diff() {
+        let mut output = String::new();
+
+        write_unified_diff(&mut output, &hunks()).unwrap();
+
+        assert_eq!(output, "@@ -1,2 +1,2 @@\n a\n-b\n+c\n@@ -10,0 +10,1 @@\n+d\n");
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt;
+
+use icu_list::ListFormatter;
+
+use {Position, WithPosition};
+
+/// Extension trait for iterators which adds the `join_locale` method.
+pub trait LocaleJoinWithPosition: Iterator + Sized where Self::Item: fmt::Display {
+    /// Return a value which implements `Display` by writing the items as a
+    /// list using the separators and conjunctions of the locale the formatter
+    /// was created for, such as "a, b, and c" in English or "a, b y c" in
+    /// Spanish. This is the locale aware counterpart to `join_natural`.
+    ///
+    /// The patterns used between the first, middle and last items can depend
+    /// on the items themselves, so the items are formatted into strings first
+    /// and then handed to the formatter as a whole. A single item is written
+    /// as it is.
+    fn join_locale(self, formatter: &ListFormatter) -> LocaleJoin<'_, Self> {
+        LocaleJoin { iter: self, formatter }
+    }
+}
+
+impl<I> LocaleJoinWithPosition for I where I: Iterator, I::Item: fmt::Display {}
+
+/// A `Display` adapter which joins the items of an iterator according to a
+/// locale, created by `LocaleJoinWithPosition::join_locale`. It formats a
+/// clone of the iterator, so it can be formatted more than once.
+pub struct LocaleJoin<'a, I> {
+    iter: I,
+    formatter: &'a ListFormatter,
+}
+
+impl<'a, I> fmt::Display for LocaleJoin<'a, I> where I: Iterator + Clone, I::Item: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut items = Vec::new();
+        for (position, item) in self.iter.clone().with_position() {
+            match position {
+                Position::Only => return item.fmt(f),
+                _ => items.push(item.to_string()),
+            }
+        }
+        fmt::Display::fmt(&self.formatter.format(items.iter()), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use icu_list::options::ListFormatterOptions;
+    use icu_list::ListFormatter;
+    use icu_locale_core::Locale;
+    use LocaleJoinWithPosition;
+
+    fn formatter(locale: &str) -> ListFormatter {
+        let locale: Locale = locale.parse().unwrap();
+        ListFormatter::try_new_and((&locale).into(), ListFormatterOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn it_joins_items_with_english_conjunctions() {
+        let formatter = formatter("en");
+
+        assert_eq!(vec!["a"].into_iter().join_locale(&formatter).to_string(), "a");
+        assert_eq!(format!("{:>3}", vec!["a"].into_iter().join_locale(&formatter)), "  a");
+        assert_eq!(vec!["a", "b"].into_iter().join_locale(&formatter).to_string(), "a and b");
+        assert_eq!(vec![1, 2, 3].into_iter().join_locale(&formatter).to_string(), "1, 2, and 3");
+
+        let join = vec!["a", "b"].into_iter().join_locale(&formatter);
+        assert_eq!(format!("{}; {}", join, join), "a and b; a and b");
+    }
+
+    #[test]
+    fn it_joins_items_with_spanish_conjunctions() {
+        let formatter = formatter("es");
+
+        assert_eq!(vec!["España", "Suiza", "Italia"].into_iter().join_locale(&formatter).to_string(), "España, Suiza e Italia");
+    }
+}
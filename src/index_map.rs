@@ -0,0 +1,92 @@
+use std::hash::BuildHasher;
+use std::ops::RangeBounds;
+
+use indexmap::{map, set, IndexMap, IndexSet};
+
+use ExactPositionIterator;
+
+/// Extension trait for `IndexMap` which adds positioned iteration methods, in
+/// insertion order. Positions are computed from the length of the map.
+pub trait IndexMapWithPosition<K, V> {
+    /// Yield a tuple of `(Position, (&key, &value))` for each entry in the map.
+    fn entries_with_position(&self) -> ExactPositionIterator<map::Iter<'_, K, V>>;
+
+    /// Yield a tuple of `(Position, &key)` for each key in the map.
+    fn keys_with_position(&self) -> ExactPositionIterator<map::Keys<'_, K, V>>;
+
+    /// Yield a tuple of `(Position, &value)` for each value in the map.
+    fn values_with_position(&self) -> ExactPositionIterator<map::Values<'_, K, V>>;
+
+    /// Remove the entries in the given range, yielding a tuple of
+    /// `(Position, (key, value))` with positions relative to the range.
+    fn drain_with_position<R>(&mut self, range: R) -> ExactPositionIterator<map::Drain<'_, K, V>> where R: RangeBounds<usize>;
+}
+
+impl<K, V, S> IndexMapWithPosition<K, V> for IndexMap<K, V, S> where S: BuildHasher {
+    fn entries_with_position(&self) -> ExactPositionIterator<map::Iter<'_, K, V>> {
+        ExactPositionIterator::new(self.iter())
+    }
+
+    fn keys_with_position(&self) -> ExactPositionIterator<map::Keys<'_, K, V>> {
+        ExactPositionIterator::new(self.keys())
+    }
+
+    fn values_with_position(&self) -> ExactPositionIterator<map::Values<'_, K, V>> {
+        ExactPositionIterator::new(self.values())
+    }
+
+    fn drain_with_position<R>(&mut self, range: R) -> ExactPositionIterator<map::Drain<'_, K, V>> where R: RangeBounds<usize> {
+        ExactPositionIterator::new(self.drain(range))
+    }
+}
+
+/// Extension trait for `IndexSet` which adds positioned iteration methods, in
+/// insertion order.
+pub trait IndexSetWithPosition<T> {
+    /// Yield a tuple of `(Position, &item)` for each item in the set.
+    fn iter_with_position(&self) -> ExactPositionIterator<set::Iter<'_, T>>;
+
+    /// Remove the items in the given range, yielding a tuple of
+    /// `(Position, item)` with positions relative to the range.
+    fn drain_with_position<R>(&mut self, range: R) -> ExactPositionIterator<set::Drain<'_, T>> where R: RangeBounds<usize>;
+}
+
+impl<T, S> IndexSetWithPosition<T> for IndexSet<T, S> where S: BuildHasher {
+    fn iter_with_position(&self) -> ExactPositionIterator<set::Iter<'_, T>> {
+        ExactPositionIterator::new(self.iter())
+    }
+
+    fn drain_with_position<R>(&mut self, range: R) -> ExactPositionIterator<set::Drain<'_, T>> where R: RangeBounds<usize> {
+        ExactPositionIterator::new(self.drain(range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::{IndexMap, IndexSet};
+    use {IndexMapWithPosition, IndexSetWithPosition, Position};
+
+    #[test]
+    fn it_marks_positions_in_insertion_order() {
+        let mut map = IndexMap::new();
+        map.insert("b", 2);
+        map.insert("a", 1);
+
+        assert_eq!(map.entries_with_position().collect::<Vec<_>>(), vec![
+            (Position::First, (&"b", &2)),
+            (Position::Last, (&"a", &1)),
+        ]);
+        assert_eq!(map.keys_with_position().next(), Some((Position::First, &"b")));
+        assert_eq!(map.values_with_position().next_back(), Some((Position::Last, &1)));
+    }
+
+    #[test]
+    fn it_drains_with_positions_relative_to_the_range() {
+        let mut set: IndexSet<_> = vec![1, 2, 3, 4].into_iter().collect();
+
+        let drained: Vec<_> = set.drain_with_position(2..).collect();
+
+        assert_eq!(drained, vec![(Position::First, 3), (Position::Last, 4)]);
+        assert_eq!(set.iter_with_position().collect::<Vec<_>>(), vec![(Position::First, &1), (Position::Last, &2)]);
+    }
+}
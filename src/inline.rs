@@ -0,0 +1,12 @@
+#[cfg(feature = "smallvec")]
+use smallvec::SmallVec;
+
+/// The storage used by the windowing, chunking and look-ahead adapters for
+/// the few items they hold at a time. With the `smallvec` feature up to four
+/// items are kept inline instead of being allocated on the heap. It is never
+/// part of a public signature, so the feature does not change any public type.
+#[cfg(not(feature = "smallvec"))]
+pub(crate) type InlineVec<T> = Vec<T>;
+
+#[cfg(feature = "smallvec")]
+pub(crate) type InlineVec<T> = SmallVec<[T; 4]>;
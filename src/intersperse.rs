@@ -0,0 +1,96 @@
+use std::iter::FusedIterator;
+
+use {Position, PositionIterator};
+
+/// The state shared by the intersperse adapters: an item which has been read
+/// from the source, but is held back until the separator before it has been
+/// yielded.
+struct Interspersed<I> where I: Iterator {
+    iter: PositionIterator<I>,
+    pending: Option<I::Item>,
+}
+
+impl<I> Interspersed<I> where I: Iterator {
+    fn next<F>(&mut self, separator: F) -> Option<I::Item> where F: FnOnce(Position) -> I::Item {
+        if let Some(item) = self.pending.take() {
+            return Some(item);
+        }
+        let (position, item) = self.iter.next()?;
+        if position.is_first() {
+            Some(item)
+        } else {
+            self.pending = Some(item);
+            Some(separator(position))
+        }
+    }
+}
+
+/// An iterator adapter that yields the items with a clone of the separator
+/// between them, created by `WithPosition::intersperse_positioned`.
+pub struct IntersperseWithPosition<I> where I: Iterator {
+    inner: Interspersed<I>,
+    separator: I::Item,
+}
+
+impl<I> IntersperseWithPosition<I> where I: Iterator {
+    pub(crate) fn new(iter: PositionIterator<I>, separator: I::Item) -> IntersperseWithPosition<I> {
+        IntersperseWithPosition { inner: Interspersed { iter, pending: None }, separator }
+    }
+}
+
+impl<I> Iterator for IntersperseWithPosition<I> where I: Iterator, I::Item: Clone {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let separator = &self.separator;
+        self.inner.next(|_| separator.clone())
+    }
+}
+
+impl<I> FusedIterator for IntersperseWithPosition<I> where I: FusedIterator, I::Item: Clone {}
+
+/// An iterator adapter that yields the items with a separator between them,
+/// which is made by a closure receiving the position of the following item,
+/// created by `WithPosition::intersperse_positioned_with`.
+pub struct IntersperseWithPositionWith<I, F> where I: Iterator {
+    inner: Interspersed<I>,
+    separator: F,
+}
+
+impl<I, F> IntersperseWithPositionWith<I, F> where I: Iterator, F: FnMut(Position) -> I::Item {
+    pub(crate) fn new(iter: PositionIterator<I>, separator: F) -> IntersperseWithPositionWith<I, F> {
+        IntersperseWithPositionWith { inner: Interspersed { iter, pending: None }, separator }
+    }
+}
+
+impl<I, F> Iterator for IntersperseWithPositionWith<I, F> where I: Iterator, F: FnMut(Position) -> I::Item {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.inner.next(&mut self.separator)
+    }
+}
+
+impl<I, F> FusedIterator for IntersperseWithPositionWith<I, F> where I: FusedIterator, F: FnMut(Position) -> I::Item {}
+
+#[cfg(test)]
+mod tests {
+    use {Position, WithPosition};
+
+    #[test]
+    fn it_intersperses_a_separator_between_items() {
+        let result: Vec<_> = vec!["a", "b", "c"].into_iter().intersperse_positioned(",").collect();
+
+        assert_eq!(result, vec!["a", ",", "b", ",", "c"]);
+        assert_eq!(Some("a").into_iter().intersperse_positioned(",").collect::<Vec<_>>(), vec!["a"]);
+    }
+
+    #[test]
+    fn it_makes_separators_from_the_position_of_the_following_item() {
+        let result: String = vec!["a", "b", "c"].into_iter()
+            .intersperse_positioned_with(|position| if position == Position::Last { " and " } else { ", " })
+            .collect();
+
+        assert_eq!(result, "a, b and c");
+    }
+}
@@ -0,0 +1,273 @@
+use std::io::{self, BufRead, Read};
+use std::iter::FusedIterator;
+use std::ops::Range;
+
+use {Position, PositionIterator, WithPosition};
+
+/// Extension trait for buffered readers which adds the `lines_with_position`
+/// method.
+pub trait BufReadWithPosition: BufRead + Sized {
+    /// Yield an `io::Result<(Position, line)>` for each line of the reader. Like
+    /// `BufRead::lines`, the line terminator is stripped, and a final line
+    /// without a terminating newline is still yielded as the last line.
+    fn lines_with_position(self) -> PositionedLines<Self> {
+        PositionedLines { reader: self, peeked: None, did_iter: false, ends_with_newline: false }
+    }
+}
+
+impl<B> BufReadWithPosition for B where B: BufRead {}
+
+/// An iterator over the positioned lines of a reader, created by
+/// `BufReadWithPosition::lines_with_position`.
+pub struct PositionedLines<B> {
+    reader: B,
+    peeked: Option<Option<io::Result<(String, bool)>>>,
+    did_iter: bool,
+    ends_with_newline: bool,
+}
+
+impl<B> PositionedLines<B> where B: BufRead {
+    /// Whether the most recently yielded line was terminated by a newline. Once
+    /// the last line has been yielded this tells whether the input ended with a
+    /// newline, so a rewriter can preserve it.
+    pub fn ends_with_newline(&self) -> bool {
+        self.ends_with_newline
+    }
+
+    fn read_line(&mut self) -> Option<io::Result<(String, bool)>> {
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) => None,
+            Ok(_) => {
+                let had_newline = line.ends_with('\n');
+                if had_newline {
+                    line.pop();
+                    if line.ends_with('\r') {
+                        line.pop();
+                    }
+                }
+                Some(Ok((line, had_newline)))
+            }
+            Err(error) => Some(Err(error)),
+        }
+    }
+}
+
+impl<B> Iterator for PositionedLines<B> where B: BufRead {
+    type Item = io::Result<(Position, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = match self.peeked.take() {
+            Some(current) => current,
+            None => self.read_line(),
+        };
+        let (line, had_newline) = match current? {
+            Ok(line) => line,
+            Err(error) => return Some(Err(error)),
+        };
+
+        let next = self.read_line();
+        let is_last = next.is_none();
+        self.peeked = Some(next);
+
+        let is_first = !self.did_iter;
+        self.did_iter = true;
+        self.ends_with_newline = had_newline;
+
+        Some(Ok((Position::from_flags(is_first, is_last), line)))
+    }
+}
+
+/// Extension trait for readers which adds the `read_chunks_with_position`
+/// method.
+pub trait ReadWithPosition: Read + Sized {
+    /// Yield an `io::Result<(Position, chunk)>` for each chunk of `size` bytes
+    /// read from the reader. Every chunk is full except possibly the last one,
+    /// which is marked as `Last` even if it is short. If reading fails after
+    /// part of a chunk has been read, that partial chunk is yielded first and
+    /// the error is returned by the following call, so no bytes are lost.
+    ///
+    /// Panics if `size` is zero.
+    fn read_chunks_with_position(self, size: usize) -> PositionedChunks<Self> {
+        assert!(size != 0, "chunk size must be non-zero");
+        PositionedChunks { reader: self, size, peeked: None, error: None, did_iter: false }
+    }
+}
+
+impl<R> ReadWithPosition for R where R: Read {}
+
+/// An iterator over positioned chunks of a reader, created by
+/// `ReadWithPosition::read_chunks_with_position`.
+pub struct PositionedChunks<R> {
+    reader: R,
+    size: usize,
+    peeked: Option<Option<io::Result<Vec<u8>>>>,
+    error: Option<io::Error>,
+    did_iter: bool,
+}
+
+impl<R> PositionedChunks<R> where R: Read {
+    fn read_chunk(&mut self) -> Option<io::Result<Vec<u8>>> {
+        if let Some(error) = self.error.take() {
+            return Some(Err(error));
+        }
+
+        let mut chunk = vec![0; self.size];
+        let mut filled = 0;
+
+        while filled < self.size {
+            match self.reader.read(&mut chunk[filled..]) {
+                Ok(0) => break,
+                Ok(read) => filled += read,
+                Err(ref error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) if filled == 0 => return Some(Err(error)),
+                Err(error) => {
+                    // Yield the bytes read so far, and the error after them.
+                    self.error = Some(error);
+                    break;
+                }
+            }
+        }
+
+        if filled == 0 {
+            None
+        } else {
+            chunk.truncate(filled);
+            Some(Ok(chunk))
+        }
+    }
+}
+
+impl<R> Iterator for PositionedChunks<R> where R: Read {
+    type Item = io::Result<(Position, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = match self.peeked.take() {
+            Some(current) => current,
+            None => self.read_chunk(),
+        };
+        let chunk = match current? {
+            Ok(chunk) => chunk,
+            Err(error) => return Some(Err(error)),
+        };
+
+        let next = self.read_chunk();
+        let is_last = next.is_none();
+        self.peeked = Some(next);
+
+        let is_first = !self.did_iter;
+        self.did_iter = true;
+
+        Some(Ok((Position::from_flags(is_first, is_last), chunk)))
+    }
+}
+
+/// An iterator adapter that yields byte chunks with their position and the
+/// range of bytes they cover in the whole stream, created by
+/// `WithPosition::with_byte_offsets`.
+pub struct ByteOffsets<I> where I: Iterator {
+    iter: PositionIterator<I>,
+    offset: u64,
+}
+
+impl<I> ByteOffsets<I> where I: Iterator, I::Item: AsRef<[u8]> {
+    pub(crate) fn new(iter: I) -> ByteOffsets<I> {
+        ByteOffsets { iter: iter.with_position(), offset: 0 }
+    }
+
+    /// The number of bytes yielded so far, which is the total length of the
+    /// stream once the last chunk has been yielded.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+impl<I> Iterator for ByteOffsets<I> where I: Iterator, I::Item: AsRef<[u8]> {
+    type Item = (Position, Range<u64>, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (position, item) = self.iter.next()?;
+        let start = self.offset;
+        self.offset += item.as_ref().len() as u64;
+        Some((position, start..self.offset, item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> FusedIterator for ByteOffsets<I> where I: Iterator, I::Item: AsRef<[u8]> {}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Cursor, Read};
+    use {BufReadWithPosition, Position, ReadWithPosition, WithPosition};
+
+    struct Broken;
+
+    impl Read for Broken {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("broken"))
+        }
+    }
+
+    #[test]
+    fn it_marks_positions_of_lines() {
+        let mut lines = Cursor::new("one\r\ntwo\nthree").lines_with_position();
+
+        assert_eq!(lines.next().unwrap().unwrap(), (Position::First, String::from("one")));
+        assert_eq!(lines.next().unwrap().unwrap(), (Position::Middle, String::from("two")));
+        assert_eq!(lines.next().unwrap().unwrap(), (Position::Last, String::from("three")));
+        assert!(!lines.ends_with_newline());
+        assert!(lines.next().is_none());
+    }
+
+    #[test]
+    fn it_marks_the_last_line_with_a_trailing_newline() {
+        let mut lines = Cursor::new("only\n").lines_with_position();
+
+        assert_eq!(lines.next().unwrap().unwrap(), (Position::Only, String::from("only")));
+        assert!(lines.ends_with_newline());
+        assert!(lines.next().is_none());
+    }
+
+    #[test]
+    fn it_marks_the_short_final_chunk_as_last() {
+        let chunks: Vec<_> = Cursor::new(vec![1, 2, 3, 4, 5]).read_chunks_with_position(2).map(Result::unwrap).collect();
+
+        assert_eq!(chunks, vec![
+            (Position::First, vec![1, 2]),
+            (Position::Middle, vec![3, 4]),
+            (Position::Last, vec![5]),
+        ]);
+    }
+
+    #[test]
+    fn it_marks_a_full_final_chunk_as_last() {
+        let chunks: Vec<_> = Cursor::new(vec![1, 2]).read_chunks_with_position(2).map(Result::unwrap).collect();
+
+        assert_eq!(chunks, vec![(Position::Only, vec![1, 2])]);
+        assert!(Cursor::new(vec![]).read_chunks_with_position(2).next().is_none());
+    }
+
+    #[test]
+    fn it_yields_a_partial_chunk_before_an_error() {
+        let mut chunks = Cursor::new(vec![1, 2, 3]).chain(Broken).read_chunks_with_position(2);
+
+        assert_eq!(chunks.next().unwrap().unwrap(), (Position::First, vec![1, 2]));
+        assert_eq!(chunks.next().unwrap().unwrap(), (Position::Middle, vec![3]));
+        assert_eq!(chunks.next().unwrap().unwrap_err().kind(), io::ErrorKind::Other);
+    }
+
+    #[test]
+    fn it_tracks_byte_offsets_of_chunks() {
+        let mut chunks = Cursor::new(b"abcdefg".to_vec()).read_chunks_with_position(3).map(|chunk| chunk.unwrap().1).with_byte_offsets();
+
+        assert_eq!(chunks.next(), Some((Position::First, 0..3, b"abc".to_vec())));
+        assert_eq!(chunks.next(), Some((Position::Middle, 3..6, b"def".to_vec())));
+        assert_eq!(chunks.next(), Some((Position::Last, 6..7, b"g".to_vec())));
+        assert_eq!(chunks.offset(), 7);
+        assert_eq!(vec!["héllo"].into_iter().with_byte_offsets().next(), Some((Position::Only, 0..6, "héllo")));
+    }
+}
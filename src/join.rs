@@ -0,0 +1,351 @@
+use std::fmt;
+
+use {Position, Separator, WithPosition};
+
+/// A `Display` adapter which writes the items of an iterator with a separator
+/// between them, created by `WithPosition::positioned_join`.
+pub struct PositionedJoin<I, S> {
+    iter: I,
+    separator: S,
+}
+
+impl<I, S> PositionedJoin<I, S> {
+    pub(crate) fn new(iter: I, separator: S) -> PositionedJoin<I, S> {
+        PositionedJoin { iter, separator }
+    }
+}
+
+impl<I, S> fmt::Display for PositionedJoin<I, S> where I: Iterator + Clone, I::Item: fmt::Display, S: Separator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (position, item) in self.iter.clone().with_position() {
+            if !position.is_first() {
+                self.separator.write_separator(position, f)?;
+            }
+            item.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// A `Display` adapter which joins the items of an iterator the way a list is
+/// written in a sentence, created by `WithPosition::join_natural`. Two items
+/// are joined by just the conjunction, as in "a and b", and longer lists by the
+/// separator, with the conjunction before the last item, as in "a, b, and c".
+/// Like `PositionedJoin`, it formats a clone of the iterator, so it can be
+/// formatted more than once.
+pub struct NaturalJoin<I, S, C> {
+    iter: I,
+    separator: S,
+    conjunction: C,
+    serial_comma: bool,
+}
+
+impl<I, S, C> NaturalJoin<I, S, C> {
+    pub(crate) fn new(iter: I, separator: S, conjunction: C) -> NaturalJoin<I, S, C> {
+        NaturalJoin { iter, separator, conjunction, serial_comma: true }
+    }
+
+    /// Set whether the separator is written before the conjunction in lists of
+    /// three or more items, which is the case by default. Without it the
+    /// output is "a, b and c".
+    pub fn serial_comma(mut self, serial_comma: bool) -> NaturalJoin<I, S, C> {
+        self.serial_comma = serial_comma;
+        self
+    }
+}
+
+impl<I, S, C> fmt::Display for NaturalJoin<I, S, C> where I: Iterator + Clone, I::Item: fmt::Display, S: Separator, C: Separator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, (position, item)) in self.iter.clone().with_position().enumerate() {
+            match position {
+                Position::First | Position::Only => {}
+                Position::Middle => {
+                    self.separator.write_separator(position, f)?;
+                    f.write_str(" ")?;
+                }
+                Position::Last => {
+                    if index > 1 && self.serial_comma {
+                        self.separator.write_separator(position, f)?;
+                    }
+                    f.write_str(" ")?;
+                    self.conjunction.write_separator(position, f)?;
+                    f.write_str(" ")?;
+                }
+            }
+            item.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// A trait for types which are displayed differently depending on their
+/// position in a list, such as items which render an opening bracket when
+/// they come first. Lists of them can be displayed with `display_positioned`.
+pub trait PositionedDisplay {
+    fn fmt_positioned(&self, position: Position, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+impl<T> PositionedDisplay for &T where T: PositionedDisplay + ?Sized {
+    fn fmt_positioned(&self, position: Position, f: &mut fmt::Formatter) -> fmt::Result {
+        (**self).fmt_positioned(position, f)
+    }
+}
+
+/// Return a value which implements `Display` by writing each item with
+/// `PositionedDisplay::fmt_positioned`. The iterator is cloned each time the
+/// value is formatted.
+pub fn display_positioned<I>(iter: I) -> DisplayPositioned<I::IntoIter> where I: IntoIterator, I::Item: PositionedDisplay {
+    DisplayPositioned { iter: iter.into_iter() }
+}
+
+/// A `Display` adapter for items implementing `PositionedDisplay`, created by
+/// `display_positioned`.
+pub struct DisplayPositioned<I> {
+    iter: I,
+}
+
+impl<I> fmt::Display for DisplayPositioned<I> where I: Iterator + Clone, I::Item: PositionedDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_positioned(f, self.iter.clone(), |f, position, item| item.fmt_positioned(position, f))
+    }
+}
+
+/// Write the items of an iterator to a formatter, calling the closure with
+/// the formatter, the position and the item for each of them and stopping at
+/// the first error. This is useful for implementing `Display` or `Debug` for
+/// types which contain a collection.
+///
+/// ```
+/// use std::fmt;
+/// use with_position::write_positioned;
+///
+/// struct Path(Vec<&'static str>);
+///
+/// impl fmt::Display for Path {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         write_positioned(f, &self.0, |f, position, segment| {
+///             if position.is_last() { write!(f, "{}", segment) } else { write!(f, "{}/", segment) }
+///         })
+///     }
+/// }
+///
+/// assert_eq!(Path(vec!["usr", "local", "bin"]).to_string(), "usr/local/bin");
+/// ```
+pub fn write_positioned<I, F>(f: &mut fmt::Formatter, iter: I, mut write: F) -> fmt::Result
+    where I: IntoIterator, F: FnMut(&mut fmt::Formatter, Position, I::Item) -> fmt::Result
+{
+    for (position, item) in iter.into_iter().with_position() {
+        write(f, position, item)?;
+    }
+    Ok(())
+}
+
+/// Render the items as a breadcrumb trail, joined by the separator, as in
+/// "Home > Docs > Page". This is the same as `Breadcrumbs::new(separator)`
+/// without truncation.
+pub fn breadcrumbs<I, S>(iter: I, separator: S) -> String where I: IntoIterator, I::Item: fmt::Display, S: Separator {
+    Breadcrumbs::new(separator).render(iter)
+}
+
+/// Renders breadcrumb trails, optionally truncating long trails by replacing
+/// items in the middle with an ellipsis, while always showing the first and
+/// the last item.
+///
+/// ```
+/// use with_position::Breadcrumbs;
+///
+/// let trail = Breadcrumbs::new(" > ").truncate(3).render(vec!["Home", "Docs", "Guides", "Setup"]);
+///
+/// assert_eq!(trail, "Home > … > Guides > Setup");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Breadcrumbs<'a, S = &'a str> {
+    separator: S,
+    ellipsis: &'a str,
+    max_items: Option<usize>,
+}
+
+impl<'a, S> Breadcrumbs<'a, S> where S: Separator {
+    pub fn new(separator: S) -> Breadcrumbs<'a, S> {
+        Breadcrumbs { separator, ellipsis: "…", max_items: None }
+    }
+
+    /// Show at most `max_items` items, the first one and the ones closest to
+    /// the end. The first and the last item are always shown, even if
+    /// `max_items` is less than two.
+    pub fn truncate(mut self, max_items: usize) -> Breadcrumbs<'a, S> {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Set the text which replaces the hidden items, `…` by default.
+    pub fn ellipsis(mut self, ellipsis: &'a str) -> Breadcrumbs<'a, S> {
+        self.ellipsis = ellipsis;
+        self
+    }
+
+    pub fn render<I>(&self, iter: I) -> String where I: IntoIterator, I::Item: fmt::Display {
+        let items: Vec<String> = iter.into_iter().map(|item| item.to_string()).collect();
+        let max_items = self.max_items.map_or(items.len(), |max_items| max_items.max(2));
+
+        let mut output = String::new();
+        for (index, (position, item)) in items.iter().with_position().enumerate() {
+            let hidden = !position.is_first() && index + max_items <= items.len();
+            if hidden {
+                if index == 1 {
+                    self.write_separator(Position::Middle, &mut output);
+                    output.push_str(self.ellipsis);
+                }
+                continue;
+            }
+            if !position.is_first() {
+                self.write_separator(position, &mut output);
+            }
+            output.push_str(item);
+        }
+        output
+    }
+
+    fn write_separator(&self, position: Position, output: &mut String) {
+        self.separator.write_separator(position, output).expect("writing to a String cannot fail");
+    }
+}
+
+/// Lay out the words onto lines of at most `width` characters, joining the
+/// words on a line with the separator and the lines with newlines. No
+/// separator is written after the last word of a line, and there is no newline
+/// after the last line. Words which are longer than `width` are put on a line
+/// of their own. A separator which depends on the position is measured for
+/// every word.
+pub fn wrap_join<I, S>(words: I, width: usize, separator: S) -> String where I: IntoIterator, I::Item: fmt::Display, S: Separator {
+    let mut output = String::new();
+    let mut separator_text = String::new();
+    let mut line_len = 0;
+    for (position, word) in words.into_iter().with_position() {
+        let word = word.to_string();
+        let word_len = word.chars().count();
+        if !position.is_first() {
+            separator_text.clear();
+            separator.write_separator(position, &mut separator_text).expect("writing to a String cannot fail");
+            let separator_len = separator_text.chars().count();
+            if line_len + separator_len + word_len > width {
+                output.push('\n');
+                line_len = 0;
+            } else {
+                output.push_str(&separator_text);
+                line_len += separator_len;
+            }
+        }
+        output.push_str(&word);
+        line_len += word_len;
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+    use std::iter;
+    use {breadcrumbs, display_positioned, wrap_join, write_positioned, Breadcrumbs, Position, PositionedDisplay, WithPosition};
+
+    #[test]
+    fn it_joins_items_with_a_separator() {
+        assert_eq!(vec![1, 2, 3].into_iter().positioned_join(", ").to_string(), "1, 2, 3");
+        assert_eq!(iter::once("a").positioned_join(", ").to_string(), "a");
+        assert_eq!(iter::empty::<u8>().positioned_join(", ").to_string(), "");
+    }
+
+    #[test]
+    fn it_can_be_formatted_more_than_once() {
+        let join = vec![1, 2].into_iter().positioned_join(", ");
+        let natural = vec!["a", "b"].into_iter().join_natural(",", "and");
+
+        assert_eq!(format!("{} {}", join, join), "1, 2 1, 2");
+        assert_eq!(format!("{} {}", natural, natural), "a and b a and b");
+    }
+
+    #[test]
+    fn it_passes_formatting_options_to_the_items() {
+        assert_eq!(format!("{:>3}", vec![1, 2].into_iter().positioned_join("|")), "  1|  2");
+    }
+
+    #[test]
+    fn it_joins_items_as_a_natural_language_list() {
+        let join = |items: &[&str]| items.iter().join_natural(",", "and").to_string();
+
+        assert_eq!(join(&["a"]), "a");
+        assert_eq!(join(&["a", "b"]), "a and b");
+        assert_eq!(join(&["a", "b", "c"]), "a, b, and c");
+    }
+
+    #[test]
+    fn it_joins_items_without_a_serial_comma() {
+        let result = vec!["a", "b", "c"].into_iter().join_natural(",", "or").serial_comma(false).to_string();
+
+        assert_eq!(result, "a, b or c");
+    }
+
+    #[test]
+    fn it_joins_items_naturally_with_any_separator() {
+        let result = vec![1, 2, 3].into_iter().join_natural(';', format_args!("&")).to_string();
+
+        assert_eq!(result, "1; 2; & 3");
+    }
+
+    #[test]
+    fn it_propagates_errors_from_write_positioned() {
+        struct Failing;
+
+        impl fmt::Display for Failing {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write_positioned(f, 1..4, |f, position, item| {
+                    if position == Position::Middle { Err(fmt::Error) } else { write!(f, "{}", item) }
+                })
+            }
+        }
+
+        let mut output = String::new();
+        assert!(fmt::write(&mut output, format_args!("{}", Failing)).is_err());
+        assert_eq!(output, "1");
+    }
+
+    #[test]
+    fn it_wraps_words_onto_lines() {
+        let text = "the quick brown fox jumps over the lazy dog";
+
+        assert_eq!(wrap_join(text.split(' '), 10, " "), "the quick\nbrown fox\njumps over\nthe lazy\ndog");
+        assert_eq!(wrap_join(vec!["extraordinary", "a"], 5, " "), "extraordinary\na");
+    }
+
+    #[test]
+    fn it_displays_items_depending_on_their_position() {
+        struct Arg(&'static str);
+
+        impl PositionedDisplay for Arg {
+            fn fmt_positioned(&self, position: Position, f: &mut fmt::Formatter) -> fmt::Result {
+                if position.is_first() { f.write_str("(")?; }
+                f.write_str(self.0)?;
+                if position.is_last() { f.write_str(")") } else { f.write_str(", ") }
+            }
+        }
+
+        let args = vec![Arg("a"), Arg("b")];
+
+        assert_eq!(display_positioned(&args).to_string(), "(a, b)");
+    }
+
+    #[test]
+    fn it_renders_breadcrumbs() {
+        assert_eq!(breadcrumbs(vec!["Home", "Docs"], " / "), "Home / Docs");
+        assert_eq!(breadcrumbs(iter::once("Home"), " / "), "Home");
+        assert_eq!(breadcrumbs(vec!["usr", "bin"], '/'), "usr/bin");
+    }
+
+    #[test]
+    fn it_always_shows_the_first_and_last_breadcrumb() {
+        let trail = Breadcrumbs::new(" > ").truncate(0).ellipsis("...").render(vec!["a", "b", "c", "d"]);
+
+        assert_eq!(trail, "a > ... > d");
+        assert_eq!(Breadcrumbs::new(" > ").truncate(4).render(vec!["a", "b", "c", "d"]), "a > b > c > d");
+    }
+}
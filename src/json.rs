@@ -0,0 +1,54 @@
+use std::slice;
+
+use serde_json::{map, Value};
+
+use ExactPositionIterator;
+
+/// If the value is an array, iterate over references to its elements, yielding
+/// a tuple of `(Position, &element)`.
+pub fn json_array_with_position(value: &Value) -> Option<ExactPositionIterator<slice::Iter<'_, Value>>> {
+    value.as_array().map(|array| ExactPositionIterator::new(array.iter()))
+}
+
+/// If the value is an array, iterate over mutable references to its elements,
+/// yielding a tuple of `(Position, &mut element)`.
+pub fn json_array_with_position_mut(value: &mut Value) -> Option<ExactPositionIterator<slice::IterMut<'_, Value>>> {
+    value.as_array_mut().map(|array| ExactPositionIterator::new(array.iter_mut()))
+}
+
+/// If the value is an object, iterate over its entries, yielding a tuple of
+/// `(Position, (&key, &value))`.
+pub fn json_object_with_position(value: &Value) -> Option<ExactPositionIterator<map::Iter<'_>>> {
+    value.as_object().map(|object| ExactPositionIterator::new(object.iter()))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+    use {json_array_with_position, json_array_with_position_mut, json_object_with_position, Position};
+
+    #[test]
+    fn it_marks_positions_of_array_elements() {
+        let mut value: Value = "[1, 2, 3]".parse().unwrap();
+
+        let positions: Vec<_> = json_array_with_position(&value).unwrap().map(|(position, _)| position).collect();
+        assert_eq!(positions, vec![Position::First, Position::Middle, Position::Last]);
+
+        for (position, element) in json_array_with_position_mut(&mut value).unwrap() {
+            if position.is_last() {
+                *element = Value::Null;
+            }
+        }
+        assert_eq!(value.to_string(), "[1,2,null]");
+        assert!(json_array_with_position(&Value::Null).is_none());
+    }
+
+    #[test]
+    fn it_marks_positions_of_object_entries() {
+        let value: Value = r#"{"a": 1}"#.parse().unwrap();
+
+        let entries: Vec<_> = json_object_with_position(&value).unwrap().map(|(position, (key, _))| (position, key.as_str())).collect();
+
+        assert_eq!(entries, vec![(Position::Only, "a")]);
+    }
+}
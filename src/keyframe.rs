@@ -0,0 +1,67 @@
+use std::iter::FusedIterator;
+
+use Position;
+
+/// Extension trait for exact size iterators which adds the `with_keyframes`
+/// method.
+pub trait KeyframeWithPosition: ExactSizeIterator + Sized {
+    /// Yield a tuple of `(Position, t, item)` where `t` is the normalized time
+    /// of the item across the sequence, going from `0.0` for the first item to
+    /// exactly `1.0` for the last one. A single item is both the first and the
+    /// last keyframe, and gets a time of `1.0`.
+    fn with_keyframes(self) -> Keyframes<Self> {
+        let len = self.len();
+        Keyframes { iter: self, len, index: 0 }
+    }
+}
+
+impl<I> KeyframeWithPosition for I where I: ExactSizeIterator {}
+
+/// An iterator adapter that yields tuples of `(Position, t, item)`, created by
+/// `KeyframeWithPosition::with_keyframes`.
+pub struct Keyframes<I> {
+    iter: I,
+    len: usize,
+    index: usize,
+}
+
+impl<I> Iterator for Keyframes<I> where I: ExactSizeIterator {
+    type Item = (Position, f64, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        let position = Position::from_index(self.index, self.len);
+        let t = if position.is_last() { 1.0 } else { self.index as f64 / (self.len - 1) as f64 };
+        self.index += 1;
+        Some((position, t, item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for Keyframes<I> where I: ExactSizeIterator {}
+
+impl<I> FusedIterator for Keyframes<I> where I: ExactSizeIterator + FusedIterator {}
+
+#[cfg(test)]
+mod tests {
+    use {KeyframeWithPosition, Position};
+
+    #[test]
+    fn it_yields_normalized_times_with_exact_endpoints() {
+        let result: Vec<_> = (0..3).with_keyframes().collect();
+
+        assert_eq!(result, vec![(Position::First, 0.0, 0), (Position::Middle, 0.5, 1), (Position::Last, 1.0, 2)]);
+    }
+
+    #[test]
+    fn it_ends_on_exactly_one() {
+        let times: Vec<_> = (0..7).with_keyframes().map(|(_, t, _)| t).collect();
+
+        assert_eq!(times.first(), Some(&0.0));
+        assert_eq!(times.last(), Some(&1.0));
+        assert_eq!((0..1).with_keyframes().next(), Some((Position::Only, 1.0, 0)));
+    }
+}
@@ -19,16 +19,359 @@
 //! assert_eq!(result[1].0.is_first(), false);
 //! ```
 
-use std::iter::Peekable;
+#![cfg_attr(feature = "coroutines", feature(coroutines, coroutine_trait, stmt_expr_attributes))]
+
+#[cfg(feature = "anstyle")]
+extern crate anstyle;
+#[cfg(feature = "askama")]
+extern crate askama;
+#[cfg(all(test, feature = "async-std"))]
+extern crate async_std;
+#[cfg(feature = "bytes")]
+extern crate bytes;
+#[cfg(feature = "chrono")]
+extern crate chrono;
+#[cfg(feature = "crossbeam")]
+extern crate crossbeam_channel;
+#[cfg(feature = "csv")]
+extern crate csv;
+#[cfg(feature = "fallible-iterator")]
+extern crate fallible_iterator;
+#[cfg(all(test, feature = "futures"))]
+extern crate futures;
+#[cfg(feature = "futures")]
+extern crate futures_core;
+#[cfg(feature = "futures")]
+extern crate futures_sink;
+#[cfg(feature = "genawaiter")]
+extern crate genawaiter;
+#[cfg(feature = "icu")]
+extern crate icu_list;
+#[cfg(all(test, feature = "icu"))]
+extern crate icu_locale_core;
+#[cfg(feature = "indexmap")]
+extern crate indexmap;
+#[cfg(feature = "indicatif")]
+extern crate indicatif;
+#[cfg(feature = "itertools")]
+extern crate itertools;
+#[cfg(feature = "wasm")]
+extern crate js_sys;
+#[cfg(feature = "log")]
+#[macro_use]
+extern crate log;
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
+#[cfg(feature = "futures")]
+#[macro_use]
+extern crate pin_project_lite;
+#[cfg(any(feature = "quote", feature = "syn"))]
+extern crate proc_macro2;
+#[cfg(feature = "pyo3")]
+extern crate pyo3;
+#[cfg(feature = "quote")]
+extern crate quote;
+#[cfg(feature = "ratatui")]
+extern crate ratatui;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "rusqlite")]
+extern crate rusqlite;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(any(feature = "serde_json", all(test, feature = "serde")))]
+extern crate serde_json;
+#[cfg(feature = "smallvec")]
+extern crate smallvec;
+#[cfg(feature = "streaming-iterator")]
+extern crate streaming_iterator;
+#[cfg(feature = "syn")]
+extern crate syn;
+#[cfg(feature = "tera")]
+extern crate tera;
+#[cfg(feature = "tokio")]
+extern crate tokio;
+#[cfg(feature = "tokio")]
+extern crate tokio_stream;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "unicode")]
+extern crate unicode_segmentation;
+#[cfg(feature = "wasm")]
+extern crate wasm_bindgen;
+
+use std::hash::{Hash, Hasher};
+use std::iter::{Cloned, Copied, FusedIterator, Peekable, Rev};
 use std::cell::Cell;
 
-/// An enum which indicates the position of an item in an iteration.
+#[macro_use]
+mod macros;
+
+mod array;
+#[cfg(feature = "ndarray")]
+mod axes;
+mod batcher;
+mod bracket;
+mod buckets;
+#[cfg(feature = "bytes")]
+mod buf;
+mod c_api;
+mod channel;
+mod chunks;
+mod coalesce;
+mod collections;
+#[cfg(feature = "coroutines")]
+mod coroutine;
+#[cfg(feature = "crossbeam")]
+mod crossbeam;
+#[cfg(feature = "csv")]
+mod csv_reader;
+mod cycle;
+mod debug;
+mod diff;
+mod dot;
+mod error;
+mod exact;
+#[cfg(feature = "fallible-iterator")]
+mod fallible;
+mod flags;
+mod framing;
+#[cfg(feature = "genawaiter")]
+mod generator;
+mod grid;
+#[cfg(feature = "itertools")]
+mod groups;
+mod hashing;
+mod history;
+mod hooks;
+mod html;
+mod hunks;
+#[cfg(feature = "icu")]
+mod i18n;
+#[cfg(feature = "indexmap")]
+mod index_map;
+mod inline;
+mod intersperse;
+mod io;
+mod join;
+#[cfg(feature = "serde_json")]
+mod json;
+mod keyframe;
+mod links;
+mod list;
+mod logging;
+mod map_at;
+mod markup;
+mod merge_join;
+mod non_empty;
+mod observer;
+mod once;
+mod option;
+mod pagination;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod peek;
+#[cfg(feature = "chrono")]
+mod period;
+mod positioned;
+mod predicate;
+#[cfg(feature = "indicatif")]
+mod progress;
+#[cfg(feature = "syn")]
+mod punctuated;
+#[cfg(feature = "pyo3")]
+mod python;
+mod queue;
+mod range;
+mod reducer;
+mod repeat;
+mod result;
+mod retry;
+mod scoped;
+mod segments;
+mod separator;
+#[cfg(feature = "serde")]
+mod serialize;
+mod skip_last;
+mod slice;
+mod span;
+mod split_last;
+mod sql;
+#[cfg(feature = "rusqlite")]
+mod sqlite;
+mod stats;
+#[cfg(feature = "futures")]
+mod stream;
+#[cfg(feature = "streaming-iterator")]
+mod streaming;
+mod string;
+mod successors;
+#[cfg(feature = "anstyle")]
+mod styled;
+mod table;
+mod template;
+mod timed;
+#[cfg(feature = "quote")]
+mod tokens;
+#[cfg(feature = "tokio")]
+mod tokio_streams;
+#[cfg(feature = "tracing")]
+mod traced;
+mod trailing;
+mod transitions;
+mod tree;
+#[cfg(feature = "ratatui")]
+mod tui;
+#[cfg(feature = "unicode")]
+mod unicode;
+mod unique;
+mod vec;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod windows;
+mod writer;
+mod zip;
+
+pub use array::{positions_of, FixedArrayWithPosition};
+#[cfg(feature = "ndarray")]
+pub use axes::ArrayWithPosition;
+pub use batcher::Batcher;
+pub use bracket::{PrefixWith, Surround, TerminateWith};
+pub use buckets::BucketPositions;
+#[cfg(feature = "bytes")]
+pub use buf::{BufChunks, BufWithPosition};
+pub use c_api::{drive_positioned, PositionedCallback};
+pub use channel::PositionedReceiver;
+pub use chunks::{ArrayChunksWithPosition, Chunk, ChunksExactWithPosition, RChunksWithPosition};
+pub use coalesce::CoalescePositioned;
+pub use collections::{BTreeMapWithPosition, BinaryHeapWithPosition, Deque, DequeDrain, DequeWithPosition};
+#[cfg(feature = "coroutines")]
+pub use coroutine::{coroutine_with_position, coroutine_with_position_resume, CoroutineIter};
+#[cfg(feature = "crossbeam")]
+pub use crossbeam::PositionedCrossbeamReceiver;
+#[cfg(feature = "csv")]
+pub use csv_reader::CsvReaderWithPosition;
+pub use cycle::CycleWithPosition;
+pub use debug::DebugPositions;
+pub use diff::{diff_with_position, Change};
+pub use dot::DotGraph;
+pub use error::{PositionedError, TryMapWithPosition};
+pub use exact::ExactPositionIterator;
+#[cfg(feature = "fallible-iterator")]
+pub use fallible::{FallibleWithPosition, PositionFallibleIterator};
+pub use flags::{WithIsFirst, WithIsLast};
+pub use framing::{write_chunked, FrameWriter};
+#[cfg(all(feature = "genawaiter", feature = "futures"))]
+pub use generator::gen_stream_with_position;
+#[cfg(feature = "genawaiter")]
+pub use generator::{gen_with_position, GenIter};
+pub use grid::{CellPositions, Corner, GridCell, GridPosition, GridPositionIterator, GridWithPosition, RowsWithPosition, Side};
+#[cfg(feature = "itertools")]
+pub use groups::{ChunkByWithPosition, PositionedGroups};
+pub use hashing::{HashBytes, HashItems};
+pub use history::UndoHistory;
+pub use hooks::{OnFirst, OnLast, TapFirst, TapLast};
+pub use html::ClassAttr;
+pub use hunks::{hunk_lines, write_unified_diff, DiffLine, Hunk, HunkLines};
+#[cfg(feature = "icu")]
+pub use i18n::{LocaleJoin, LocaleJoinWithPosition};
+#[cfg(feature = "indexmap")]
+pub use index_map::{IndexMapWithPosition, IndexSetWithPosition};
+pub use intersperse::{IntersperseWithPosition, IntersperseWithPositionWith};
+pub use io::{BufReadWithPosition, ByteOffsets, PositionedChunks, PositionedLines, ReadWithPosition};
+pub use join::{breadcrumbs, display_positioned, wrap_join, write_positioned, Breadcrumbs, DisplayPositioned, NaturalJoin, PositionedDisplay, PositionedJoin};
+#[cfg(feature = "serde_json")]
+pub use json::{json_array_with_position, json_array_with_position_mut, json_object_with_position};
+pub use keyframe::{KeyframeWithPosition, Keyframes};
+pub use links::{link_neighbors, Linked};
+pub use list::{render_html_entries, render_html_list, render_markdown_entries, render_markdown_list, ListEntry, ListKind};
+pub use logging::{LogProgress, LogProgressWithPosition, ProgressReport};
+pub use map_at::MapAt;
+pub use markup::Element;
+pub use merge_join::{merge_join_with_position, MergeJoin};
+pub use non_empty::{EmptyIter, NonEmptyPositionIterator};
+pub use observer::PositionObserver;
+pub use once::{empty_with_position, once_with_position, EmptyWithPosition, OnceWithPosition};
+pub use option::{OptionWithPosition, PositionSome};
+pub use pagination::{pagination, PageNav, PageToken};
+#[cfg(feature = "rayon")]
+pub use parallel::{collect_in_order, ParallelBridgeWithPosition, ParallelPositionIterator, ParallelWithPosition};
+pub use peek::{PeekLast, PeekLastWithPosition, PositionPeek};
+#[cfg(feature = "chrono")]
+pub use period::{Period, PeriodPositions, PeriodWithPosition};
+pub use positioned::{Positioned, PositionedVec};
+pub use predicate::{PositionAfterFilter, PositionWhere};
+#[cfg(feature = "indicatif")]
+pub use progress::{PositionProgress, ProgressWithPosition};
+#[cfg(feature = "syn")]
+pub use punctuated::{punctuated_from_positioned, PunctuatedWithPosition};
+#[cfg(feature = "pyo3")]
+pub use python::PyPositionIterator;
+pub use queue::SharedPositionQueue;
+pub use range::{range_with_position, PositionRange, RangePositionIterator};
+pub use reducer::PositionedReducer;
+pub use repeat::{repeat_n_with_position, RepeatNWithPosition};
+pub use result::{PositionOk, PositionTry, ResultWithPosition};
+pub use retry::{retry_schedule, run_with_retries, RetrySchedule};
+pub use scoped::map_chunks_with_position;
+pub use segments::{PathSegments, PathWithPosition};
+pub use separator::Separator;
+#[cfg(feature = "serde")]
+pub use serialize::{HookedSeq, PositionedSeq};
+pub use skip_last::SkipLast;
+pub use slice::SliceWithPosition;
+pub use span::{spans_with_position, Location, Span, SpannedTokens};
+pub use split_last::SplitLast;
+pub use sql::{insert_batches, InsertBatch, InsertBatches, PlaceholderStyle, Placeholders};
+#[cfg(feature = "rusqlite")]
+pub use sqlite::{SqliteRowsWithPosition, StatementWithPosition};
+pub use stats::{CollectStats, PositionStats, PositionStatsWithPosition};
+#[cfg(feature = "futures")]
+pub use stream::{forward_positioned, ForwardPositioned, PositionStream, StreamWithPositionExt};
+#[cfg(feature = "streaming-iterator")]
+pub use streaming::{PositionStreamingIterator, StreamingWithPosition};
+pub use string::StrWithPosition;
+pub use successors::successors_with_position;
+#[cfg(feature = "anstyle")]
+pub use styled::LineStyles;
+pub use table::{render_table, TableCharset};
+#[cfg(feature = "askama")]
+pub use template::askama_filters;
+#[cfg(feature = "tera")]
+pub use template::register_tera_filters;
+pub use template::{template_items, TemplateItem, TemplateItems};
+pub use timed::{TimeWindow, TimeWindowWithPosition, TimeWindows};
+#[cfg(feature = "quote")]
+pub use tokens::join_tokens;
+#[cfg(feature = "tokio")]
+pub use tokio_streams::{interval_with_position, receiver_with_position, unbounded_receiver_with_position, AsyncBufReadWithPosition, PositionedAsyncLines, PositionedTokioReceiver, PositionTimeoutStream, RecvPositioned, StreamTimeoutWithPosition};
+#[cfg(feature = "tracing")]
+pub use traced::{PositionTraced, TracedWithPosition};
+pub use trailing::{AppendSummary, EnsureTrailing, ReplaceLast};
+pub use transitions::{Transition, Transitions, TransitionsWithPosition};
+pub use tree::{bfs_with_position, dfs_forest_with_position, dfs_with_position, AncestorFlags, BfsWithPosition, DfsWithPosition, Outline, OutlineNumber, TreeCharset, TreeEvent, TreeEvents, TreePrefixes};
+#[cfg(feature = "ratatui")]
+pub use tui::ListItemStyles;
+#[cfg(feature = "unicode")]
+pub use unicode::UnicodeWithPosition;
+pub use unique::{DedupByKeyWithPosition, UniqueWithPosition};
+pub use vec::VecWithPosition;
+#[cfg(feature = "wasm")]
+pub use wasm::JsPositionIterator;
+pub use windows::{Frame, OverlappingWindows, OverlappingWindowsWithPosition, Rolling, RollingWithPosition, WindowPhase};
+pub use writer::{CsvWriter, JsonArrayWriter, ListWriter};
+pub use zip::{EitherOrBoth, ZipLongest};
+
+/// An enum which indicates the position of an item in an iteration. It is
+/// represented as a `u8`, with the discriminants in declaration order from
+/// zero, so that it can be passed across FFI boundaries.
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(u8)]
 pub enum Position {
-    First,
-    Middle,
-    Last,
-    Only,
+    First = 0,
+    Middle = 1,
+    Last = 2,
+    Only = 3,
 }
 
 impl Position {
@@ -43,41 +386,104 @@ impl Position {
     pub fn is_only(self) -> bool {
         self == Position::Only
     }
+
+    /// Return the lowercase name of the position, `"first"`, `"middle"`,
+    /// `"last"` or `"only"`. This is how positions are represented outside of
+    /// Rust, such as in JSON, in templates and in JavaScript and Python.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Position::First => "first",
+            Position::Middle => "middle",
+            Position::Last => "last",
+            Position::Only => "only",
+        }
+    }
+
+    const fn from_index(index: usize, len: usize) -> Position {
+        Position::from_flags(index == 0, index + 1 == len)
+    }
+
+    const fn from_flags(is_first: bool, is_last: bool) -> Position {
+        match (is_first, is_last) {
+            (true, true) => Position::Only,
+            (true, false) => Position::First,
+            (false, true) => Position::Last,
+            (false, false) => Position::Middle,
+        }
+    }
 }
 
 /// An iterator adapter that yields tuples where the first element is a Position
-/// and the second is the item.
+/// and the second is the item. It is fused, so after the last item has been
+/// yielded it only returns `None`, even if the source is not fused. With the
+/// `debug_assert_fused` feature it panics if the source yields another item.
 pub struct PositionIterator<T> where T: Iterator {
     iter: Peekable<T>,
     did_iter: Cell<bool>,
+    pending: Option<T::Item>,
+    finished: bool,
+}
+
+impl<T> PositionIterator<T> where T: Iterator {
+    /// Create a positioned iterator from an existing `Peekable`, reusing it
+    /// instead of wrapping it in another one. An item which has already been
+    /// peeked is yielded first.
+    pub fn from_peekable(iter: Peekable<T>) -> PositionIterator<T> {
+        PositionIterator { iter, did_iter: Cell::new(false), pending: None, finished: false }
+    }
+
+    /// Return the underlying `Peekable`, for example to peek at the next item.
+    /// After `PositionPeek::is_next_last` the next item is held by this
+    /// iterator, so the `Peekable` peeks at the item after it.
+    pub fn peekable_mut(&mut self) -> &mut Peekable<T> {
+        &mut self.iter
+    }
 }
 
 impl<T> Iterator for PositionIterator<T> where T: Iterator {
     type Item = (Position, T::Item);
 
     fn next(&mut self) -> Option<Self::Item> {
+        // Once the last item has been yielded nothing more is yielded, even if
+        // the source is not fused and would yield more items.
+        if self.finished {
+            if cfg!(feature = "debug_assert_fused") && self.iter.next().is_some() {
+                panic!("source iterator yielded an item after the last item");
+            }
+            return None;
+        }
+
         let did_iter = self.did_iter.get();
         self.did_iter.set(true);
 
-        let next = match self.iter.next() {
-            Some(item) => item,
-            None => return None,
+        let next = match self.pending.take() {
+            Some(next) => next,
+            None => match self.iter.next() {
+                Some(next) => next,
+                None => {
+                    self.finished = true;
+                    return None;
+                }
+            },
         };
 
-        if did_iter {
-            match self.iter.peek() {
-                Some(_) => Some((Position::Middle, next)),
-                None => Some((Position::Last, next)),
-            }
-        } else {
-            match self.iter.peek() {
-                Some(_) => Some((Position::First, next)),
-                None => Some((Position::Only, next)),
-            }
+        let is_last = self.iter.peek().is_none();
+        if is_last {
+            // Clear the `None` remembered by the peek, so that the source is
+            // asked directly when checking whether it resumes.
+            self.iter.next();
+            self.finished = true;
         }
+        Some((Position::from_flags(!did_iter, is_last), next))
     }
 }
 
+impl<T> FusedIterator for PositionIterator<T> where T: Iterator {}
+
+/// The first item, an iterator over the middle items and the last item, as
+/// returned by `WithPosition::head_body_tail`.
+pub type HeadBodyTail<T> = (Option<T>, std::vec::IntoIter<T>, Option<T>);
+
 /// Extension trait for iterators which adds the `with_position` method
 pub trait WithPosition where {
     type Iterator: Iterator;
@@ -85,19 +491,532 @@ pub trait WithPosition where {
     /// Yield a tuple of `(Position, item)` where position indicates whether this
     /// is the first, middle or last item.
     fn with_position(self) -> PositionIterator<Self::Iterator>;
+
+    /// Collect the items into a vector of `Positioned` structs, which name the
+    /// position and the item instead of pairing them in a tuple.
+    fn collect_positioned(self) -> Vec<Positioned<<Self::Iterator as Iterator>::Item>>;
+
+    /// Split the items into the first item, an iterator over the middle items
+    /// and the last item. When there is only one item it is returned as the
+    /// head, with an empty body and no tail. Since the last item is only known
+    /// once the iterator is exhausted, the items are buffered.
+    fn head_body_tail(self) -> HeadBodyTail<<Self::Iterator as Iterator>::Item>;
+
+    /// Yield a tuple of `(Option<Position>, item)`, where only the items for
+    /// which `counts` returns true are labeled, with their position among each
+    /// other. Other items, such as comments or tombstones, are yielded with
+    /// `None` and are skipped over when looking ahead for the last item.
+    fn with_position_where<F>(self, counts: F) -> PositionWhere<Self::Iterator, F>
+        where F: FnMut(&<Self::Iterator as Iterator>::Item) -> bool;
+
+    /// Yield a tuple of `(Position, item)` for only the items for which the
+    /// predicate returns true, with positions computed among those items. This
+    /// is the same as `filter(predicate).with_position()`, but in a single
+    /// adapter.
+    fn position_after_filter<P>(self, predicate: P) -> PositionAfterFilter<Self::Iterator, P>
+        where P: FnMut(&<Self::Iterator as Iterator>::Item) -> bool;
+
+    /// Call the fallible closure with each item and its position, stopping at
+    /// the first error, which is returned together with the position and the
+    /// index of the item that caused it.
+    fn try_for_each_with_position<F, E>(self, f: F) -> Result<(), PositionedError<E>>
+        where F: FnMut(Position, <Self::Iterator as Iterator>::Item) -> Result<(), E>;
+
+    /// Map each item together with its position using the fallible closure. Any
+    /// error is wrapped in a `PositionedError` which records where it occurred.
+    fn try_map_with_position<F, U, E>(self, f: F) -> TryMapWithPosition<Self::Iterator, F>
+        where F: FnMut(Position, <Self::Iterator as Iterator>::Item) -> Result<U, E>;
+
+    /// Drive the reducer over the items, calling `finish` as soon as the last
+    /// item has been stepped, or `empty` if there are no items.
+    fn reduce_positioned<R>(self, reducer: R) -> R::Output where R: PositionedReducer<<Self::Iterator as Iterator>::Item>;
+
+    /// Peek at the first item and return `EmptyIter` if there is none, or
+    /// otherwise an iterator which is guaranteed to yield at least one
+    /// `(Position, item)` tuple.
+    fn ensure_nonempty(self) -> Result<NonEmptyPositionIterator<Self::Iterator>, EmptyIter>;
+
+    /// Return a value which implements `Display` by writing the items with the
+    /// separator between them, but not after the last one, without collecting
+    /// them into an intermediate `String`. The separator can be any
+    /// `Separator`, such as a `&str` or a closure which picks the separator by
+    /// the position of the following item. Each time the value is formatted
+    /// the items are written from a clone of the iterator, so it implements
+    /// `Display` when the iterator is `Clone`.
+    fn positioned_join<S>(self, separator: S) -> PositionedJoin<Self::Iterator, S> where S: Separator;
+
+    /// Return a value which implements `Display` by writing the items as a
+    /// list in a sentence, such as "a, b, and c", with the given separator and
+    /// conjunction. See `NaturalJoin` for details.
+    fn join_natural<S, C>(self, separator: S, conjunction: C) -> NaturalJoin<Self::Iterator, S, C> where S: Separator, C: Separator;
+
+    /// Collect and sort the items, then yield a tuple of `(Position, item)` in
+    /// sorted order. Positions are computed from the index of each item in the
+    /// sorted buffer, so this also works from the back.
+    fn sorted_with_position(self) -> ExactPositionIterator<std::vec::IntoIter<<Self::Iterator as Iterator>::Item>>
+        where <Self::Iterator as Iterator>::Item: Ord;
+
+    /// Like `sorted_with_position`, but sorts the items by the key returned by
+    /// the closure.
+    fn sorted_by_key_with_position<K, F>(self, f: F) -> ExactPositionIterator<std::vec::IntoIter<<Self::Iterator as Iterator>::Item>>
+        where K: Ord, F: FnMut(&<Self::Iterator as Iterator>::Item) -> K;
+
+    /// Yield a tuple of `(Position, item)` for the first occurrence of each
+    /// item, with positions computed among the unique items, so the last
+    /// position is given to the last unique item rather than to the last item
+    /// of the source. Seen items are remembered in a `HashSet`.
+    fn unique_with_position(self) -> UniqueWithPosition<Self::Iterator>
+        where <Self::Iterator as Iterator>::Item: Clone + Eq + Hash;
+
+    /// Drop each item whose key, as returned by the closure, equals the key of
+    /// the item before it, and yield a tuple of `(Position, item)` for the
+    /// remaining items, with positions computed among them. Only the first
+    /// item of each run of equal keys is kept.
+    fn dedup_by_key_with_position<K, F>(self, key: F) -> DedupByKeyWithPosition<Self::Iterator, K, F>
+        where K: PartialEq, F: FnMut(&<Self::Iterator as Iterator>::Item) -> K;
+
+    /// Yield the items with a clone of the separator between each of them, but
+    /// not after the last one.
+    fn intersperse_positioned(self, separator: <Self::Iterator as Iterator>::Item) -> IntersperseWithPosition<Self::Iterator>
+        where <Self::Iterator as Iterator>::Item: Clone;
+
+    /// Yield the items with a separator between each of them, made by calling
+    /// the closure with the position of the item following the separator.
+    fn intersperse_positioned_with<F>(self, separator: F) -> IntersperseWithPositionWith<Self::Iterator, F>
+        where F: FnMut(Position) -> <Self::Iterator as Iterator>::Item;
+
+    /// Yield the items followed by the terminator, such as a sentinel record or
+    /// an end of stream marker, but only if there were any items.
+    fn terminate_with(self, terminator: <Self::Iterator as Iterator>::Item) -> TerminateWith<Self::Iterator>;
+
+    /// Yield the prefix, such as a header, followed by the items, but only if
+    /// there are any items.
+    fn prefix_with(self, prefix: <Self::Iterator as Iterator>::Item) -> PrefixWith<Self::Iterator>;
+
+    /// Yield the items between the opening and the closing item, such as
+    /// brackets, or nothing at all if there are no items.
+    fn surround(self, open: <Self::Iterator as Iterator>::Item, close: <Self::Iterator as Iterator>::Item) -> Surround<Self::Iterator>;
+
+    /// Map only the first item with the closure, which is also the only item
+    /// if there is just one, and yield the others unchanged.
+    fn map_first<F>(self, f: F) -> MapAt<Self::Iterator, F>
+        where F: FnMut(<Self::Iterator as Iterator>::Item) -> <Self::Iterator as Iterator>::Item;
+
+    /// Map only the last item with the closure, which is also the only item if
+    /// there is just one, and yield the others unchanged.
+    fn map_last<F>(self, f: F) -> MapAt<Self::Iterator, F>
+        where F: FnMut(<Self::Iterator as Iterator>::Item) -> <Self::Iterator as Iterator>::Item;
+
+    /// Map the item with the closure only if it is the only item.
+    fn map_only<F>(self, f: F) -> MapAt<Self::Iterator, F>
+        where F: FnMut(<Self::Iterator as Iterator>::Item) -> <Self::Iterator as Iterator>::Item;
+
+    /// Yield all but the last `n` items, without knowing the length upfront.
+    /// Up to `n + 1` items are buffered while iterating.
+    fn skip_last(self, n: usize) -> SkipLast<Self::Iterator>;
+
+    /// Yield a tuple of `(Position, chunk)` for each full chunk of `n` items,
+    /// with positions computed among the full chunks. The items at the end
+    /// which do not fill a whole chunk are not yielded, but can be retrieved
+    /// with `ChunksExactWithPosition::into_remainder`.
+    ///
+    /// Panics if `n` is zero.
+    fn chunks_exact_with_position(self, n: usize) -> ChunksExactWithPosition<Self::Iterator>;
+
+    /// Group the items into batches, which are limited by size or age with the
+    /// builder methods of `Batcher`, and yield a tuple of `(Position, batch)`.
+    /// The final batch is yielded as `Last` when the source ends, even if it
+    /// is not full.
+    fn batcher(self) -> Batcher<Self::Iterator>;
+
+    /// Like `chunks_exact_with_position`, but yields each full chunk as an
+    /// array of `N` items, so no chunk is allocated on the heap.
+    ///
+    /// Panics if `N` is zero.
+    fn array_chunks_with_position<const N: usize>(self) -> ArrayChunksWithPosition<Self::Iterator, N>;
+
+    /// Yield a tuple of `(Position, chunk)` for chunks of `n` items taken from
+    /// the end of the source, like `slice::rchunks`, so the newest items come
+    /// first while each chunk keeps the order of its items. The last chunk
+    /// yielded may be shorter than `n`.
+    ///
+    /// Panics if `n` is zero.
+    fn rchunks_with_position(self, n: usize) -> RChunksWithPosition<Self::Iterator>
+        where Self::Iterator: DoubleEndedIterator + ExactSizeIterator;
+
+    /// Split off the last item, returning an iterator over the other items
+    /// from which the last item can be retrieved afterwards with
+    /// `SplitLast::into_last`.
+    fn split_last_iter(self) -> SplitLast<Self::Iterator>;
+
+    /// Pass the last item through the closure, which may change it or drop it
+    /// by returning `None`, and yield the other items unchanged.
+    fn replace_last<F>(self, f: F) -> ReplaceLast<Self::Iterator, F>
+        where F: FnOnce(<Self::Iterator as Iterator>::Item) -> Option<<Self::Iterator as Iterator>::Item>;
+
+    /// Yield the items, followed by an extra item made by `make_item` if the
+    /// last item does not satisfy the predicate, for example to make sure that
+    /// the output ends with a newline. Nothing is added if there are no items.
+    fn ensure_trailing<P, M>(self, predicate: P, make_item: M) -> EnsureTrailing<Self::Iterator, P, M>
+        where P: FnOnce(&<Self::Iterator as Iterator>::Item) -> bool, M: FnOnce() -> <Self::Iterator as Iterator>::Item;
+
+    /// Yield the items unchanged while folding them into an accumulator,
+    /// starting from `init`, and then an extra item made from the accumulator
+    /// by `summary`, such as a totals row. Nothing is added if there are no
+    /// items.
+    fn append_summary<A, F, S>(self, init: A, fold: F, summary: S) -> AppendSummary<Self::Iterator, A, F, S>
+        where F: FnMut(A, &<Self::Iterator as Iterator>::Item) -> A, S: FnOnce(A) -> <Self::Iterator as Iterator>::Item;
+
+    /// Merge adjacent items with the closure, which returns the merged item, or
+    /// both items back if they should not be merged, and yield a tuple of
+    /// `(Position, item)` with positions computed among the merged items.
+    fn coalesce_positioned<F>(self, f: F) -> CoalescePositioned<Self::Iterator, F>
+        where F: FnMut(<Self::Iterator as Iterator>::Item, <Self::Iterator as Iterator>::Item)
+            -> Result<<Self::Iterator as Iterator>::Item, (<Self::Iterator as Iterator>::Item, <Self::Iterator as Iterator>::Item)>;
+
+    /// Collect the items into a buffer, so that the last item can be inspected
+    /// with `PeekLast::last_item` before iteration starts. Double ended
+    /// iterators can use `PeekLastWithPosition::peek_last` instead, which does
+    /// not buffer.
+    fn peek_last_buffered(self) -> PeekLast<std::vec::IntoIter<<Self::Iterator as Iterator>::Item>>;
+
+    /// Yield a tuple of `(is_last, item)`, for when only the end of the
+    /// iteration matters, such as for flushing or committing after the last
+    /// item.
+    fn with_is_last(self) -> WithIsLast<Self::Iterator>;
+
+    /// Yield a tuple of `(is_first, item)`, for when only the start of the
+    /// iteration matters, such as for setup before the first item. This does
+    /// not look ahead at all.
+    fn with_is_first(self) -> WithIsFirst<Self::Iterator>;
+
+    /// For iterators over references, yield a tuple of `(Position, item)` with
+    /// a clone of each item. This is the same as `cloned().with_position()`.
+    fn with_position_cloned<'a, U>(self) -> PositionIterator<Cloned<Self::Iterator>>
+        where Self::Iterator: Iterator<Item = &'a U>, U: Clone + 'a;
+
+    /// For iterators over references, yield a tuple of `(Position, item)` with
+    /// a copy of each item. This is the same as `copied().with_position()`.
+    fn with_position_copied<'a, U>(self) -> PositionIterator<Copied<Self::Iterator>>
+        where Self::Iterator: Iterator<Item = &'a U>, U: Copy + 'a;
+
+    /// Iterate from the back, yielding a tuple of `(Position, item)` with
+    /// positions relative to the reversed order, so the last item of the
+    /// source is labelled first. This is the same as `rev().with_position()`.
+    fn rev_with_position(self) -> PositionIterator<Rev<Self::Iterator>> where Self::Iterator: DoubleEndedIterator;
+
+    /// Zip the items with the items of `other` until both have run out,
+    /// yielding a tuple of `(Position, EitherOrBoth)`, so the last position is
+    /// given to the end of the longer sequence. This is useful for side by
+    /// side output where one column runs out before the other.
+    fn zip_longest_with_position<U>(self, other: U) -> PositionIterator<ZipLongest<Self::Iterator, U::IntoIter>> where U: IntoIterator;
+
+    /// Repeat the items forever, yielding a tuple of `(cycle, Position, item)`,
+    /// where the position is relative to each pass over the items and the
+    /// cycle counts the passes from zero. Nothing is yielded if there are no
+    /// items.
+    fn cycle_with_position(self) -> CycleWithPosition<Self::Iterator> where Self::Iterator: Clone;
+
+    /// Call the closure once just before the first item is yielded, for
+    /// example to open a resource, and yield the items unchanged.
+    fn on_first<F>(self, f: F) -> OnFirst<Self::Iterator, F> where F: FnOnce();
+
+    /// Call the closure once after the last item has been handled, when `next`
+    /// returns `None`, for example to close a resource, and yield the items
+    /// unchanged. It is not called if there are no items, or if iteration
+    /// stops before the end.
+    fn on_last<F>(self, f: F) -> OnLast<Self::Iterator, F> where F: FnOnce();
+
+    /// Pass a reference to the first item to the closure, for example to
+    /// record the first timestamp, and yield the items unchanged.
+    fn tap_first<F>(self, f: F) -> TapFirst<Self::Iterator, F> where F: FnOnce(&<Self::Iterator as Iterator>::Item);
+
+    /// Pass a reference to the last item to the closure, for example to record
+    /// the last timestamp, and yield the items unchanged.
+    fn tap_last<F>(self, f: F) -> TapLast<Self::Iterator, F> where F: FnOnce(&<Self::Iterator as Iterator>::Item);
+
+    /// Yield a tuple of `(bucket, Position, item)`, where the bucket is the
+    /// index of the histogram bucket of the key of the item, given the sorted
+    /// edges between the buckets, and the position is the position of the item
+    /// within its bucket. An item whose key equals an edge belongs to the
+    /// bucket after it. The items are expected to be sorted by key, so that
+    /// each bucket is a single run of items.
+    fn with_bucket_position<K, F>(self, edges: Vec<K>, key: F) -> BucketPositions<Self::Iterator, K, F>
+        where K: PartialOrd, F: FnMut(&<Self::Iterator as Iterator>::Item) -> K;
+
+    /// Yield a tuple of `(Position, range, item)` for byte chunks, lines or
+    /// other items which can be viewed as bytes, where the range is the range
+    /// of bytes the item covers in the whole stream. The offsets only count
+    /// the bytes of the items themselves, so line terminators stripped by
+    /// `lines` are not included.
+    fn with_byte_offsets(self) -> ByteOffsets<Self::Iterator> where <Self::Iterator as Iterator>::Item: AsRef<[u8]>;
+
+    /// Yield the items unchanged while feeding each of them into the hasher
+    /// with the closure, and finish the hash as soon as the last item has been
+    /// yielded. See `HashItems` for how to retrieve it.
+    fn hash_items<H, F>(self, hasher: H, feed: F) -> HashItems<Self::Iterator, H, F>
+        where H: Hasher, F: FnMut(&mut H, &<Self::Iterator as Iterator>::Item);
+
+    /// Like `hash_items`, but feeds the bytes of each item into the hasher.
+    fn hash_bytes<H>(self, hasher: H) -> HashBytes<Self::Iterator, H>
+        where H: Hasher, <Self::Iterator as Iterator>::Item: AsRef<[u8]>;
+
+    /// Yield the items unchanged, while recording a trace of their positions
+    /// such as `F M M L`, which can be printed to standard error with the
+    /// label and the number of items when the adapter is dropped by calling
+    /// `DebugPositions::print_on_drop`. This helps to find out why a consumer
+    /// never saw the last item.
+    fn debug_positions(self, label: &str) -> DebugPositions<Self::Iterator>;
 }
 
 impl<T> WithPosition for T where T: Iterator {
     type Iterator = T;
 
     fn with_position(self) -> PositionIterator<T> {
-        PositionIterator { iter: self.peekable(), did_iter: Cell::new(false) }
+        PositionIterator::from_peekable(self.peekable())
+    }
+
+    fn collect_positioned(self) -> Vec<Positioned<T::Item>> {
+        self.with_position().map(Positioned::from).collect()
+    }
+
+    fn head_body_tail(self) -> HeadBodyTail<T::Item> {
+        let mut items: Vec<_> = self.collect();
+        let tail = if items.len() > 1 { items.pop() } else { None };
+        let mut body = items.into_iter();
+        let head = body.next();
+        (head, body, tail)
+    }
+
+    fn with_position_where<F>(self, counts: F) -> PositionWhere<T, F> where F: FnMut(&T::Item) -> bool {
+        PositionWhere::new(self, counts)
+    }
+
+    fn position_after_filter<P>(self, predicate: P) -> PositionAfterFilter<T, P> where P: FnMut(&T::Item) -> bool {
+        PositionAfterFilter::new(self, predicate)
+    }
+
+    fn try_for_each_with_position<F, E>(self, mut f: F) -> Result<(), PositionedError<E>>
+        where F: FnMut(Position, T::Item) -> Result<(), E>
+    {
+        for (index, (position, item)) in self.with_position().enumerate() {
+            f(position, item).map_err(|source| PositionedError { position, index, source })?;
+        }
+        Ok(())
+    }
+
+    fn try_map_with_position<F, U, E>(self, f: F) -> TryMapWithPosition<T, F>
+        where F: FnMut(Position, T::Item) -> Result<U, E>
+    {
+        TryMapWithPosition::new(self.with_position(), f)
+    }
+
+    fn reduce_positioned<R>(self, mut reducer: R) -> R::Output where R: PositionedReducer<T::Item> {
+        let mut acc = None;
+        for (position, item) in self.with_position() {
+            let current = match acc.take() {
+                Some(current) => current,
+                None => reducer.init(),
+            };
+            let next = reducer.step(current, position, item);
+            if position.is_last() {
+                return reducer.finish(next);
+            }
+            acc = Some(next);
+        }
+        reducer.empty()
+    }
+
+    fn ensure_nonempty(self) -> Result<NonEmptyPositionIterator<T>, EmptyIter> {
+        let mut iter = self.with_position();
+        match iter.iter.peek() {
+            Some(_) => Ok(NonEmptyPositionIterator::new(iter)),
+            None => Err(EmptyIter),
+        }
+    }
+
+    fn positioned_join<S>(self, separator: S) -> PositionedJoin<T, S> where S: Separator {
+        PositionedJoin::new(self, separator)
+    }
+
+    fn join_natural<S, C>(self, separator: S, conjunction: C) -> NaturalJoin<T, S, C> where S: Separator, C: Separator {
+        NaturalJoin::new(self, separator, conjunction)
+    }
+
+    fn sorted_with_position(self) -> ExactPositionIterator<std::vec::IntoIter<T::Item>> where T::Item: Ord {
+        let mut items: Vec<_> = self.collect();
+        items.sort();
+        ExactPositionIterator::new(items.into_iter())
+    }
+
+    fn sorted_by_key_with_position<K, F>(self, f: F) -> ExactPositionIterator<std::vec::IntoIter<T::Item>>
+        where K: Ord, F: FnMut(&T::Item) -> K
+    {
+        let mut items: Vec<_> = self.collect();
+        items.sort_by_key(f);
+        ExactPositionIterator::new(items.into_iter())
+    }
+
+    fn unique_with_position(self) -> UniqueWithPosition<T> where T::Item: Clone + Eq + Hash {
+        UniqueWithPosition::new(self)
+    }
+
+    fn dedup_by_key_with_position<K, F>(self, key: F) -> DedupByKeyWithPosition<T, K, F> where K: PartialEq, F: FnMut(&T::Item) -> K {
+        DedupByKeyWithPosition::new(self, key)
+    }
+
+    fn intersperse_positioned(self, separator: T::Item) -> IntersperseWithPosition<T> where T::Item: Clone {
+        IntersperseWithPosition::new(self.with_position(), separator)
+    }
+
+    fn intersperse_positioned_with<F>(self, separator: F) -> IntersperseWithPositionWith<T, F> where F: FnMut(Position) -> T::Item {
+        IntersperseWithPositionWith::new(self.with_position(), separator)
+    }
+
+    fn terminate_with(self, terminator: T::Item) -> TerminateWith<T> {
+        TerminateWith::new(self, terminator)
+    }
+
+    fn prefix_with(self, prefix: T::Item) -> PrefixWith<T> {
+        PrefixWith::new(self, prefix)
+    }
+
+    fn surround(self, open: T::Item, close: T::Item) -> Surround<T> {
+        Surround::new(self, open, close)
+    }
+
+    fn map_first<F>(self, f: F) -> MapAt<T, F> where F: FnMut(T::Item) -> T::Item {
+        MapAt::new(self.with_position(), Position::is_first, f)
+    }
+
+    fn map_last<F>(self, f: F) -> MapAt<T, F> where F: FnMut(T::Item) -> T::Item {
+        MapAt::new(self.with_position(), Position::is_last, f)
+    }
+
+    fn map_only<F>(self, f: F) -> MapAt<T, F> where F: FnMut(T::Item) -> T::Item {
+        MapAt::new(self.with_position(), Position::is_only, f)
+    }
+
+    fn skip_last(self, n: usize) -> SkipLast<T> {
+        SkipLast::new(self, n)
+    }
+
+    fn chunks_exact_with_position(self, n: usize) -> ChunksExactWithPosition<T> {
+        ChunksExactWithPosition::new(self, n)
+    }
+
+    fn batcher(self) -> Batcher<T> {
+        Batcher::new(self)
+    }
+
+    fn array_chunks_with_position<const N: usize>(self) -> ArrayChunksWithPosition<T, N> {
+        ArrayChunksWithPosition::new(self)
+    }
+
+    fn rchunks_with_position(self, n: usize) -> RChunksWithPosition<T> where T: DoubleEndedIterator + ExactSizeIterator {
+        RChunksWithPosition::new(self, n)
+    }
+
+    fn split_last_iter(self) -> SplitLast<T> {
+        SplitLast::new(self)
+    }
+
+    fn replace_last<F>(self, f: F) -> ReplaceLast<T, F> where F: FnOnce(T::Item) -> Option<T::Item> {
+        ReplaceLast::new(self.with_position(), f)
+    }
+
+    fn ensure_trailing<P, M>(self, predicate: P, make_item: M) -> EnsureTrailing<T, P, M>
+        where P: FnOnce(&T::Item) -> bool, M: FnOnce() -> T::Item
+    {
+        EnsureTrailing::new(self.with_position(), predicate, make_item)
+    }
+
+    fn append_summary<A, F, S>(self, init: A, fold: F, summary: S) -> AppendSummary<T, A, F, S>
+        where F: FnMut(A, &T::Item) -> A, S: FnOnce(A) -> T::Item
+    {
+        AppendSummary::new(self, init, fold, summary)
+    }
+
+    fn coalesce_positioned<F>(self, f: F) -> CoalescePositioned<T, F>
+        where F: FnMut(T::Item, T::Item) -> Result<T::Item, (T::Item, T::Item)>
+    {
+        CoalescePositioned::new(self, f)
+    }
+
+    fn peek_last_buffered(self) -> PeekLast<std::vec::IntoIter<T::Item>> {
+        let mut items: Vec<_> = self.collect();
+        let last = items.pop();
+        PeekLast::new(items.into_iter(), last)
+    }
+
+    fn with_is_last(self) -> WithIsLast<T> {
+        WithIsLast::new(self)
+    }
+
+    fn with_is_first(self) -> WithIsFirst<T> {
+        WithIsFirst::new(self)
+    }
+
+    fn with_position_cloned<'a, U>(self) -> PositionIterator<Cloned<T>> where T: Iterator<Item = &'a U>, U: Clone + 'a {
+        self.cloned().with_position()
+    }
+
+    fn with_position_copied<'a, U>(self) -> PositionIterator<Copied<T>> where T: Iterator<Item = &'a U>, U: Copy + 'a {
+        self.copied().with_position()
+    }
+
+    fn rev_with_position(self) -> PositionIterator<Rev<T>> where T: DoubleEndedIterator {
+        self.rev().with_position()
+    }
+
+    fn zip_longest_with_position<U>(self, other: U) -> PositionIterator<ZipLongest<T, U::IntoIter>> where U: IntoIterator {
+        ZipLongest::new(self, other.into_iter()).with_position()
+    }
+
+    fn cycle_with_position(self) -> CycleWithPosition<T> where T: Clone {
+        CycleWithPosition::new(self)
+    }
+
+    fn on_first<F>(self, f: F) -> OnFirst<T, F> where F: FnOnce() {
+        OnFirst::new(self, f)
+    }
+
+    fn on_last<F>(self, f: F) -> OnLast<T, F> where F: FnOnce() {
+        OnLast::new(self, f)
+    }
+
+    fn tap_first<F>(self, f: F) -> TapFirst<T, F> where F: FnOnce(&T::Item) {
+        TapFirst::new(self, f)
+    }
+
+    fn tap_last<F>(self, f: F) -> TapLast<T, F> where F: FnOnce(&T::Item) {
+        TapLast::new(self, f)
+    }
+
+    fn with_bucket_position<K, F>(self, edges: Vec<K>, key: F) -> BucketPositions<T, K, F> where K: PartialOrd, F: FnMut(&T::Item) -> K {
+        BucketPositions::new(self, edges, key)
+    }
+
+    fn with_byte_offsets(self) -> ByteOffsets<T> where T::Item: AsRef<[u8]> {
+        ByteOffsets::new(self)
+    }
+
+    fn hash_items<H, F>(self, hasher: H, feed: F) -> HashItems<T, H, F> where H: Hasher, F: FnMut(&mut H, &T::Item) {
+        HashItems::new(self, hasher, feed)
+    }
+
+    fn hash_bytes<H>(self, hasher: H) -> HashBytes<T, H> where H: Hasher, T::Item: AsRef<[u8]> {
+        HashItems::new(self, hasher, hashing::write_bytes)
+    }
+
+    fn debug_positions(self, label: &str) -> DebugPositions<T> {
+        DebugPositions::new(self, label)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{WithPosition, Position};
+    use super::{PositionIterator, WithPosition, Position};
 
     #[test]
     fn it_marks_first_middle_and_last_position() {
@@ -125,6 +1044,25 @@ mod tests {
     }
 
     #[test]
+    fn it_splits_into_head_body_and_tail() {
+        let (head, body, tail) = vec![1,2,3,4].into_iter().head_body_tail();
+
+        assert_eq!(head, Some(1));
+        assert_eq!(body.collect::<Vec<_>>(), vec![2,3]);
+        assert_eq!(tail, Some(4));
+    }
+
+    #[test]
+    fn it_splits_a_single_item_into_head_only() {
+        let (head, mut body, tail) = vec![1].into_iter().head_body_tail();
+
+        assert_eq!(head, Some(1));
+        assert_eq!(body.next(), None);
+        assert_eq!(tail, None);
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn it_has_boolean_methods_on_position() {
         assert_eq!(Position::First.is_first(), true);
         assert_eq!(Position::Middle.is_first(), false);
@@ -141,4 +1079,81 @@ mod tests {
         assert_eq!(Position::Last.is_only(), false);
         assert_eq!(Position::Only.is_only(), true);
     }
+
+    #[test]
+    fn it_names_positions() {
+        let names: Vec<_> = vec![Position::First, Position::Middle, Position::Last, Position::Only].into_iter().map(Position::as_str).collect();
+
+        assert_eq!(names, vec!["first", "middle", "last", "only"]);
+    }
+
+    #[test]
+    fn it_yields_sorted_items_with_positions() {
+        let result: Vec<_> = vec![3, 1, 2].into_iter().sorted_with_position().collect();
+        assert_eq!(result, vec![(Position::First, 1), (Position::Middle, 2), (Position::Last, 3)]);
+
+        let mut by_length = vec!["ccc", "a", "bb"].into_iter().sorted_by_key_with_position(|word| word.len());
+        assert_eq!(by_length.next_back(), Some((Position::Last, "ccc")));
+        assert_eq!(by_length.next(), Some((Position::First, "a")));
+    }
+
+    #[test]
+    fn it_yields_cloned_and_copied_items_with_positions() {
+        let words = [String::from("a"), String::from("b")];
+        let result: Vec<_> = words.iter().with_position_cloned().collect();
+        assert_eq!(result, vec![(Position::First, String::from("a")), (Position::Last, String::from("b"))]);
+
+        let result: Vec<_> = [1, 2, 3].iter().with_position_copied().collect();
+        assert_eq!(result, vec![(Position::First, 1), (Position::Middle, 2), (Position::Last, 3)]);
+    }
+
+    #[test]
+    fn it_marks_positions_relative_to_the_reversed_order() {
+        let result: Vec<_> = vec![1, 2, 3].into_iter().rev_with_position().collect();
+
+        assert_eq!(result, vec![(Position::First, 3), (Position::Middle, 2), (Position::Last, 1)]);
+    }
+
+    /// A source which yields another item after having returned `None`.
+    struct Resuming(u32);
+
+    impl Iterator for Resuming {
+        type Item = u32;
+
+        fn next(&mut self) -> Option<u32> {
+            self.0 += 1;
+            if self.0 == 2 { None } else { Some(self.0) }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "debug_assert_fused"))]
+    fn it_never_yields_after_the_last_item() {
+        let mut iter = Resuming(0).with_position();
+
+        assert_eq!(iter.next(), Some((Position::Only, 1)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "debug_assert_fused")]
+    #[should_panic(expected = "source iterator yielded an item after the last item")]
+    fn it_panics_if_the_source_resumes_after_the_last_item() {
+        let mut iter = Resuming(0).with_position();
+
+        assert_eq!(iter.next(), Some((Position::Only, 1)));
+        iter.next();
+    }
+
+    #[test]
+    fn it_reuses_an_existing_peekable() {
+        let mut tokens = vec!["(", "a", ")"].into_iter().peekable();
+        assert_eq!(tokens.peek(), Some(&"("));
+
+        let mut iter = PositionIterator::from_peekable(tokens);
+        assert_eq!(iter.next(), Some((Position::First, "(")));
+        assert_eq!(iter.peekable_mut().peek(), Some(&"a"));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![(Position::Middle, "a"), (Position::Last, ")")]);
+    }
 }
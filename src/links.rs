@@ -0,0 +1,48 @@
+use {Position, WithPosition};
+
+/// An item together with the indices of its neighbours, as built by
+/// `link_neighbors`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Linked<T> {
+    pub value: T,
+    pub position: Position,
+    pub prev: Option<usize>,
+    pub next: Option<usize>,
+}
+
+/// Collect the items into a vector where each item knows the index of the
+/// item before and after it, which makes it easy to step back and forth
+/// through something like a playlist or the steps of a wizard.
+///
+/// ```
+/// use with_position::link_neighbors;
+///
+/// let steps = link_neighbors(vec!["account", "address", "confirm"]);
+///
+/// assert_eq!((steps[0].prev, steps[0].next), (None, Some(1)));
+/// assert_eq!((steps[2].prev, steps[2].next), (Some(1), None));
+/// ```
+pub fn link_neighbors<I>(iter: I) -> Vec<Linked<I::Item>> where I: IntoIterator {
+    iter.into_iter().with_position().enumerate().map(|(index, (position, value))| {
+        let prev = if position.is_first() { None } else { Some(index - 1) };
+        let next = if position.is_last() { None } else { Some(index + 1) };
+        Linked { value, position, prev, next }
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {link_neighbors, Linked, Position};
+
+    #[test]
+    fn it_links_a_single_item_to_nothing() {
+        assert_eq!(link_neighbors(Some(1)), vec![Linked { value: 1, position: Position::Only, prev: None, next: None }]);
+    }
+
+    #[test]
+    fn it_links_items_to_their_neighbors() {
+        let links: Vec<_> = link_neighbors(0..4).into_iter().map(|node| (node.prev, node.next)).collect();
+
+        assert_eq!(links, vec![(None, Some(1)), (Some(0), Some(2)), (Some(1), Some(3)), (Some(2), None)]);
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt::{Display, Write};
+
+use WithPosition;
+
+/// Whether a rendered list is numbered or bulleted.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ListKind {
+    Ordered,
+    Unordered,
+}
+
+/// An entry in a nested list, with a value and a list of child entries which
+/// are rendered as a nested list below it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ListEntry<T> {
+    pub value: T,
+    pub children: Vec<ListEntry<T>>,
+}
+
+impl<T> ListEntry<T> {
+    pub fn new(value: T) -> ListEntry<T> {
+        ListEntry { value, children: Vec::new() }
+    }
+
+    pub fn with_children(mut self, children: Vec<ListEntry<T>>) -> ListEntry<T> {
+        self.children = children;
+        self
+    }
+}
+
+/// Render the items as a Markdown list, with one line per item and no
+/// trailing newline after the last one.
+pub fn render_markdown_list<I>(iter: I, kind: ListKind) -> String where I: IntoIterator, I::Item: Display {
+    let entries: Vec<_> = iter.into_iter().map(ListEntry::new).collect();
+    render_markdown_entries(&entries, kind)
+}
+
+/// Render the entries as a Markdown list, indenting nested lists below their
+/// parent entry.
+pub fn render_markdown_entries<T>(entries: &[ListEntry<T>], kind: ListKind) -> String where T: Display {
+    let mut output = String::new();
+    write_markdown(&mut output, entries, kind, 0);
+    output
+}
+
+/// Render the items as an HTML list. The items are written as they are,
+/// without escaping.
+pub fn render_html_list<I>(iter: I, kind: ListKind) -> String where I: IntoIterator, I::Item: Display {
+    let entries: Vec<_> = iter.into_iter().map(ListEntry::new).collect();
+    render_html_entries(&entries, kind)
+}
+
+/// Render the entries as an HTML list, with nested lists inside the `li`
+/// element of their parent entry. Nothing is rendered for an empty list.
+pub fn render_html_entries<T>(entries: &[ListEntry<T>], kind: ListKind) -> String where T: Display {
+    let mut output = String::new();
+    write_html(&mut output, entries, kind);
+    output
+}
+
+fn write_markdown<T>(output: &mut String, entries: &[ListEntry<T>], kind: ListKind, indent: usize) where T: Display {
+    for (index, (position, entry)) in entries.iter().with_position().enumerate() {
+        if !position.is_first() {
+            output.push('\n');
+        }
+        let marker = match kind {
+            ListKind::Ordered => format!("{}. ", index + 1),
+            ListKind::Unordered => String::from("- "),
+        };
+        write!(output, "{:indent$}{}{}", "", marker, entry.value, indent = indent).unwrap();
+        if !entry.children.is_empty() {
+            output.push('\n');
+            write_markdown(output, &entry.children, kind, indent + marker.len());
+        }
+    }
+}
+
+fn write_html<T>(output: &mut String, entries: &[ListEntry<T>], kind: ListKind) where T: Display {
+    let tag = match kind {
+        ListKind::Ordered => "ol",
+        ListKind::Unordered => "ul",
+    };
+    for (position, entry) in entries.iter().with_position() {
+        if position.is_first() {
+            write!(output, "<{}>", tag).unwrap();
+        }
+        write!(output, "<li>{}", entry.value).unwrap();
+        write_html(output, &entry.children, kind);
+        output.push_str("</li>");
+        if position.is_last() {
+            write!(output, "</{}>", tag).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+    use {render_html_entries, render_html_list, render_markdown_entries, render_markdown_list, ListEntry, ListKind};
+
+    #[test]
+    fn it_renders_markdown_lists() {
+        assert_eq!(render_markdown_list(vec!["a", "b"], ListKind::Unordered), "- a\n- b");
+        assert_eq!(render_markdown_list(vec!["a", "b"], ListKind::Ordered), "1. a\n2. b");
+        assert_eq!(render_markdown_list(iter::empty::<u8>(), ListKind::Ordered), "");
+    }
+
+    #[test]
+    fn it_renders_nested_lists() {
+        let entries = vec![
+            ListEntry::new("a").with_children(vec![ListEntry::new("b"), ListEntry::new("c")]),
+            ListEntry::new("d"),
+        ];
+
+        assert_eq!(render_markdown_entries(&entries, ListKind::Ordered), "1. a\n   1. b\n   2. c\n2. d");
+        assert_eq!(render_html_entries(&entries, ListKind::Unordered), "<ul><li>a<ul><li>b</li><li>c</li></ul></li><li>d</li></ul>");
+    }
+
+    #[test]
+    fn it_renders_nothing_for_an_empty_html_list() {
+        assert_eq!(render_html_list(Vec::<String>::new(), ListKind::Unordered), "");
+        assert_eq!(render_html_list(vec![1], ListKind::Ordered), "<ol><li>1</li></ol>");
+    }
+}
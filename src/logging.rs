@@ -0,0 +1,98 @@
+use {Position, PositionIterator, WithPosition};
+
+/// A progress report for an item, passed to the callback of
+/// `LogProgressWithPosition::log_progress_with`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ProgressReport {
+    pub position: Position,
+    /// The number of items yielded so far, including this one.
+    pub count: usize,
+    /// The total number of items, if it is known from the size hint.
+    pub total: Option<usize>,
+}
+
+/// Extension trait for iterators which adds methods to report progress while
+/// iterating.
+pub trait LogProgressWithPosition: Iterator + Sized {
+    /// Yield a tuple of `(Position, item)` like `with_position`, while calling
+    /// the callback with a `ProgressReport` for the first item, every
+    /// `every_n`th item and the last item. If `every_n` is zero, only the first
+    /// and the last items are reported.
+    fn log_progress_with<F>(self, every_n: usize, report: F) -> LogProgress<Self, F> where F: FnMut(ProgressReport) {
+        let total = match self.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(lower),
+            _ => None,
+        };
+        LogProgress { iter: self.with_position(), every_n, count: 0, total, report }
+    }
+
+    /// Like `log_progress_with`, but logs the reports at the info level using
+    /// the `log` crate.
+    #[cfg(feature = "log")]
+    fn log_progress(self, every_n: usize) -> LogProgress<Self, fn(ProgressReport)> {
+        self.log_progress_with(every_n, log_report)
+    }
+}
+
+impl<I> LogProgressWithPosition for I where I: Iterator {}
+
+#[cfg(feature = "log")]
+fn log_report(report: ProgressReport) {
+    match report.total {
+        Some(total) => info!("processed {} of {} items", report.count, total),
+        None => info!("processed {} items", report.count),
+    }
+}
+
+/// An iterator adapter that yields tuples of `(Position, item)` and reports
+/// progress, created by `LogProgressWithPosition::log_progress_with`.
+pub struct LogProgress<I, F> where I: Iterator {
+    iter: PositionIterator<I>,
+    every_n: usize,
+    count: usize,
+    total: Option<usize>,
+    report: F,
+}
+
+impl<I, F> Iterator for LogProgress<I, F> where I: Iterator, F: FnMut(ProgressReport) {
+    type Item = (Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (position, item) = self.iter.next()?;
+        self.count += 1;
+        let is_nth = self.every_n != 0 && self.count.is_multiple_of(self.every_n);
+        if position != Position::Middle || is_nth {
+            (self.report)(ProgressReport { position, count: self.count, total: self.total });
+        }
+        Some((position, item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {LogProgressWithPosition, Position};
+
+    #[test]
+    fn it_reports_the_first_every_nth_and_last_item() {
+        let mut reports = Vec::new();
+
+        let count = (0..7).log_progress_with(3, |report| reports.push((report.position, report.count, report.total))).count();
+
+        assert_eq!(count, 7);
+        assert_eq!(reports, vec![
+            (Position::First, 1, Some(7)),
+            (Position::Middle, 3, Some(7)),
+            (Position::Middle, 6, Some(7)),
+            (Position::Last, 7, Some(7)),
+        ]);
+    }
+
+    #[test]
+    fn it_reports_only_the_edges_when_every_n_is_zero() {
+        let mut counts = Vec::new();
+
+        (0..5).filter(|_| true).log_progress_with(0, |report| counts.push((report.count, report.total))).for_each(drop);
+
+        assert_eq!(counts, vec![(1, None), (5, None)]);
+    }
+}
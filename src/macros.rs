@@ -0,0 +1,46 @@
+/// Assert that a positioned iterator yields the given positions, or the given
+/// positions and values, in order. Positions are given by their variant name.
+/// On failure, the expected and actual sequences are shown in full.
+///
+/// ```
+/// #[macro_use]
+/// extern crate with_position;
+///
+/// use with_position::WithPosition;
+///
+/// # fn main() {
+/// assert_positions!(vec!["a", "b", "c"].into_iter().with_position(), [First, Middle, Last]);
+/// assert_positions!(vec!["a", "b"].into_iter().with_position(), [First => "a", Last => "b"]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_positions {
+    ($iter:expr, [$($position:ident),* $(,)*]) => {{
+        let actual: ::std::vec::Vec<$crate::Position> = ::std::iter::Iterator::map($iter, |(position, _)| position).collect();
+        let expected: ::std::vec::Vec<$crate::Position> = vec![$($crate::Position::$position),*];
+        assert_eq!(actual, expected, "positions did not match");
+    }};
+    ($iter:expr, [$($position:ident => $value:expr),* $(,)*]) => {{
+        let actual: ::std::vec::Vec<_> = ::std::iter::Iterator::collect($iter);
+        let expected = vec![$(($crate::Position::$position, $value)),*];
+        assert_eq!(actual, expected, "positions and values did not match");
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use WithPosition;
+
+    #[test]
+    fn it_asserts_positions() {
+        assert_positions!((0..3).with_position(), [First, Middle, Last]);
+        assert_positions!(Some(1).into_iter().with_position(), [Only => 1]);
+        assert_positions!(None::<i32>.into_iter().with_position(), []);
+    }
+
+    #[test]
+    #[should_panic(expected = "positions did not match")]
+    fn it_fails_for_wrong_positions() {
+        assert_positions!((0..2).with_position(), [First, Middle]);
+    }
+}
@@ -0,0 +1,57 @@
+use std::iter::FusedIterator;
+
+use {Position, PositionIterator};
+
+/// An iterator adapter that maps only the items at certain positions, and
+/// yields the others unchanged, created by `WithPosition::map_first`,
+/// `WithPosition::map_last` and `WithPosition::map_only`.
+pub struct MapAt<I, F> where I: Iterator {
+    iter: PositionIterator<I>,
+    at: fn(Position) -> bool,
+    f: F,
+}
+
+impl<I, F> MapAt<I, F> where I: Iterator, F: FnMut(I::Item) -> I::Item {
+    pub(crate) fn new(iter: PositionIterator<I>, at: fn(Position) -> bool, f: F) -> MapAt<I, F> {
+        MapAt { iter, at, f }
+    }
+}
+
+impl<I, F> Iterator for MapAt<I, F> where I: Iterator, F: FnMut(I::Item) -> I::Item {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let (position, item) = self.iter.next()?;
+        if (self.at)(position) {
+            Some((self.f)(item))
+        } else {
+            Some(item)
+        }
+    }
+}
+
+impl<I, F> FusedIterator for MapAt<I, F> where I: FusedIterator, F: FnMut(I::Item) -> I::Item {}
+
+#[cfg(test)]
+mod tests {
+    use WithPosition;
+
+    #[test]
+    fn it_maps_the_first_and_last_items() {
+        let result: Vec<_> = vec![" a ", " b ", " c "].into_iter()
+            .map_first(str::trim_start)
+            .map_last(str::trim_end)
+            .collect();
+
+        assert_eq!(result, vec!["a ", " b ", " c"]);
+    }
+
+    #[test]
+    fn it_maps_a_single_item_as_both_first_and_last() {
+        let result: Vec<_> = Some(1).into_iter().map_first(|n| n + 1).map_last(|n| n * 10).collect();
+        assert_eq!(result, vec![20]);
+
+        assert_eq!(vec![1, 2].into_iter().map_only(|n| n * 10).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(Some(1).into_iter().map_only(|n| n * 10).collect::<Vec<_>>(), vec![10]);
+    }
+}
@@ -0,0 +1,165 @@
+use std::fmt::{self, Write};
+
+use WithPosition;
+
+/// An element of a tree of markup, which can be written as indented XML or as
+/// an S-expression.
+///
+/// ```
+/// use with_position::Element;
+///
+/// let config = Element::new("config")
+///     .child(Element::new("name").text("example"))
+///     .child(Element::new("debug").attribute("enabled", "true"));
+///
+/// assert_eq!(config.to_xml(), "<config>\n  <name>example</name>\n  <debug enabled=\"true\"/>\n</config>");
+/// assert_eq!(config.to_sexpr(), "(config\n  (name \"example\")\n  (debug :enabled \"true\"))");
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub text: Option<String>,
+    pub children: Vec<Element>,
+}
+
+impl Element {
+    pub fn new<N>(name: N) -> Element where N: Into<String> {
+        Element { name: name.into(), attributes: Vec::new(), text: None, children: Vec::new() }
+    }
+
+    pub fn attribute<K, V>(mut self, key: K, value: V) -> Element where K: Into<String>, V: Into<String> {
+        self.attributes.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn text<T>(mut self, text: T) -> Element where T: Into<String> {
+        self.text = Some(text.into());
+        self
+    }
+
+    pub fn child(mut self, child: Element) -> Element {
+        self.children.push(child);
+        self
+    }
+
+    /// Write the element as XML, with each child on its own line, indented by
+    /// two spaces per level. Elements without children are written on a single
+    /// line, and elements without children or text are self-closing.
+    pub fn write_xml<W>(&self, out: &mut W) -> fmt::Result where W: Write + ?Sized {
+        self.write_xml_at(out, 0)
+    }
+
+    /// Write the element as an S-expression, with attributes as keyword
+    /// arguments and each child on its own line. The closing parentheses are
+    /// written directly after the last child.
+    pub fn write_sexpr<W>(&self, out: &mut W) -> fmt::Result where W: Write + ?Sized {
+        self.write_sexpr_at(out, 0)
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut output = String::new();
+        self.write_xml(&mut output).unwrap();
+        output
+    }
+
+    pub fn to_sexpr(&self) -> String {
+        let mut output = String::new();
+        self.write_sexpr(&mut output).unwrap();
+        output
+    }
+
+    fn write_xml_at<W>(&self, out: &mut W, depth: usize) -> fmt::Result where W: Write + ?Sized {
+        write!(out, "<{}", self.name)?;
+        for (key, value) in &self.attributes {
+            write!(out, " {}=\"{}\"", key, XmlEscaped(value))?;
+        }
+        if self.text.is_none() && self.children.is_empty() {
+            return out.write_str("/>");
+        }
+        out.write_char('>')?;
+        if let Some(ref text) = self.text {
+            write!(out, "{}", XmlEscaped(text))?;
+        }
+        for (position, child) in self.children.iter().with_position() {
+            write!(out, "\n{:indent$}", "", indent = (depth + 1) * 2)?;
+            child.write_xml_at(out, depth + 1)?;
+            if position.is_last() {
+                write!(out, "\n{:indent$}", "", indent = depth * 2)?;
+            }
+        }
+        write!(out, "</{}>", self.name)
+    }
+
+    fn write_sexpr_at<W>(&self, out: &mut W, depth: usize) -> fmt::Result where W: Write + ?Sized {
+        write!(out, "({}", self.name)?;
+        for (key, value) in &self.attributes {
+            write!(out, " :{} \"{}\"", key, SexprEscaped(value))?;
+        }
+        if let Some(ref text) = self.text {
+            write!(out, " \"{}\"", SexprEscaped(text))?;
+        }
+        for child in &self.children {
+            write!(out, "\n{:indent$}", "", indent = (depth + 1) * 2)?;
+            child.write_sexpr_at(out, depth + 1)?;
+        }
+        out.write_char(')')
+    }
+}
+
+struct SexprEscaped<'a>(&'a str);
+
+impl<'a> fmt::Display for SexprEscaped<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '\\' => f.write_str("\\\\")?,
+                '"' => f.write_str("\\\"")?,
+                c => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+struct XmlEscaped<'a>(&'a str);
+
+impl<'a> fmt::Display for XmlEscaped<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                c => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use Element;
+
+    #[test]
+    fn it_writes_nested_xml() {
+        let element = Element::new("a").child(Element::new("b").child(Element::new("c").text("x < y")));
+
+        assert_eq!(element.to_xml(), "<a>\n  <b>\n    <c>x &lt; y</c>\n  </b>\n</a>");
+    }
+
+    #[test]
+    fn it_writes_a_single_element() {
+        assert_eq!(Element::new("a").to_xml(), "<a/>");
+        assert_eq!(Element::new("a").to_sexpr(), "(a)");
+    }
+
+    #[test]
+    fn it_escapes_only_backslashes_and_quotes_in_sexprs() {
+        let element = Element::new("a").attribute("path", "C:\\tmp").text("say \"héllo\"\n");
+
+        assert_eq!(element.to_sexpr(), "(a :path \"C:\\\\tmp\" \"say \\\"héllo\\\"\n\")");
+    }
+}
@@ -0,0 +1,104 @@
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::vec;
+
+use {ExactPositionIterator, Position, PositionIterator, WithPosition};
+
+/// Join two iterators which are sorted by key, yielding a tuple of
+/// `(Position, key, rows)` for every key which occurs in both. The position is
+/// the position of the group among all matched groups, and the rows are every
+/// combination of a left and a right item with that key, labelled with their
+/// position within the group. Items whose key only occurs on one side are
+/// skipped.
+///
+/// ```
+/// use with_position::{merge_join_with_position, Position};
+///
+/// let orders = vec![(1, "apples"), (1, "pears"), (3, "plums")];
+/// let customers = vec![(1, "alice"), (2, "bob"), (3, "carol")];
+///
+/// let groups: Vec<_> = merge_join_with_position(orders, customers, |order| order.0, |customer| customer.0)
+///     .map(|(position, key, rows)| (position, key, rows.map(|(position, (order, customer))| (position, order.1, customer.1)).collect::<Vec<_>>()))
+///     .collect();
+///
+/// assert_eq!(groups, vec![
+///     (Position::First, 1, vec![(Position::First, "apples", "alice"), (Position::Last, "pears", "alice")]),
+///     (Position::Last, 3, vec![(Position::Only, "plums", "carol")]),
+/// ]);
+/// ```
+pub fn merge_join_with_position<A, B, K, F, G>(left: A, right: B, left_key: F, right_key: G) -> MergeJoin<A::IntoIter, B::IntoIter, F, G, K>
+    where A: IntoIterator, B: IntoIterator, A::Item: Clone, B::Item: Clone, F: FnMut(&A::Item) -> K, G: FnMut(&B::Item) -> K, K: Ord
+{
+    let groups = JoinGroups { left: left.into_iter().peekable(), right: right.into_iter().peekable(), left_key, right_key };
+    MergeJoin { groups: groups.with_position() }
+}
+
+/// An iterator over the matched groups of a merge-join with their positions,
+/// created by `merge_join_with_position`.
+pub struct MergeJoin<I, J, F, G, K> where I: Iterator, J: Iterator, I::Item: Clone, J::Item: Clone, F: FnMut(&I::Item) -> K, G: FnMut(&J::Item) -> K, K: Ord {
+    groups: PositionIterator<JoinGroups<I, J, F, G>>,
+}
+
+impl<I, J, F, G, K> Iterator for MergeJoin<I, J, F, G, K> where I: Iterator, J: Iterator, I::Item: Clone, J::Item: Clone, F: FnMut(&I::Item) -> K, G: FnMut(&J::Item) -> K, K: Ord {
+    type Item = (Position, K, ExactPositionIterator<vec::IntoIter<(I::Item, J::Item)>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (position, (key, rows)) = self.groups.next()?;
+        Some((position, key, ExactPositionIterator::new(rows.into_iter())))
+    }
+}
+
+struct JoinGroups<I, J, F, G> where I: Iterator, J: Iterator {
+    left: Peekable<I>,
+    right: Peekable<J>,
+    left_key: F,
+    right_key: G,
+}
+
+impl<I, J, F, G, K> Iterator for JoinGroups<I, J, F, G> where I: Iterator, J: Iterator, I::Item: Clone, J::Item: Clone, F: FnMut(&I::Item) -> K, G: FnMut(&J::Item) -> K, K: Ord {
+    type Item = (K, Vec<(I::Item, J::Item)>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = loop {
+            let left_key = (self.left_key)(self.left.peek()?);
+            let right_key = (self.right_key)(self.right.peek()?);
+            match left_key.cmp(&right_key) {
+                Ordering::Less => { self.left.next(); }
+                Ordering::Greater => { self.right.next(); }
+                Ordering::Equal => break left_key,
+            }
+        };
+
+        let lefts = take_run(&mut self.left, &mut self.left_key, &key);
+        let rights = take_run(&mut self.right, &mut self.right_key, &key);
+        let rows = lefts.iter().flat_map(|left| rights.iter().map(move |right| (left.clone(), right.clone()))).collect();
+        Some((key, rows))
+    }
+}
+
+fn take_run<I, F, K>(iter: &mut Peekable<I>, key_fn: &mut F, key: &K) -> Vec<I::Item> where I: Iterator, F: FnMut(&I::Item) -> K, K: Ord {
+    let mut run = Vec::new();
+    while let Some(item) = iter.next_if(|item| key_fn(item) == *key) {
+        run.push(item);
+    }
+    run
+}
+
+#[cfg(test)]
+mod tests {
+    use {merge_join_with_position, Position};
+
+    #[test]
+    fn it_yields_the_cross_product_of_each_group() {
+        let groups: Vec<_> = merge_join_with_position(vec![1, 2, 2], vec![2, 2, 3], |&n| n, |&n| n)
+            .map(|(position, key, rows)| (position, key, rows.map(|(position, _)| position).collect::<Vec<_>>()))
+            .collect();
+
+        assert_eq!(groups, vec![(Position::Only, 2, vec![Position::First, Position::Middle, Position::Middle, Position::Last])]);
+    }
+
+    #[test]
+    fn it_yields_nothing_without_matching_keys() {
+        assert!(merge_join_with_position(vec![1, 3], vec![2, 4], |&n| n, |&n| n).next().is_none());
+    }
+}
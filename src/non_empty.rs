@@ -0,0 +1,63 @@
+use std::error::Error;
+use std::fmt;
+
+use {Position, PositionIterator};
+
+/// The error returned by `WithPosition::ensure_nonempty` when the iterator did
+/// not yield any items.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct EmptyIter;
+
+impl fmt::Display for EmptyIter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("iterator is empty")
+    }
+}
+
+impl Error for EmptyIter {}
+
+/// A `PositionIterator` which is known to yield at least one item, created by
+/// `WithPosition::ensure_nonempty`.
+pub struct NonEmptyPositionIterator<I> where I: Iterator {
+    iter: PositionIterator<I>,
+}
+
+impl<I> NonEmptyPositionIterator<I> where I: Iterator {
+    pub(crate) fn new(iter: PositionIterator<I>) -> NonEmptyPositionIterator<I> {
+        NonEmptyPositionIterator { iter }
+    }
+
+    /// Split off the first item, which is always present, returning it with
+    /// its position together with an iterator over the remaining items.
+    pub fn split_first(mut self) -> ((Position, I::Item), PositionIterator<I>) {
+        let first = self.iter.next().expect("non-empty iterator yielded no items");
+        (first, self.iter)
+    }
+}
+
+impl<I> Iterator for NonEmptyPositionIterator<I> where I: Iterator {
+    type Item = (Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+    use {EmptyIter, Position, WithPosition};
+
+    #[test]
+    fn it_rejects_an_empty_iterator() {
+        assert_eq!(iter::empty::<i32>().ensure_nonempty().err(), Some(EmptyIter));
+    }
+
+    #[test]
+    fn it_splits_off_the_first_item_of_a_non_empty_iterator() {
+        let (first, rest) = vec![1, 2, 3].into_iter().ensure_nonempty().unwrap().split_first();
+
+        assert_eq!(first, (Position::First, 1));
+        assert_eq!(rest.collect::<Vec<_>>(), vec![(Position::Middle, 2), (Position::Last, 3)]);
+    }
+}
@@ -0,0 +1,224 @@
+use std::thread;
+
+use Position;
+
+type ItemHook<'a, T> = Box<dyn FnMut(&T) + 'a>;
+type EachHook<'a, T> = Box<dyn FnMut(Position, &T) + 'a>;
+
+/// A push based alternative to `with_position`. Callbacks are registered for
+/// the first, each, and last item, as well as for when there were no items at
+/// all, and are then called as items are pushed into the observer or it is
+/// driven by an iterator.
+///
+/// One item is held back until the next one is pushed, since only then is its
+/// position known. The held back item is handled as the last item when
+/// `finish` is called, or when the observer is dropped. Callbacks are not
+/// called when the observer is dropped while the thread is panicking, since a
+/// panic in a callback would then abort the process.
+///
+/// ```
+/// use std::cell::RefCell;
+/// use with_position::PositionObserver;
+///
+/// let output = RefCell::new(String::new());
+/// PositionObserver::new()
+///     .on_first(|_: &&str| output.borrow_mut().push('['))
+///     .on_each(|position, item| {
+///         output.borrow_mut().push_str(item);
+///         if !position.is_last() {
+///             output.borrow_mut().push_str(", ");
+///         }
+///     })
+///     .on_last(|_| output.borrow_mut().push(']'))
+///     .observe(vec!["a", "b"]);
+///
+/// assert_eq!(*output.borrow(), "[a, b]");
+/// ```
+pub struct PositionObserver<'a, T> {
+    on_first: Option<ItemHook<'a, T>>,
+    on_each: Option<EachHook<'a, T>>,
+    on_last: Option<ItemHook<'a, T>>,
+    on_empty: Option<Box<dyn FnMut() + 'a>>,
+    pending: Option<T>,
+    did_iter: bool,
+    finished: bool,
+}
+
+impl<'a, T> PositionObserver<'a, T> {
+    pub fn new() -> PositionObserver<'a, T> {
+        PositionObserver {
+            on_first: None,
+            on_each: None,
+            on_last: None,
+            on_empty: None,
+            pending: None,
+            did_iter: false,
+            finished: false,
+        }
+    }
+
+    /// Call the given closure with the first item, before `on_each`.
+    pub fn on_first<F>(mut self, f: F) -> PositionObserver<'a, T> where F: FnMut(&T) + 'a {
+        self.on_first = Some(Box::new(f));
+        self
+    }
+
+    /// Call the given closure with each item and its position.
+    pub fn on_each<F>(mut self, f: F) -> PositionObserver<'a, T> where F: FnMut(Position, &T) + 'a {
+        self.on_each = Some(Box::new(f));
+        self
+    }
+
+    /// Call the given closure with the last item, after `on_each`.
+    pub fn on_last<F>(mut self, f: F) -> PositionObserver<'a, T> where F: FnMut(&T) + 'a {
+        self.on_last = Some(Box::new(f));
+        self
+    }
+
+    /// Call the given closure when the observer is finished without any items
+    /// having been pushed.
+    pub fn on_empty<F>(mut self, f: F) -> PositionObserver<'a, T> where F: FnMut() + 'a {
+        self.on_empty = Some(Box::new(f));
+        self
+    }
+
+    /// Push an item into the observer. This calls the callbacks for the
+    /// previously pushed item, which is now known not to be the last one.
+    pub fn push(&mut self, item: T) {
+        if self.finished {
+            return;
+        }
+        if let Some(previous) = self.pending.take() {
+            self.emit(previous, false);
+        }
+        self.pending = Some(item);
+    }
+
+    /// Push all items of the iterator into the observer and then finish it.
+    pub fn observe<I>(mut self, iter: I) where I: IntoIterator<Item = T> {
+        for item in iter {
+            self.push(item);
+        }
+        self.complete();
+    }
+
+    /// Finish the observer, calling the callbacks for the last item, or
+    /// `on_empty` if there were no items.
+    pub fn finish(mut self) {
+        self.complete();
+    }
+
+    fn complete(&mut self) {
+        if self.finished {
+            return;
+        }
+        match self.pending.take() {
+            Some(last) => self.emit(last, true),
+            None => {
+                if let Some(ref mut on_empty) = self.on_empty {
+                    on_empty();
+                }
+            }
+        }
+        self.finished = true;
+    }
+
+    fn emit(&mut self, item: T, is_last: bool) {
+        let position = Position::from_flags(!self.did_iter, is_last);
+        self.did_iter = true;
+
+        if position.is_first() {
+            if let Some(ref mut on_first) = self.on_first {
+                on_first(&item);
+            }
+        }
+        if let Some(ref mut on_each) = self.on_each {
+            on_each(position, &item);
+        }
+        if position.is_last() {
+            if let Some(ref mut on_last) = self.on_last {
+                on_last(&item);
+            }
+        }
+    }
+}
+
+impl<'a, T> Default for PositionObserver<'a, T> {
+    fn default() -> PositionObserver<'a, T> {
+        PositionObserver::new()
+    }
+}
+
+impl<'a, T> Drop for PositionObserver<'a, T> {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            self.complete();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::panic::{self, AssertUnwindSafe};
+    use {Position, PositionObserver};
+
+    #[test]
+    fn it_calls_the_hooks_for_pushed_items() {
+        let events = RefCell::new(Vec::new());
+
+        {
+            let mut observer = PositionObserver::new()
+                .on_first(|item: &i32| events.borrow_mut().push(format!("first {}", item)))
+                .on_each(|position, item| events.borrow_mut().push(format!("{:?} {}", position, item)))
+                .on_last(|item| events.borrow_mut().push(format!("last {}", item)))
+                .on_empty(|| events.borrow_mut().push(String::from("empty")));
+
+            observer.push(1);
+            observer.push(2);
+            assert_eq!(events.borrow().len(), 2);
+            observer.finish();
+        }
+
+        assert_eq!(*events.borrow(), vec!["first 1", "First 1", "Last 2", "last 2"]);
+    }
+
+    #[test]
+    fn it_calls_the_hooks_exactly_once_for_a_single_item() {
+        let positions = RefCell::new(Vec::new());
+        let firsts = RefCell::new(0);
+        let lasts = RefCell::new(0);
+
+        PositionObserver::new()
+            .on_first(|_: &i32| *firsts.borrow_mut() += 1)
+            .on_each(|position, _| positions.borrow_mut().push(position))
+            .on_last(|_| *lasts.borrow_mut() += 1)
+            .observe(Some(1));
+
+        assert_eq!(*positions.borrow(), vec![Position::Only]);
+        assert_eq!((*firsts.borrow(), *lasts.borrow()), (1, 1));
+    }
+
+    #[test]
+    fn it_calls_on_empty_when_dropped_without_items() {
+        let empty = RefCell::new(false);
+
+        drop(PositionObserver::<i32>::new().on_empty(|| *empty.borrow_mut() = true));
+
+        assert!(*empty.borrow());
+    }
+
+    #[test]
+    fn it_skips_the_hooks_when_dropped_during_a_panic() {
+        let lasts = RefCell::new(0);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut observer = PositionObserver::new().on_last(|_: &i32| *lasts.borrow_mut() += 1);
+            observer.push(1);
+            panic!("stopped early");
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(*lasts.borrow(), 0);
+    }
+}
@@ -0,0 +1,103 @@
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+use Position;
+
+/// An iterator which yields a single item as `(Position::Only, item)`,
+/// created by `once_with_position`.
+#[derive(Clone, Debug)]
+pub struct OnceWithPosition<T> {
+    item: Option<T>,
+}
+
+/// Yield the item once, paired with `Position::Only`, without any of the
+/// look ahead of `with_position`.
+///
+/// ```
+/// use with_position::{once_with_position, Position};
+///
+/// assert_eq!(once_with_position("a").collect::<Vec<_>>(), vec![(Position::Only, "a")]);
+/// ```
+pub fn once_with_position<T>(item: T) -> OnceWithPosition<T> {
+    OnceWithPosition { item: Some(item) }
+}
+
+impl<T> Iterator for OnceWithPosition<T> {
+    type Item = (Position, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.item.take().map(|item| (Position::Only, item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = if self.item.is_some() { 1 } else { 0 };
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for OnceWithPosition<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next()
+    }
+}
+
+impl<T> ExactSizeIterator for OnceWithPosition<T> {}
+
+impl<T> FusedIterator for OnceWithPosition<T> {}
+
+/// An iterator which yields no `(Position, T)` tuples at all, created by
+/// `empty_with_position`.
+#[derive(Clone, Debug)]
+pub struct EmptyWithPosition<T> {
+    marker: PhantomData<fn() -> T>,
+}
+
+/// Return an iterator of `(Position, T)` tuples which yields nothing, for
+/// when generic code needs a positioned iterator of a concrete type.
+pub fn empty_with_position<T>() -> EmptyWithPosition<T> {
+    EmptyWithPosition { marker: PhantomData }
+}
+
+impl<T> Iterator for EmptyWithPosition<T> {
+    type Item = (Position, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
+}
+
+impl<T> DoubleEndedIterator for EmptyWithPosition<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        None
+    }
+}
+
+impl<T> ExactSizeIterator for EmptyWithPosition<T> {}
+
+impl<T> FusedIterator for EmptyWithPosition<T> {}
+
+#[cfg(test)]
+mod tests {
+    use {empty_with_position, once_with_position, Position};
+
+    #[test]
+    fn it_yields_a_single_item_as_only() {
+        let mut iter = once_with_position(1);
+
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next_back(), Some((Position::Only, 1)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn it_yields_nothing_when_empty() {
+        let mut iter = empty_with_position::<u8>();
+
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+    }
+}
@@ -0,0 +1,79 @@
+use std::iter::FusedIterator;
+
+use Position;
+
+/// Extension trait for iterators over options which adds the
+/// `with_position_some` method.
+pub trait OptionWithPosition<T>: Iterator<Item = Option<T>> + Sized {
+    /// Drop the `None` items and yield a tuple of `(Position, value)` for each
+    /// `Some` item, with positions computed over the `Some` items only. This is
+    /// the same as `flatten().with_position()`, but is done in a single
+    /// adapter, which holds on to at most one value.
+    fn with_position_some(self) -> PositionSome<Self, T> {
+        PositionSome { iter: self, pending: None, did_iter: false, done: false }
+    }
+}
+
+impl<I, T> OptionWithPosition<T> for I where I: Iterator<Item = Option<T>> {}
+
+/// An iterator adapter that yields the values of the `Some` items of an
+/// iterator over options, labeled with their position.
+pub struct PositionSome<I, T> {
+    iter: I,
+    pending: Option<T>,
+    did_iter: bool,
+    done: bool,
+}
+
+impl<I, T> PositionSome<I, T> where I: Iterator<Item = Option<T>> {
+    fn next_some(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+        for item in &mut self.iter {
+            if item.is_some() {
+                return item;
+            }
+        }
+        self.done = true;
+        None
+    }
+}
+
+impl<I, T> Iterator for PositionSome<I, T> where I: Iterator<Item = Option<T>> {
+    type Item = (Position, T);
+
+    fn next(&mut self) -> Option<(Position, T)> {
+        let current = match self.pending.take() {
+            Some(current) => current,
+            None => self.next_some()?,
+        };
+        self.pending = self.next_some();
+
+        let is_first = !self.did_iter;
+        self.did_iter = true;
+        Some((Position::from_flags(is_first, self.pending.is_none()), current))
+    }
+}
+
+impl<I, T> FusedIterator for PositionSome<I, T> where I: Iterator<Item = Option<T>> {}
+
+#[cfg(test)]
+mod tests {
+    use {OptionWithPosition, Position};
+
+    #[test]
+    fn it_marks_positions_of_some_values() {
+        let result: Vec<_> = vec![None, Some(1), None, Some(2), Some(3), None].into_iter().with_position_some().collect();
+
+        assert_eq!(result, vec![(Position::First, 1), (Position::Middle, 2), (Position::Last, 3)]);
+    }
+
+    #[test]
+    fn it_yields_nothing_when_all_items_are_none() {
+        let mut iter = vec![None::<i32>, None].into_iter().with_position_some();
+
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+}
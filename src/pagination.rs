@@ -0,0 +1,112 @@
+use std::vec;
+
+use {ExactPositionIterator, Position};
+
+/// A button in a pagination control.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PageToken {
+    /// A link to the page with this number, counting from one.
+    Page(usize),
+    /// A gap of hidden pages.
+    Ellipsis,
+}
+
+/// Return the buttons of a pagination control for the `current` of `total`
+/// pages, as a positioned iterator. The first and the last page are always
+/// included, together with the pages up to `radius` pages away from the
+/// current one, and gaps between them are replaced by an ellipsis. A gap of a
+/// single page is filled in with that page instead, since an ellipsis would
+/// not save any space.
+///
+/// ```
+/// use with_position::{pagination, PageToken, Position};
+///
+/// let tokens: Vec<_> = pagination(5, 10, 1).collect();
+///
+/// assert_eq!(tokens, vec![
+///     (Position::First, PageToken::Page(1)),
+///     (Position::Middle, PageToken::Ellipsis),
+///     (Position::Middle, PageToken::Page(4)),
+///     (Position::Middle, PageToken::Page(5)),
+///     (Position::Middle, PageToken::Page(6)),
+///     (Position::Middle, PageToken::Ellipsis),
+///     (Position::Last, PageToken::Page(10)),
+/// ]);
+/// ```
+pub fn pagination(current: usize, total: usize, radius: usize) -> ExactPositionIterator<vec::IntoIter<PageToken>> {
+    let mut tokens = Vec::new();
+    if total > 0 {
+        let current = current.max(1).min(total);
+        let start = current.saturating_sub(radius).max(1);
+        let end = current.saturating_add(radius).min(total);
+        let mut previous = 0;
+        for page in Some(1).into_iter().chain(start..=end).chain(Some(total)) {
+            if page <= previous {
+                continue;
+            }
+            if page == previous + 2 {
+                tokens.push(PageToken::Page(previous + 1));
+            } else if page > previous + 2 {
+                tokens.push(PageToken::Ellipsis);
+            }
+            tokens.push(PageToken::Page(page));
+            previous = page;
+        }
+    }
+    ExactPositionIterator::new(tokens.into_iter())
+}
+
+/// Whether there are pages before and after a page, for filling in `prev`
+/// and `next` links.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct PageNav {
+    pub has_prev: bool,
+    pub has_next: bool,
+    pub is_only_page: bool,
+}
+
+impl PageNav {
+    /// Return the navigation for the `current` of `total` pages, counting from
+    /// one. Having no pages at all is treated like having a single page.
+    pub fn new(current: usize, total: usize) -> PageNav {
+        let total = total.max(1);
+        let current = current.max(1).min(total);
+        PageNav::from(Position::from_flags(current == 1, current == total))
+    }
+}
+
+impl From<Position> for PageNav {
+    /// Return the navigation for a page with the given position among all
+    /// pages.
+    fn from(position: Position) -> PageNav {
+        PageNav { has_prev: !position.is_first(), has_next: !position.is_last(), is_only_page: position.is_only() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {pagination, PageNav, PageToken, Position, WithPosition};
+
+    #[test]
+    fn it_fills_in_single_page_gaps() {
+        let tokens: Vec<_> = pagination(3, 6, 0).map(|(_, token)| token).collect();
+
+        assert_eq!(tokens, vec![PageToken::Page(1), PageToken::Page(2), PageToken::Page(3), PageToken::Ellipsis, PageToken::Page(6)]);
+    }
+
+    #[test]
+    fn it_handles_a_single_page_and_no_pages() {
+        assert_eq!(pagination(1, 1, 2).collect::<Vec<_>>(), vec![(Position::Only, PageToken::Page(1))]);
+        assert_eq!(pagination(1, 0, 2).next(), None);
+    }
+
+    #[test]
+    fn it_builds_page_navigation() {
+        assert_eq!(PageNav::new(1, 3), PageNav { has_prev: false, has_next: true, is_only_page: false });
+        assert_eq!(PageNav::new(3, 3), PageNav { has_prev: true, has_next: false, is_only_page: false });
+        assert_eq!(PageNav::new(1, 0), PageNav { has_prev: false, has_next: false, is_only_page: true });
+
+        let navs: Vec<_> = vec!["a", "b"].into_iter().with_position().map(|(position, _)| PageNav::from(position)).collect();
+        assert_eq!(navs, vec![PageNav::new(1, 2), PageNav::new(2, 2)]);
+    }
+}
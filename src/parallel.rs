@@ -0,0 +1,209 @@
+use std::iter::Enumerate;
+
+use rayon::iter::plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::{IndexedParallelIterator, IterBridge, ParallelBridge, ParallelIterator};
+
+use {Position, PositionIterator, WithPosition};
+
+/// Extension trait for indexed parallel iterators which adds the
+/// `with_position` method.
+pub trait ParallelWithPosition: IndexedParallelIterator {
+    /// Yield a tuple of `(Position, item)` where position indicates whether this
+    /// is the first, middle or last item. Positions are computed from the index
+    /// of each item and the length of the iterator, so the work can be split
+    /// across threads as usual.
+    fn with_position(self) -> ParallelPositionIterator<Self> {
+        ParallelPositionIterator { base: self }
+    }
+}
+
+impl<I> ParallelWithPosition for I where I: IndexedParallelIterator {}
+
+/// A parallel iterator adapter that yields tuples where the first element is a
+/// Position and the second is the item.
+pub struct ParallelPositionIterator<I> {
+    base: I,
+}
+
+impl<I> ParallelIterator for ParallelPositionIterator<I> where I: IndexedParallelIterator {
+    type Item = (Position, I::Item);
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result where C: UnindexedConsumer<Self::Item> {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.base.len())
+    }
+}
+
+impl<I> IndexedParallelIterator for ParallelPositionIterator<I> where I: IndexedParallelIterator {
+    fn drive<C>(self, consumer: C) -> C::Result where C: Consumer<Self::Item> {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.base.len()
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output where CB: ProducerCallback<Self::Item> {
+        let len = self.base.len();
+        self.base.with_producer(Callback { callback, len })
+    }
+}
+
+struct Callback<CB> {
+    callback: CB,
+    len: usize,
+}
+
+impl<T, CB> ProducerCallback<T> for Callback<CB> where CB: ProducerCallback<(Position, T)> {
+    type Output = CB::Output;
+
+    fn callback<P>(self, base: P) -> CB::Output where P: Producer<Item = T> {
+        self.callback.callback(PositionProducer { base, offset: 0, len: self.len })
+    }
+}
+
+struct PositionProducer<P> {
+    base: P,
+    offset: usize,
+    len: usize,
+}
+
+impl<P> Producer for PositionProducer<P> where P: Producer {
+    type Item = (Position, P::Item);
+    type IntoIter = PositionProducerIter<P::IntoIter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let iter = self.base.into_iter();
+        let end = self.offset + iter.len();
+        PositionProducerIter { iter, front: self.offset, back: end, len: self.len }
+    }
+
+    fn min_len(&self) -> usize {
+        self.base.min_len()
+    }
+
+    fn max_len(&self) -> usize {
+        self.base.max_len()
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.base.split_at(index);
+        (
+            PositionProducer { base: left, offset: self.offset, len: self.len },
+            PositionProducer { base: right, offset: self.offset + index, len: self.len },
+        )
+    }
+}
+
+/// The sequential part of a split parallel iteration, which knows the index
+/// range it covers within the whole iteration.
+struct PositionProducerIter<I> {
+    iter: I,
+    front: usize,
+    back: usize,
+    len: usize,
+}
+
+impl<I> Iterator for PositionProducerIter<I> where I: ExactSizeIterator {
+    type Item = (Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        let position = Position::from_index(self.front, self.len);
+        self.front += 1;
+        Some((position, item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> DoubleEndedIterator for PositionProducerIter<I> where I: ExactSizeIterator + DoubleEndedIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next_back()?;
+        self.back -= 1;
+        Some((Position::from_index(self.back, self.len), item))
+    }
+}
+
+impl<I> ExactSizeIterator for PositionProducerIter<I> where I: ExactSizeIterator {}
+
+/// Extension trait for sequential iterators which adds the
+/// `par_bridge_with_position` method.
+pub trait ParallelBridgeWithPosition: Iterator + Send + Sized where Self::Item: Send {
+    /// Compute positions sequentially, then bridge the iteration into a parallel
+    /// iterator of `(index, (Position, item))`. This is useful when the source
+    /// is not indexed, but the work done per item is heavy. Since `par_bridge`
+    /// does not preserve the order of items, the index can be used to restore
+    /// it afterwards, see `collect_in_order`.
+    ///
+    /// ```
+    /// # extern crate rayon;
+    /// # extern crate with_position;
+    /// use rayon::prelude::*;
+    /// use with_position::{collect_in_order, ParallelBridgeWithPosition, Position};
+    ///
+    /// # fn main() {
+    /// let lines = "a\nb\nc".lines();
+    /// let rendered = collect_in_order(lines.par_bridge_with_position().map(|(index, (position, line))| {
+    ///     let terminator = if position.is_last() { "." } else { "," };
+    ///     (index, format!("{}{}", line.to_uppercase(), terminator))
+    /// }));
+    ///
+    /// assert_eq!(rendered, vec!["A,", "B,", "C."]);
+    /// # }
+    /// ```
+    fn par_bridge_with_position(self) -> IterBridge<Enumerate<PositionIterator<Self>>> {
+        self.with_position().enumerate().par_bridge()
+    }
+}
+
+impl<I> ParallelBridgeWithPosition for I where I: Iterator + Send, I::Item: Send {}
+
+/// Collect a parallel iteration of `(index, item)` into a vector in index
+/// order, dropping the index.
+pub fn collect_in_order<P, T>(par_iter: P) -> Vec<T> where P: ParallelIterator<Item = (usize, T)>, T: Send {
+    let mut items: Vec<_> = par_iter.collect();
+    items.sort_unstable_by_key(|&(index, _)| index);
+    items.into_iter().map(|(_, item)| item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+    use {collect_in_order, ParallelBridgeWithPosition, ParallelWithPosition, Position};
+
+    #[test]
+    fn it_marks_positions_in_parallel() {
+        let input: Vec<_> = (0..1000).collect();
+
+        let result: Vec<_> = input.par_iter().with_position().map(|(position, &item)| (position, item * 2)).collect();
+
+        assert_eq!(result.len(), 1000);
+        assert_eq!(result[0], (Position::First, 0));
+        assert_eq!(result[500], (Position::Middle, 1000));
+        assert_eq!(result[999], (Position::Last, 1998));
+        assert_eq!(result.iter().filter(|&&(position, _)| position == Position::Middle).count(), 998);
+    }
+
+    #[test]
+    fn it_marks_only_position_in_parallel() {
+        let result: Vec<_> = vec![1].into_par_iter().with_position().collect();
+
+        assert_eq!(result, vec![(Position::Only, 1)]);
+    }
+
+    #[test]
+    fn it_keeps_positions_paired_across_par_bridge() {
+        let result = collect_in_order((0..100).filter(|n| n % 2 == 0).par_bridge_with_position());
+
+        assert_eq!(result.len(), 50);
+        assert_eq!(result[0], (Position::First, 0));
+        assert_eq!(result[1], (Position::Middle, 2));
+        assert_eq!(result[49], (Position::Last, 98));
+    }
+}
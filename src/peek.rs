@@ -0,0 +1,123 @@
+use std::iter::FusedIterator;
+
+use PositionIterator;
+
+/// Lookahead for positioned iterators, without consuming any items. This is
+/// useful for parsers which need to know whether they are at the final token,
+/// for example to accept or reject a trailing comma.
+pub trait PositionPeek {
+    /// Return whether the next item is the last one, or `None` if there are no
+    /// more items.
+    fn is_next_last(&mut self) -> Option<bool>;
+}
+
+impl<T> PositionPeek for PositionIterator<T> where T: Iterator {
+    fn is_next_last(&mut self) -> Option<bool> {
+        if self.finished {
+            return None;
+        }
+        if self.pending.is_none() {
+            self.pending = Some(self.iter.next()?);
+        }
+        Some(self.iter.peek().is_none())
+    }
+}
+
+/// Extension trait for double ended iterators which adds the `peek_last`
+/// method.
+pub trait PeekLastWithPosition: DoubleEndedIterator + Sized {
+    /// Take the last item from the back of the iterator, so that it can be
+    /// inspected before iteration starts, for example to measure it. The
+    /// returned iterator still yields all items in order, including the last
+    /// one. For iterators which are not double ended, see
+    /// `WithPosition::peek_last_buffered`.
+    fn peek_last(mut self) -> PeekLast<Self> {
+        let last = self.next_back();
+        PeekLast { iter: self, last }
+    }
+}
+
+impl<I> PeekLastWithPosition for I where I: DoubleEndedIterator {}
+
+/// An iterator adapter which gives access to the last item before it is
+/// yielded, created by `PeekLastWithPosition::peek_last`.
+pub struct PeekLast<I> where I: Iterator {
+    iter: I,
+    last: Option<I::Item>,
+}
+
+impl<I> PeekLast<I> where I: Iterator {
+    pub(crate) fn new(iter: I, last: Option<I::Item>) -> PeekLast<I> {
+        PeekLast { iter, last }
+    }
+
+    /// The last item, or `None` if the iterator is empty or the last item has
+    /// been yielded already.
+    pub fn last_item(&self) -> Option<&I::Item> {
+        self.last.as_ref()
+    }
+}
+
+impl<I> Iterator for PeekLast<I> where I: Iterator {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.iter.next().or_else(|| self.last.take())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let last = if self.last.is_some() { 1 } else { 0 };
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_add(last), upper.and_then(|upper| upper.checked_add(last)))
+    }
+}
+
+impl<I> ExactSizeIterator for PeekLast<I> where I: ExactSizeIterator {}
+
+impl<I> FusedIterator for PeekLast<I> where I: FusedIterator {}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+    use {PeekLastWithPosition, Position, PositionPeek, WithPosition};
+
+    #[test]
+    fn it_looks_ahead_without_consuming() {
+        let mut tokens = vec!["a", ",", "b"].into_iter().with_position();
+
+        assert_eq!(tokens.is_next_last(), Some(false));
+        assert_eq!(tokens.next(), Some((Position::First, "a")));
+        assert_eq!(tokens.next(), Some((Position::Middle, ",")));
+        assert_eq!(tokens.is_next_last(), Some(true));
+        assert_eq!(tokens.is_next_last(), Some(true));
+        assert_eq!(tokens.next(), Some((Position::Last, "b")));
+        assert_eq!(tokens.is_next_last(), None);
+        assert_eq!(tokens.next(), None);
+    }
+
+    #[test]
+    fn it_keeps_the_position_of_a_single_peeked_item() {
+        let mut tokens = Some(1).into_iter().with_position();
+
+        assert_eq!(tokens.is_next_last(), Some(true));
+        assert_eq!(tokens.next(), Some((Position::Only, 1)));
+    }
+
+    #[test]
+    fn it_peeks_at_the_last_item_before_iterating() {
+        let mut iter = vec!["a", "bb", "ccc"].into_iter().peek_last();
+
+        assert_eq!(iter.last_item(), Some(&"ccc"));
+        assert_eq!(iter.by_ref().with_position().collect::<Vec<_>>(), vec![(Position::First, "a"), (Position::Middle, "bb"), (Position::Last, "ccc")]);
+        assert_eq!(iter.last_item(), None);
+    }
+
+    #[test]
+    fn it_peeks_at_the_last_item_by_buffering() {
+        let iter = "a b c".split(' ').filter(|_| true).peek_last_buffered();
+
+        assert_eq!(iter.last_item(), Some(&"c"));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter::empty::<i32>().peek_last_buffered().last_item(), None);
+    }
+}
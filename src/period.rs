@@ -0,0 +1,107 @@
+use std::iter::FusedIterator;
+
+use chrono::Datelike;
+
+use buckets::Runs;
+use Position;
+
+/// A calendar period, for grouping chronologically sorted items.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Period {
+    Day,
+    /// An ISO 8601 week, which starts on Monday.
+    Week,
+    Month,
+    Year,
+}
+
+impl Period {
+    fn key<D>(self, date: &D) -> (i32, u32) where D: Datelike {
+        match self {
+            Period::Day => (date.year(), date.ordinal()),
+            Period::Week => (date.iso_week().year(), date.iso_week().week()),
+            Period::Month => (date.year(), date.month()),
+            Period::Year => (date.year(), 0),
+        }
+    }
+}
+
+/// Extension trait for iterators which adds the `with_period_position` method.
+pub trait PeriodWithPosition: Iterator + Sized {
+    /// Yield a tuple of `(Position, item)`, where the position is the position
+    /// of the item within its calendar period, such as the day of its date.
+    /// The items are expected to be sorted chronologically, so that each
+    /// period is a single run of items. This makes it possible to insert a
+    /// date header before the first item of each day.
+    ///
+    /// ```
+    /// extern crate chrono;
+    /// # extern crate with_position;
+    /// use chrono::NaiveDate;
+    /// use with_position::{Period, PeriodWithPosition, Position};
+    ///
+    /// # fn main() {
+    /// let events = vec![
+    ///     (NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), "breakfast"),
+    ///     (NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), "lunch"),
+    ///     (NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(), "breakfast"),
+    /// ];
+    ///
+    /// let positions: Vec<_> = events.iter().with_period_position(|event| event.0, Period::Day).map(|(position, event)| (position, event.1)).collect();
+    ///
+    /// assert_eq!(positions, vec![(Position::First, "breakfast"), (Position::Last, "lunch"), (Position::Only, "breakfast")]);
+    /// # }
+    /// ```
+    fn with_period_position<F, D>(self, date: F, period: Period) -> PeriodPositions<Self, F> where F: FnMut(&Self::Item) -> D, D: Datelike {
+        PeriodPositions { runs: Runs::new(self), date, period }
+    }
+}
+
+impl<I> PeriodWithPosition for I where I: Iterator {}
+
+/// An iterator adapter that labels items with their position within their
+/// calendar period, created by `PeriodWithPosition::with_period_position`.
+pub struct PeriodPositions<I, F> where I: Iterator {
+    runs: Runs<I, (i32, u32)>,
+    date: F,
+    period: Period,
+}
+
+impl<I, F, D> Iterator for PeriodPositions<I, F> where I: Iterator, F: FnMut(&I::Item) -> D, D: Datelike {
+    type Item = (Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let date = &mut self.date;
+        let period = self.period;
+        let (_, position, item) = self.runs.next_with(|item| period.key(&date(item)))?;
+        Some((position, item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.runs.size_hint()
+    }
+}
+
+impl<I, F, D> FusedIterator for PeriodPositions<I, F> where I: Iterator, F: FnMut(&I::Item) -> D, D: Datelike {}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use {Period, PeriodWithPosition, Position};
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn it_marks_positions_within_weeks_and_months() {
+        // 2024-01-07 is a Sunday and 2024-01-08 a Monday.
+        let dates = [date(1, 6), date(1, 7), date(1, 8), date(2, 1)];
+
+        let weeks: Vec<_> = dates.iter().with_period_position(|&&date| date, Period::Week).map(|(position, _)| position).collect();
+        let months: Vec<_> = dates.iter().with_period_position(|&&date| date, Period::Month).map(|(position, _)| position).collect();
+
+        assert_eq!(weeks, vec![Position::First, Position::Last, Position::Only, Position::Only]);
+        assert_eq!(months, vec![Position::First, Position::Middle, Position::Last, Position::Only]);
+    }
+}
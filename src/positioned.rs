@@ -0,0 +1,189 @@
+use std::iter::FromIterator;
+use std::ops::{Deref, Range};
+use std::slice;
+use std::vec;
+
+use Position;
+
+/// An item together with its position, as a named-field alternative to the
+/// `(Position, item)` tuples yielded by the iterator adapters.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Positioned<T> {
+    pub position: Position,
+    pub value: T,
+}
+
+impl<T> From<(Position, T)> for Positioned<T> {
+    fn from((position, value): (Position, T)) -> Positioned<T> {
+        Positioned { position, value }
+    }
+}
+
+/// A vector of positioned items. The positions are recomputed from the index of
+/// each item when collecting and updated whenever items are added or removed,
+/// so they are always consistent with the contents.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PositionedVec<T> {
+    items: Vec<Positioned<T>>,
+}
+
+impl<T> PositionedVec<T> {
+    pub fn new() -> PositionedVec<T> {
+        PositionedVec { items: Vec::new() }
+    }
+
+    /// Append a value to the end of the vector. The previous last item becomes a
+    /// middle item.
+    pub fn push(&mut self, value: T) {
+        self.items.push(Positioned { position: Position::Only, value });
+        let len = self.items.len();
+        self.relabel(len.saturating_sub(2)..len);
+    }
+
+    /// Remove the last value from the vector, the previous item becomes the
+    /// last item.
+    pub fn pop(&mut self) -> Option<T> {
+        let item = self.items.pop()?;
+        let len = self.items.len();
+        self.relabel(len.saturating_sub(1)..len);
+        Some(item.value)
+    }
+
+    /// Insert a value at the given index, shifting all items after it.
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        self.items.insert(index, Positioned { position: Position::Only, value });
+        self.relabel(index.saturating_sub(1)..index + 2);
+    }
+
+    /// Remove and return the value at the given index, shifting all items after
+    /// it.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        let item = self.items.remove(index);
+        self.relabel(index.saturating_sub(1)..index + 1);
+        item.value
+    }
+
+    /// Get a mutable reference to the value at the given index. Only the value
+    /// can be changed, since the position is determined by the index.
+    pub fn value_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.items.get_mut(index).map(|item| &mut item.value)
+    }
+
+    /// Convert into a vector of the values, discarding the positions.
+    pub fn into_values(self) -> Vec<T> {
+        self.items.into_iter().map(|item| item.value).collect()
+    }
+
+    fn relabel(&mut self, range: Range<usize>) {
+        let len = self.items.len();
+        for index in range.start..range.end.min(len) {
+            self.items[index].position = Position::from_index(index, len);
+        }
+    }
+}
+
+impl<T> Default for PositionedVec<T> {
+    fn default() -> PositionedVec<T> {
+        PositionedVec::new()
+    }
+}
+
+impl<T> Deref for PositionedVec<T> {
+    type Target = [Positioned<T>];
+
+    fn deref(&self) -> &[Positioned<T>] {
+        &self.items
+    }
+}
+
+impl<T> FromIterator<(Position, T)> for PositionedVec<T> {
+    fn from_iter<I>(iter: I) -> PositionedVec<T> where I: IntoIterator<Item = (Position, T)> {
+        let values: Vec<T> = iter.into_iter().map(|(_, value)| value).collect();
+        let len = values.len();
+        let items = values.into_iter().enumerate().map(|(index, value)| {
+            Positioned { position: Position::from_index(index, len), value }
+        }).collect();
+
+        PositionedVec { items }
+    }
+}
+
+impl<T> IntoIterator for PositionedVec<T> {
+    type Item = Positioned<T>;
+    type IntoIter = vec::IntoIter<Positioned<T>>;
+
+    fn into_iter(self) -> vec::IntoIter<Positioned<T>> {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PositionedVec<T> {
+    type Item = &'a Positioned<T>;
+    type IntoIter = slice::Iter<'a, Positioned<T>>;
+
+    fn into_iter(self) -> slice::Iter<'a, Positioned<T>> {
+        self.items.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Position, Positioned, PositionedVec, WithPosition};
+
+    #[test]
+    fn it_collects_into_named_fields() {
+        let result = vec!["a", "b"].into_iter().collect_positioned();
+
+        assert_eq!(result, vec![
+            Positioned { position: Position::First, value: "a" },
+            Positioned { position: Position::Last, value: "b" },
+        ]);
+    }
+
+    #[test]
+    fn it_collects_into_a_positioned_vec() {
+        let result: PositionedVec<_> = vec![1, 2, 3].into_iter().with_position().collect();
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].position, Position::First);
+        assert_eq!(result[1].position, Position::Middle);
+        assert_eq!(result[2], Positioned { position: Position::Last, value: 3 });
+    }
+
+    #[test]
+    fn it_keeps_positions_correct_when_pushing_and_popping() {
+        let mut list = PositionedVec::new();
+
+        list.push("a");
+        assert_eq!(list[0].position, Position::Only);
+
+        list.push("b");
+        list.push("c");
+        let positions: Vec<_> = list.iter().map(|item| item.position).collect();
+        assert_eq!(positions, vec![Position::First, Position::Middle, Position::Last]);
+
+        assert_eq!(list.pop(), Some("c"));
+        assert_eq!(list[1].position, Position::Last);
+        assert_eq!(list.pop(), Some("b"));
+        assert_eq!(list[0].position, Position::Only);
+    }
+
+    #[test]
+    fn it_keeps_positions_correct_when_inserting_and_removing() {
+        let mut list: PositionedVec<_> = vec![1, 2].into_iter().with_position().collect();
+
+        list.insert(0, 0);
+        list.insert(3, 3);
+        let positions: Vec<_> = list.iter().map(|item| item.position).collect();
+        assert_eq!(positions, vec![Position::First, Position::Middle, Position::Middle, Position::Last]);
+
+        assert_eq!(list.remove(0), 0);
+        assert_eq!(list.remove(2), 3);
+        assert_eq!(list[0], Positioned { position: Position::First, value: 1 });
+        assert_eq!(list[1], Positioned { position: Position::Last, value: 2 });
+    }
+}
@@ -0,0 +1,169 @@
+use std::iter::{Fuse, FusedIterator};
+
+use inline::InlineVec;
+use Position;
+
+/// An iterator adapter that labels the items for which a predicate returns
+/// true with their position among those items, created by
+/// `WithPosition::with_position_where`. Yields `(Some(position), item)` for
+/// counted items and `(None, item)` for ignored ones, which stay in their
+/// original place.
+///
+/// Finding out whether an item is the last counted item may require reading
+/// past a run of ignored items, which are buffered until they are yielded.
+pub struct PositionWhere<I, F> where I: Iterator {
+    iter: I,
+    counts: F,
+    // The buffered items in reverse order, so the next one is popped off the
+    // end.
+    buffer: InlineVec<(bool, I::Item)>,
+    did_iter: bool,
+    done: bool,
+}
+
+impl<I, F> PositionWhere<I, F> where I: Iterator, F: FnMut(&I::Item) -> bool {
+    pub(crate) fn new(iter: I, counts: F) -> PositionWhere<I, F> {
+        PositionWhere { iter, counts, buffer: InlineVec::new(), did_iter: false, done: false }
+    }
+
+    fn pull(&mut self) -> Option<bool> {
+        if self.done {
+            return None;
+        }
+        match self.iter.next() {
+            Some(item) => {
+                let counted = (self.counts)(&item);
+                self.buffer.push((counted, item));
+                Some(counted)
+            }
+            None => {
+                self.done = true;
+                None
+            }
+        }
+    }
+
+    fn has_counted_ahead(&mut self) -> bool {
+        if self.buffer.iter().any(|&(counted, _)| counted) {
+            return true;
+        }
+        // Pulled items are read after the buffered ones, so they are moved in
+        // front of them.
+        let buffered = self.buffer.len();
+        let mut found = false;
+        while let Some(counted) = self.pull() {
+            if counted {
+                found = true;
+                break;
+            }
+        }
+        self.buffer[buffered..].reverse();
+        self.buffer.rotate_left(buffered);
+        found
+    }
+}
+
+impl<I, F> Iterator for PositionWhere<I, F> where I: Iterator, F: FnMut(&I::Item) -> bool {
+    type Item = (Option<Position>, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty() {
+            self.pull();
+        }
+
+        let (counted, item) = self.buffer.pop()?;
+        if !counted {
+            return Some((None, item));
+        }
+
+        let is_last = !self.has_counted_ahead();
+        let is_first = !self.did_iter;
+        self.did_iter = true;
+        Some((Some(Position::from_flags(is_first, is_last)), item))
+    }
+}
+
+/// An iterator adapter that yields only the items for which a predicate
+/// returns true, labelled with their position among those items, created by
+/// `WithPosition::position_after_filter`.
+pub struct PositionAfterFilter<I, P> where I: Iterator {
+    iter: Fuse<I>,
+    predicate: P,
+    next: Option<I::Item>,
+    did_iter: bool,
+}
+
+impl<I, P> PositionAfterFilter<I, P> where I: Iterator, P: FnMut(&I::Item) -> bool {
+    pub(crate) fn new(iter: I, predicate: P) -> PositionAfterFilter<I, P> {
+        PositionAfterFilter { iter: iter.fuse(), predicate, next: None, did_iter: false }
+    }
+
+    fn find_next(&mut self) -> Option<I::Item> {
+        let predicate = &mut self.predicate;
+        self.iter.find(|item| predicate(item))
+    }
+}
+
+impl<I, P> Iterator for PositionAfterFilter<I, P> where I: Iterator, P: FnMut(&I::Item) -> bool {
+    type Item = (Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = match self.next.take() {
+            Some(item) => item,
+            None => self.find_next()?,
+        };
+        self.next = self.find_next();
+
+        let is_first = !self.did_iter;
+        self.did_iter = true;
+        Some((Position::from_flags(is_first, self.next.is_none()), item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = if self.next.is_some() { 1 } else { 0 };
+        let (_, upper) = self.iter.size_hint();
+        (pending, upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+impl<I, P> FusedIterator for PositionAfterFilter<I, P> where I: Iterator, P: FnMut(&I::Item) -> bool {}
+
+#[cfg(test)]
+mod tests {
+    use {Position, WithPosition};
+
+    #[test]
+    fn it_skips_ignored_items_when_looking_for_the_last_item() {
+        let lines = vec!["a", "# comment", "b", "# trailing"];
+
+        let result: Vec<_> = lines.into_iter().with_position_where(|line| !line.starts_with('#')).collect();
+
+        assert_eq!(result, vec![
+            (Some(Position::First), "a"),
+            (None, "# comment"),
+            (Some(Position::Last), "b"),
+            (None, "# trailing"),
+        ]);
+    }
+
+    #[test]
+    fn it_yields_only_ignored_items_unlabeled() {
+        let result: Vec<_> = vec![1, 3].into_iter().with_position_where(|n| n % 2 == 0).collect();
+
+        assert_eq!(result, vec![(None, 1), (None, 3)]);
+    }
+
+    #[test]
+    fn it_assigns_positions_after_filtering() {
+        let result: Vec<_> = (1..8).position_after_filter(|n| n % 3 != 0).collect();
+
+        assert_eq!(result, vec![
+            (Position::First, 1),
+            (Position::Middle, 2),
+            (Position::Middle, 4),
+            (Position::Middle, 5),
+            (Position::Last, 7),
+        ]);
+        assert_eq!((1..4).position_after_filter(|&n| n == 2).collect::<Vec<_>>(), vec![(Position::Only, 2)]);
+    }
+}
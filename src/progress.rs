@@ -0,0 +1,96 @@
+use indicatif::ProgressBar;
+
+use {Position, PositionIterator, WithPosition};
+
+/// Extension trait for iterators which adds the `with_progress_bar` method.
+pub trait ProgressWithPosition: Iterator + Sized {
+    /// Yield a tuple of `(Position, item)` like `with_position`, while
+    /// advancing the progress bar by one for each item. If the number of items
+    /// is known from the size hint, it is set as the length of the bar. The
+    /// bar is finished when the last item is yielded, and cleared if the
+    /// iterator is dropped before that.
+    fn with_progress_bar(self, bar: ProgressBar) -> PositionProgress<Self> {
+        if let (lower, Some(upper)) = self.size_hint() {
+            if lower == upper {
+                bar.set_length(lower as u64);
+            }
+        }
+        PositionProgress { iter: self.with_position(), bar }
+    }
+}
+
+impl<I> ProgressWithPosition for I where I: Iterator {}
+
+/// An iterator adapter that yields tuples of `(Position, item)` and reports
+/// progress to a progress bar, created by
+/// `ProgressWithPosition::with_progress_bar`.
+pub struct PositionProgress<I> where I: Iterator {
+    iter: PositionIterator<I>,
+    bar: ProgressBar,
+}
+
+impl<I> PositionProgress<I> where I: Iterator {
+    /// The progress bar which is being advanced.
+    pub fn progress_bar(&self) -> &ProgressBar {
+        &self.bar
+    }
+}
+
+impl<I> Iterator for PositionProgress<I> where I: Iterator {
+    type Item = (Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.next() {
+            Some((position, item)) => {
+                self.bar.inc(1);
+                if position.is_last() {
+                    self.bar.finish();
+                }
+                Some((position, item))
+            }
+            None => {
+                if !self.bar.is_finished() {
+                    self.bar.finish();
+                }
+                None
+            }
+        }
+    }
+}
+
+impl<I> Drop for PositionProgress<I> where I: Iterator {
+    fn drop(&mut self) {
+        if !self.bar.is_finished() {
+            self.bar.finish_and_clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indicatif::ProgressBar;
+    use {Position, ProgressWithPosition};
+
+    #[test]
+    fn it_advances_and_finishes_the_progress_bar() {
+        let bar = ProgressBar::hidden();
+        let mut iter = vec![1, 2].into_iter().with_progress_bar(bar.clone());
+
+        assert_eq!(bar.length(), Some(2));
+        assert_eq!(iter.next(), Some((Position::First, 1)));
+        assert!(!bar.is_finished());
+        assert_eq!(iter.next(), Some((Position::Last, 2)));
+        assert_eq!((bar.position(), bar.is_finished()), (2, true));
+    }
+
+    #[test]
+    fn it_clears_the_progress_bar_when_dropped_early() {
+        let bar = ProgressBar::hidden();
+        let mut iter = (0..).with_progress_bar(bar.clone());
+        iter.next();
+        drop(iter);
+
+        assert_eq!(bar.length(), None);
+        assert!(bar.is_finished());
+    }
+}
@@ -0,0 +1,70 @@
+use syn::punctuated::{Iter, Pairs, Punctuated};
+
+use {Position, PositionIterator, WithPosition};
+
+/// Build a `Punctuated` from positioned values, with a default punctuation
+/// token after every value except the last, so that there is no trailing
+/// punctuation.
+pub fn punctuated_from_positioned<I, T, P>(iter: I) -> Punctuated<T, P> where I: IntoIterator<Item = (Position, T)>, P: Default {
+    let mut punctuated = Punctuated::new();
+    for (position, value) in iter {
+        punctuated.push_value(value);
+        if !position.is_last() {
+            punctuated.push_punct(P::default());
+        }
+    }
+    punctuated
+}
+
+/// Extension trait for `Punctuated` which adds positioned iteration.
+pub trait PunctuatedWithPosition<T, P> {
+    /// Iterate over the values, yielding a tuple of `(Position, &T)`.
+    fn iter_with_position(&self) -> PositionIterator<Iter<'_, T>>;
+
+    /// Iterate over the pairs of values and punctuation, yielding a tuple of
+    /// `(Position, Pair<&T, &P>)`. The last pair only has punctuation if the
+    /// list has trailing punctuation.
+    fn pairs_with_position(&self) -> PositionIterator<Pairs<'_, T, P>>;
+}
+
+impl<T, P> PunctuatedWithPosition<T, P> for Punctuated<T, P> {
+    fn iter_with_position(&self) -> PositionIterator<Iter<'_, T>> {
+        self.iter().with_position()
+    }
+
+    fn pairs_with_position(&self) -> PositionIterator<Pairs<'_, T, P>> {
+        self.pairs().with_position()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+    use syn::punctuated::{Pair, Punctuated};
+    use syn::token::Comma;
+    use syn::{Ident, LitInt};
+    use {punctuated_from_positioned, Position, PunctuatedWithPosition, WithPosition};
+
+    #[test]
+    fn it_builds_punctuated_without_trailing_punctuation() {
+        let args: Punctuated<LitInt, Comma> = punctuated_from_positioned(["1", "2", "3"].iter().map(|n| LitInt::new(n, Span::call_site())).with_position());
+
+        assert_eq!(args.len(), 3);
+        assert!(!args.trailing_punct());
+        assert!(punctuated_from_positioned::<_, LitInt, Comma>(None).is_empty());
+    }
+
+    #[test]
+    fn it_iterates_punctuated_with_positions() {
+        let mut idents: Punctuated<Ident, Comma> = Punctuated::new();
+        idents.push(Ident::new("a", Span::call_site()));
+        idents.push(Ident::new("b", Span::call_site()));
+        idents.push_punct(Comma::default());
+
+        let values: Vec<_> = idents.iter_with_position().map(|(position, ident)| (position, ident.to_string())).collect();
+        assert_eq!(values, vec![(Position::First, String::from("a")), (Position::Last, String::from("b"))]);
+
+        let pairs: Vec<_> = idents.pairs_with_position().map(|(position, pair)| (position, match pair { Pair::Punctuated(..) => true, Pair::End(_) => false })).collect();
+        assert_eq!(pairs, vec![(Position::First, true), (Position::Last, true)]);
+    }
+}
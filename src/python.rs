@@ -0,0 +1,62 @@
+use std::sync::Mutex;
+
+use pyo3::prelude::*;
+
+use {Position, WithPosition};
+
+type PositionedObjects = Box<dyn Iterator<Item = (Position, Py<PyAny>)> + Send>;
+
+/// A Python iterator which yields tuples of `(position, value)`, where
+/// `position` is one of the strings `"first"`, `"middle"`, `"last"` or
+/// `"only"`. Return it from a `#[pyfunction]` to hand positioned items to
+/// Python.
+///
+/// ```text
+/// for position, value in rust.items():
+///     print(position, value)
+/// ```
+#[pyclass(name = "PositionIterator")]
+pub struct PyPositionIterator {
+    iter: Mutex<PositionedObjects>,
+}
+
+impl PyPositionIterator {
+    pub fn new<I>(iter: I) -> PyPositionIterator where I: IntoIterator<Item = Py<PyAny>>, I::IntoIter: Send + 'static {
+        PyPositionIterator { iter: Mutex::new(Box::new(iter.into_iter().with_position())) }
+    }
+}
+
+#[pymethods]
+impl PyPositionIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(slf: PyRefMut<'_, Self>) -> Option<(&'static str, Py<PyAny>)> {
+        let next = slf.iter.lock().unwrap().next();
+        next.map(|(position, value)| (position.as_str(), value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::prelude::*;
+    use pyo3::types::PyDict;
+    use pyo3;
+    use PyPositionIterator;
+
+    #[test]
+    fn it_yields_positioned_tuples_to_python() {
+        Python::initialize();
+        Python::attach(|py| {
+            let values = vec!["a", "b"].into_iter().map(|value| value.into_pyobject(py).unwrap().into_any().unbind()).collect::<Vec<_>>();
+            let iter = Py::new(py, PyPositionIterator::new(values)).unwrap();
+            let locals = PyDict::new(py);
+            locals.set_item("items", iter).unwrap();
+
+            let result: Vec<(String, String)> = py.eval(pyo3::ffi::c_str!("list(items)"), None, Some(&locals)).unwrap().extract().unwrap();
+
+            assert_eq!(result, vec![(String::from("first"), String::from("a")), (String::from("last"), String::from("b"))]);
+        });
+    }
+}
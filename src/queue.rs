@@ -0,0 +1,123 @@
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+
+use Position;
+
+/// A queue which can be shared between threads, for example in an `Arc`, where
+/// producers push items and consumers pop them as `(Position, item)`. An item
+/// is only popped once the next item has been pushed or the queue has been
+/// closed, so that the final item can be labelled `Last`.
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+/// use with_position::{Position, SharedPositionQueue};
+///
+/// let queue = Arc::new(SharedPositionQueue::new());
+/// let producer = {
+///     let queue = queue.clone();
+///     thread::spawn(move || {
+///         queue.push("a").unwrap();
+///         queue.push("b").unwrap();
+///         queue.close();
+///     })
+/// };
+///
+/// assert_eq!(queue.pop(), Some((Position::First, "a")));
+/// assert_eq!(queue.pop(), Some((Position::Last, "b")));
+/// assert_eq!(queue.pop(), None);
+/// producer.join().unwrap();
+/// ```
+pub struct SharedPositionQueue<T> {
+    state: Mutex<QueueState<T>>,
+    changed: Condvar,
+}
+
+struct QueueState<T> {
+    items: VecDeque<T>,
+    closed: bool,
+    did_pop: bool,
+}
+
+impl<T> SharedPositionQueue<T> {
+    pub fn new() -> SharedPositionQueue<T> {
+        SharedPositionQueue { state: Mutex::new(QueueState { items: VecDeque::new(), closed: false, did_pop: false }), changed: Condvar::new() }
+    }
+
+    /// Push an item onto the back of the queue. Returns the item as an error
+    /// if the queue has already been closed.
+    pub fn push(&self, item: T) -> Result<(), T> {
+        let mut state = self.state.lock().unwrap();
+        if state.closed {
+            return Err(item);
+        }
+        state.items.push_back(item);
+        self.changed.notify_all();
+        Ok(())
+    }
+
+    /// Close the queue, after which no more items can be pushed and the last
+    /// item can be popped.
+    pub fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.changed.notify_all();
+    }
+
+    /// Block until the next positioned item can be popped, returning `None`
+    /// once the queue is closed and all items have been popped.
+    pub fn pop(&self) -> Option<(Position, T)> {
+        let mut state = self.state.lock().unwrap();
+        while !state.closed && state.items.len() < 2 {
+            state = self.changed.wait(state).unwrap();
+        }
+        let item = state.items.pop_front()?;
+        let is_first = !state.did_pop;
+        state.did_pop = true;
+        Some((Position::from_flags(is_first, state.items.is_empty()), item))
+    }
+}
+
+impl<T> Default for SharedPositionQueue<T> {
+    fn default() -> SharedPositionQueue<T> {
+        SharedPositionQueue::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+    use {Position, SharedPositionQueue};
+
+    #[test]
+    fn it_hands_items_between_threads_with_positions() {
+        let queue = Arc::new(SharedPositionQueue::new());
+        let consumer = {
+            let queue = queue.clone();
+            thread::spawn(move || {
+                let mut result = Vec::new();
+                while let Some(item) = queue.pop() {
+                    result.push(item);
+                }
+                result
+            })
+        };
+        for n in 0..3 {
+            queue.push(n).unwrap();
+        }
+        queue.close();
+
+        assert_eq!(consumer.join().unwrap(), vec![(Position::First, 0), (Position::Middle, 1), (Position::Last, 2)]);
+    }
+
+    #[test]
+    fn it_rejects_items_after_closing() {
+        let queue = SharedPositionQueue::new();
+        queue.push("a").unwrap();
+        queue.close();
+
+        assert_eq!(queue.push("b"), Err("b"));
+        assert_eq!(queue.pop(), Some((Position::Only, "a")));
+        assert_eq!(queue.pop(), None);
+    }
+}
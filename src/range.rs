@@ -0,0 +1,102 @@
+use std::iter::FusedIterator;
+use std::ops::{Range, RangeInclusive};
+
+use Position;
+
+/// A range of integers which knows when it has been exhausted. This is
+/// implemented for `Range` and `RangeInclusive` of all integer types.
+pub trait PositionRange: DoubleEndedIterator {
+    fn is_exhausted(&self) -> bool;
+}
+
+impl<T> PositionRange for Range<T> where T: PartialOrd, Range<T>: DoubleEndedIterator {
+    fn is_exhausted(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl<T> PositionRange for RangeInclusive<T> where T: PartialOrd, RangeInclusive<T>: DoubleEndedIterator {
+    fn is_exhausted(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+/// An iterator adapter over a range which computes positions from the bounds of
+/// the range, rather than peeking ahead.
+pub struct RangePositionIterator<R> {
+    range: R,
+    front_started: bool,
+    back_started: bool,
+}
+
+/// Iterate over a range of integers, yielding a tuple of `(Position, number)`.
+/// This works from both ends, and for ranges of any integer type.
+///
+/// ```
+/// use with_position::{range_with_position, Position};
+///
+/// let mut iter = range_with_position(0u64..=2);
+///
+/// assert_eq!(iter.next(), Some((Position::First, 0)));
+/// assert_eq!(iter.next_back(), Some((Position::Last, 2)));
+/// assert_eq!(iter.next(), Some((Position::Middle, 1)));
+/// ```
+pub fn range_with_position<R>(range: R) -> RangePositionIterator<R> where R: PositionRange {
+    RangePositionIterator { range, front_started: false, back_started: false }
+}
+
+impl<R> Iterator for RangePositionIterator<R> where R: PositionRange {
+    type Item = (Position, R::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.range.next()?;
+        let is_first = !self.front_started;
+        let is_last = self.range.is_exhausted() && !self.back_started;
+        self.front_started = true;
+        Some((Position::from_flags(is_first, is_last), item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<R> DoubleEndedIterator for RangePositionIterator<R> where R: PositionRange {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let item = self.range.next_back()?;
+        let is_first = self.range.is_exhausted() && !self.front_started;
+        let is_last = !self.back_started;
+        self.back_started = true;
+        Some((Position::from_flags(is_first, is_last), item))
+    }
+}
+
+impl<R> ExactSizeIterator for RangePositionIterator<R> where R: PositionRange + ExactSizeIterator {}
+
+impl<R> FusedIterator for RangePositionIterator<R> where R: PositionRange + FusedIterator {}
+
+#[cfg(test)]
+mod tests {
+    use {range_with_position, Position};
+
+    #[test]
+    fn it_marks_positions_in_a_range() {
+        let result: Vec<_> = range_with_position(1..4).collect();
+
+        assert_eq!(result, vec![(Position::First, 1), (Position::Middle, 2), (Position::Last, 3)]);
+    }
+
+    #[test]
+    fn it_marks_positions_in_a_reversed_inclusive_range() {
+        let result: Vec<_> = range_with_position(1i64..=3).rev().collect();
+
+        assert_eq!(result, vec![(Position::Last, 3), (Position::Middle, 2), (Position::First, 1)]);
+    }
+
+    #[test]
+    fn it_marks_only_and_empty_ranges() {
+        assert_eq!(range_with_position(5u8..6).collect::<Vec<_>>(), vec![(Position::Only, 5)]);
+        assert_eq!(range_with_position(5u8..5).next(), None);
+        assert_eq!(range_with_position(0..10).len(), 10);
+    }
+}
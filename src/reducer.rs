@@ -0,0 +1,128 @@
+use Position;
+
+/// An accumulation which is finalized after the last item, driven by
+/// `WithPosition::reduce_positioned`. The accumulator is created with `init`
+/// before the first item, every item is passed to `step` together with its
+/// position, and `finish` is called right after the `Last` or `Only` item has
+/// been stepped. When there are no items, `empty` is called instead, which by
+/// default finishes a fresh accumulator.
+///
+/// ```
+/// use with_position::{Position, PositionedReducer, WithPosition};
+///
+/// struct Csv;
+///
+/// impl<'a> PositionedReducer<&'a str> for Csv {
+///     type Acc = String;
+///     type Output = String;
+///
+///     fn init(&mut self) -> String {
+///         String::new()
+///     }
+///
+///     fn step(&mut self, mut line: String, position: Position, field: &'a str) -> String {
+///         line.push_str(field);
+///         if !position.is_last() {
+///             line.push(',');
+///         }
+///         line
+///     }
+///
+///     fn finish(&mut self, mut line: String) -> String {
+///         line.push('\n');
+///         line
+///     }
+///
+///     fn empty(&mut self) -> String {
+///         String::new()
+///     }
+/// }
+///
+/// assert_eq!(vec!["a", "b"].into_iter().reduce_positioned(Csv), "a,b\n");
+/// assert_eq!(Vec::<&str>::new().into_iter().reduce_positioned(Csv), "");
+/// ```
+pub trait PositionedReducer<T> {
+    type Acc;
+    type Output;
+
+    /// Create the accumulator before the first item.
+    fn init(&mut self) -> Self::Acc;
+
+    /// Add an item at the position to the accumulator.
+    fn step(&mut self, acc: Self::Acc, position: Position, item: T) -> Self::Acc;
+
+    /// Turn the accumulator into the output after the last item.
+    fn finish(&mut self, acc: Self::Acc) -> Self::Output;
+
+    /// Return the output when there are no items at all.
+    fn empty(&mut self) -> Self::Output {
+        let acc = self.init();
+        self.finish(acc)
+    }
+}
+
+impl<T, R> PositionedReducer<T> for &mut R where R: PositionedReducer<T> + ?Sized {
+    type Acc = R::Acc;
+    type Output = R::Output;
+
+    fn init(&mut self) -> R::Acc {
+        (**self).init()
+    }
+
+    fn step(&mut self, acc: R::Acc, position: Position, item: T) -> R::Acc {
+        (**self).step(acc, position, item)
+    }
+
+    fn finish(&mut self, acc: R::Acc) -> R::Output {
+        (**self).finish(acc)
+    }
+
+    fn empty(&mut self) -> R::Output {
+        (**self).empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Position, PositionedReducer, WithPosition};
+
+    /// Records the calls made by the driver.
+    #[derive(Default)]
+    struct Calls(Vec<String>);
+
+    impl PositionedReducer<u32> for Calls {
+        type Acc = u32;
+        type Output = u32;
+
+        fn init(&mut self) -> u32 {
+            self.0.push(String::from("init"));
+            0
+        }
+
+        fn step(&mut self, acc: u32, position: Position, item: u32) -> u32 {
+            self.0.push(format!("step {:?} {}", position, item));
+            acc + item
+        }
+
+        fn finish(&mut self, acc: u32) -> u32 {
+            self.0.push(String::from("finish"));
+            acc
+        }
+    }
+
+    #[test]
+    fn it_finishes_after_the_last_item() {
+        let mut calls = Calls::default();
+
+        assert_eq!(vec![1, 2].into_iter().reduce_positioned(&mut calls), 3);
+        assert_eq!(calls.0, vec!["init", "step First 1", "step Last 2", "finish"]);
+    }
+
+    #[test]
+    fn it_calls_empty_without_items() {
+        let mut calls = Calls::default();
+
+        assert_eq!(None.into_iter().reduce_positioned(&mut calls), 0);
+        assert_eq!(calls.0, vec!["init", "finish"]);
+    }
+}
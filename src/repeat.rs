@@ -0,0 +1,77 @@
+use std::iter::FusedIterator;
+
+use Position;
+
+/// An iterator which yields a value `n` times, created by
+/// `repeat_n_with_position`.
+pub struct RepeatNWithPosition<T> {
+    value: Option<T>,
+    index: usize,
+    n: usize,
+}
+
+/// Yield the value `n` times, paired with positions which are computed from
+/// the count rather than by peeking ahead. The value is cloned for all but the
+/// last item, which gets the value itself, so a single item is yielded as
+/// `Only` without cloning, and nothing is yielded if `n` is 0.
+///
+/// ```
+/// use with_position::{repeat_n_with_position, Position};
+///
+/// let rows: Vec<_> = repeat_n_with_position("-", 3).collect();
+///
+/// assert_eq!(rows, vec![(Position::First, "-"), (Position::Middle, "-"), (Position::Last, "-")]);
+/// ```
+pub fn repeat_n_with_position<T>(value: T, n: usize) -> RepeatNWithPosition<T> where T: Clone {
+    RepeatNWithPosition { value: if n == 0 { None } else { Some(value) }, index: 0, n }
+}
+
+impl<T> Iterator for RepeatNWithPosition<T> where T: Clone {
+    type Item = (Position, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index + 1 < self.n {
+            let position = Position::from_index(self.index, self.n);
+            self.index += 1;
+            return self.value.clone().map(|value| (position, value));
+        }
+        let value = self.value.take()?;
+        let position = Position::from_index(self.index, self.n);
+        self.index += 1;
+        Some((position, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.n - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for RepeatNWithPosition<T> where T: Clone {}
+
+impl<T> FusedIterator for RepeatNWithPosition<T> where T: Clone {}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use {repeat_n_with_position, Position};
+
+    #[test]
+    fn it_yields_a_single_value_as_only_without_cloning() {
+        let value = Rc::new(1);
+        let mut iter = repeat_n_with_position(Rc::clone(&value), 1);
+
+        assert_eq!(Rc::strong_count(&value), 2);
+        assert_eq!(iter.next().map(|(position, _)| position), Some(Position::Only));
+        assert_eq!(Rc::strong_count(&value), 1);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn it_yields_nothing_for_zero_repetitions() {
+        let mut iter = repeat_n_with_position('x', 0);
+
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+    }
+}
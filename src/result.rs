@@ -0,0 +1,143 @@
+use std::iter::{FusedIterator, Peekable};
+
+use {Position, PositionWhere, WithPosition};
+
+/// Extension trait for iterators over results which adds the
+/// `with_position_ok` method.
+pub trait ResultWithPosition<T, E>: Iterator<Item = Result<T, E>> + Sized {
+    /// Yield `Ok((Position, item))` for each `Ok` item, where the position is
+    /// computed over the `Ok` items only, so an error between two items or at
+    /// the end does not affect which item is labeled as the last one. Errors
+    /// are yielded unchanged, in their original place.
+    ///
+    /// This is `with_position_where` with `Result::is_ok` as the predicate.
+    fn with_position_ok(self) -> PositionOk<Self> {
+        PositionOk { inner: self.with_position_where(Result::is_ok) }
+    }
+
+    /// Yield `Ok((Position, item))` for each `Ok` item until the first error,
+    /// which is yielded as is, after which the iterator is fused. The item right
+    /// before an error is labeled as the last one, which makes it possible to
+    /// close whatever was opened for the first item even when a failure ends
+    /// the iteration early.
+    fn try_with_position(self) -> PositionTry<Self> {
+        PositionTry { iter: self.peekable(), did_iter: false, done: false }
+    }
+}
+
+impl<I, T, E> ResultWithPosition<T, E> for I where I: Iterator<Item = Result<T, E>> {}
+
+/// An iterator adapter that labels the `Ok` items of an iterator over results
+/// with their position among the `Ok` items.
+pub struct PositionOk<I> where I: Iterator {
+    inner: PositionWhere<I, fn(&I::Item) -> bool>,
+}
+
+impl<I, T, E> Iterator for PositionOk<I> where I: Iterator<Item = Result<T, E>> {
+    type Item = Result<(Position, T), E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner.next()? {
+            (Some(position), Ok(value)) => Some(Ok((position, value))),
+            (_, Ok(_)) => unreachable!("ok items are always counted"),
+            (_, Err(error)) => Some(Err(error)),
+        }
+    }
+}
+
+/// An iterator adapter that labels the `Ok` items of an iterator over results
+/// with their position, ending the iteration at the first error.
+pub struct PositionTry<I> where I: Iterator {
+    iter: Peekable<I>,
+    did_iter: bool,
+    done: bool,
+}
+
+impl<I, T, E> Iterator for PositionTry<I> where I: Iterator<Item = Result<T, E>> {
+    type Item = Result<(Position, T), E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let value = match self.iter.next() {
+            Some(Ok(value)) => value,
+            Some(Err(error)) => {
+                self.done = true;
+                return Some(Err(error));
+            }
+            None => {
+                self.done = true;
+                return None;
+            }
+        };
+
+        let is_last = !matches!(self.iter.peek(), Some(&Ok(_)));
+        let is_first = !self.did_iter;
+        self.did_iter = true;
+        Some(Ok((Position::from_flags(is_first, is_last), value)))
+    }
+}
+
+impl<I, T, E> FusedIterator for PositionTry<I> where I: Iterator<Item = Result<T, E>> {}
+
+#[cfg(test)]
+mod tests {
+    use {Position, ResultWithPosition};
+
+    #[test]
+    fn it_marks_positions_of_ok_items_only() {
+        let items: Vec<Result<i32, &str>> = vec![Ok(1), Err("a"), Ok(2), Err("b")];
+
+        let result: Vec<_> = items.into_iter().with_position_ok().collect();
+
+        assert_eq!(result, vec![Ok((Position::First, 1)), Err("a"), Ok((Position::Last, 2)), Err("b")]);
+    }
+
+    #[test]
+    fn it_reads_past_a_run_of_errors_to_find_the_last_item() {
+        let items: Vec<Result<i32, &str>> = vec![Err("a"), Ok(1), Err("b"), Err("c"), Ok(2), Ok(3)];
+
+        let result: Vec<_> = items.into_iter().with_position_ok().collect();
+
+        assert_eq!(result, vec![
+            Err("a"),
+            Ok((Position::First, 1)),
+            Err("b"),
+            Err("c"),
+            Ok((Position::Middle, 2)),
+            Ok((Position::Last, 3)),
+        ]);
+    }
+
+    #[test]
+    fn it_marks_only_position_for_a_single_ok_item() {
+        let items: Vec<Result<i32, &str>> = vec![Err("a"), Ok(1), Err("b")];
+
+        let result: Vec<_> = items.into_iter().with_position_ok().collect();
+
+        assert_eq!(result, vec![Err("a"), Ok((Position::Only, 1)), Err("b")]);
+    }
+
+    #[test]
+    fn it_marks_the_item_before_the_first_error_as_last() {
+        let items: Vec<Result<i32, &str>> = vec![Ok(1), Ok(2), Err("a"), Ok(3)];
+
+        let mut iter = items.into_iter().try_with_position();
+
+        assert_eq!(iter.next(), Some(Ok((Position::First, 1))));
+        assert_eq!(iter.next(), Some(Ok((Position::Last, 2))));
+        assert_eq!(iter.next(), Some(Err("a")));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn it_marks_positions_when_there_are_no_errors() {
+        let items: Vec<Result<i32, &str>> = vec![Ok(1), Ok(2), Ok(3)];
+
+        let result: Vec<_> = items.into_iter().try_with_position().collect();
+
+        assert_eq!(result, vec![Ok((Position::First, 1)), Ok((Position::Middle, 2)), Ok((Position::Last, 3))]);
+    }
+}
@@ -0,0 +1,113 @@
+use std::iter::Chain;
+use std::option;
+use std::thread;
+use std::time::Duration;
+
+use {Position, PositionIterator, WithPosition};
+
+/// Return a positioned schedule of attempts. The first attempt is made right
+/// away, and each of the given delays is waited before another attempt, so
+/// there is one more attempt than there are delays. An attempt labelled as the
+/// last position is the final one.
+///
+/// ```
+/// use std::time::Duration;
+/// use with_position::{retry_schedule, Position};
+///
+/// let delays = vec![Duration::from_millis(10), Duration::from_millis(20)];
+/// let schedule: Vec<_> = retry_schedule(delays).collect();
+///
+/// assert_eq!(schedule, vec![
+///     (Position::First, Duration::from_millis(0)),
+///     (Position::Middle, Duration::from_millis(10)),
+///     (Position::Last, Duration::from_millis(20)),
+/// ]);
+/// ```
+pub fn retry_schedule<I>(delays: I) -> RetrySchedule<I::IntoIter> where I: IntoIterator<Item = Duration> {
+    RetrySchedule { delays: Some(Duration::from_millis(0)).into_iter().chain(delays).with_position() }
+}
+
+/// An iterator over the attempts of a retry schedule, yielding a tuple of
+/// `(Position, delay)` where the delay is to be waited before the attempt,
+/// created by `retry_schedule`.
+pub struct RetrySchedule<I> where I: Iterator<Item = Duration> {
+    delays: PositionIterator<Chain<option::IntoIter<Duration>, I>>,
+}
+
+impl<I> Iterator for RetrySchedule<I> where I: Iterator<Item = Duration> {
+    type Item = (Position, Duration);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.delays.next()
+    }
+}
+
+/// Call the closure for each attempt of the schedule built from the delays by
+/// `retry_schedule`, sleeping for the delay before each attempt, until it
+/// succeeds or the final attempt has failed. The closure is given the position
+/// of the attempt, so that the final attempt can for example be logged
+/// differently. The error of the final attempt is returned if all of them have
+/// failed.
+pub fn run_with_retries<D, F, T, E>(delays: D, mut f: F) -> Result<T, E>
+    where D: IntoIterator<Item = Duration>, F: FnMut(Position) -> Result<T, E>
+{
+    let mut result = None;
+    for (position, delay) in retry_schedule(delays) {
+        if delay > Duration::from_millis(0) {
+            thread::sleep(delay);
+        }
+        match f(position) {
+            Ok(value) => return Ok(value),
+            Err(error) => result = Some(Err(error)),
+        }
+    }
+    result.expect("a retry schedule always has at least one attempt")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use {retry_schedule, run_with_retries, Position};
+
+    #[test]
+    fn it_returns_the_first_success() {
+        let mut attempts = Vec::new();
+
+        let result: Result<_, ()> = run_with_retries(vec![Duration::from_millis(1); 3], |position| {
+            attempts.push(position);
+            if attempts.len() == 2 { Ok("done") } else { Err(()) }
+        });
+
+        assert_eq!(result, Ok("done"));
+        assert_eq!(attempts, vec![Position::First, Position::Middle]);
+    }
+
+    #[test]
+    fn it_returns_the_error_of_the_final_attempt() {
+        let mut attempts = Vec::new();
+
+        let result: Result<(), _> = run_with_retries(vec![Duration::from_millis(1)], |position| {
+            attempts.push(position);
+            Err(attempts.len())
+        });
+
+        assert_eq!(result, Err(2));
+        assert_eq!(attempts, vec![Position::First, Position::Last]);
+        assert_eq!(retry_schedule(None).collect::<Vec<_>>(), vec![(Position::Only, Duration::from_millis(0))]);
+    }
+
+    #[test]
+    fn it_makes_a_single_attempt_for_exhausted_delays() {
+        let mut delays = vec![Duration::from_millis(1)].into_iter();
+        delays.next();
+        let mut attempts = Vec::new();
+
+        let result: Result<(), _> = run_with_retries(delays, |position| {
+            attempts.push(position);
+            Err("failed")
+        });
+
+        assert_eq!(result, Err("failed"));
+        assert_eq!(attempts, vec![Position::Only]);
+    }
+}
@@ -0,0 +1,64 @@
+use std::thread;
+
+use Position;
+
+/// Map the items on up to `threads` scoped threads, calling `f` with every
+/// item and its position within the whole slice. The slice is split into one
+/// contiguous chunk per thread, and only the true first and last items of the
+/// slice are labelled `First` and `Last`, not the edges of the chunks. The
+/// results are returned in the order of the items.
+///
+/// With the `rayon` feature, `ParallelWithPosition` does the same for any
+/// indexed parallel iterator.
+///
+/// ```
+/// use with_position::{map_chunks_with_position, Position};
+///
+/// let items: Vec<u32> = (1..=6).collect();
+/// let result = map_chunks_with_position(&items, 3, |position, &n| (position, n * 10));
+///
+/// assert_eq!(result[0], (Position::First, 10));
+/// assert_eq!(result[2], (Position::Middle, 30));
+/// assert_eq!(result[5], (Position::Last, 60));
+/// ```
+pub fn map_chunks_with_position<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R> where T: Sync, R: Send, F: Fn(Position, &T) -> R + Sync {
+    let len = items.len();
+    if len == 0 {
+        return Vec::new();
+    }
+    let chunk_size = len.div_ceil(threads.max(1));
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = items.chunks(chunk_size).enumerate().map(|(chunk, items)| {
+            let offset = chunk * chunk_size;
+            scope.spawn(move || {
+                items.iter().enumerate().map(|(index, item)| f(Position::from_index(offset + index, len), item)).collect::<Vec<_>>()
+            })
+        }).collect();
+        handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {map_chunks_with_position, Position};
+
+    #[test]
+    fn it_labels_only_the_global_edges() {
+        let items: Vec<_> = (0..10).collect();
+
+        let result = map_chunks_with_position(&items, 4, |position, &n| (position, n));
+
+        assert_eq!(result.len(), 10);
+        assert_eq!(result[0], (Position::First, 0));
+        assert_eq!(result[9], (Position::Last, 9));
+        assert!(result[1..9].iter().enumerate().all(|(index, &(position, n))| position == Position::Middle && n == index + 1));
+    }
+
+    #[test]
+    fn it_handles_a_single_item_and_more_threads_than_items() {
+        assert_eq!(map_chunks_with_position(&["a"], 8, |position, &s| (position, s)), vec![(Position::Only, "a")]);
+        assert_eq!(map_chunks_with_position(&[1, 2], 0, |position, _| position), vec![Position::First, Position::Last]);
+        assert!(map_chunks_with_position(&Vec::<u8>::new(), 2, |position, _| position).is_empty());
+    }
+}
@@ -0,0 +1,57 @@
+use {Position, PositionIterator, WithPosition};
+
+/// Extension trait for iterators over the points of a path which adds the
+/// `path_with_position` method.
+pub trait PathWithPosition: Iterator + Sized where Self::Item: Clone {
+    /// Yield a tuple of `(Position, (from, to))` for each segment between two
+    /// consecutive points, where the first and last segments are the ones
+    /// which start and end at the endpoints of the path. A path with fewer than
+    /// two points has no segments.
+    fn path_with_position(self) -> PathSegments<Self> {
+        PathSegments { points: self.with_position(), previous: None }
+    }
+}
+
+impl<I> PathWithPosition for I where I: Iterator, I::Item: Clone {}
+
+/// An iterator adapter that yields the segments between consecutive points,
+/// created by `PathWithPosition::path_with_position`.
+pub struct PathSegments<I> where I: Iterator {
+    points: PositionIterator<I>,
+    previous: Option<(Position, I::Item)>,
+}
+
+impl<I> Iterator for PathSegments<I> where I: Iterator, I::Item: Clone {
+    type Item = (Position, (I::Item, I::Item));
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.previous.is_none() {
+            self.previous = self.points.next();
+        }
+        let (to_position, to) = self.points.next()?;
+        let (from_position, from) = self.previous.replace((to_position, to.clone()))?;
+        Some((Position::from_flags(from_position.is_first(), to_position.is_last()), (from, to)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {PathWithPosition, Position};
+
+    #[test]
+    fn it_marks_the_segments_at_the_endpoints_of_a_path() {
+        let segments: Vec<_> = vec![(0, 0), (1, 0), (1, 1), (2, 1)].into_iter().path_with_position().collect();
+
+        assert_eq!(segments, vec![
+            (Position::First, ((0, 0), (1, 0))),
+            (Position::Middle, ((1, 0), (1, 1))),
+            (Position::Last, ((1, 1), (2, 1))),
+        ]);
+    }
+
+    #[test]
+    fn it_yields_a_single_segment_as_only() {
+        assert_eq!(vec![1, 2].into_iter().path_with_position().collect::<Vec<_>>(), vec![(Position::Only, (1, 2))]);
+        assert_eq!(vec![1].into_iter().path_with_position().next(), None);
+    }
+}
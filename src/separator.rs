@@ -0,0 +1,74 @@
+use std::fmt;
+
+use Position;
+
+/// A separator written between items by joining helpers such as
+/// `WithPosition::positioned_join`. The separator is given the position of
+/// the item which follows it, so a closure can vary it by position, for
+/// example writing " and " only before the last item.
+///
+/// ```
+/// use with_position::{Position, WithPosition};
+///
+/// let separator = |position: Position| if position.is_last() { " and " } else { ", " };
+///
+/// assert_eq!(vec!["a", "b", "c"].into_iter().positioned_join(separator).to_string(), "a, b and c");
+/// assert_eq!(vec![1, 2].into_iter().positioned_join('/').to_string(), "1/2");
+/// ```
+pub trait Separator {
+    /// Write the separator before the item at `position`.
+    fn write_separator(&self, position: Position, out: &mut dyn fmt::Write) -> fmt::Result;
+}
+
+/// Displays a separator for the item at a position, so it can be written with
+/// `write!` to both `fmt::Write` and `io::Write`.
+pub(crate) struct DisplaySeparator<'a, S: 'a>(pub(crate) &'a S, pub(crate) Position);
+
+impl<'a, S> fmt::Display for DisplaySeparator<'a, S> where S: Separator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.write_separator(self.1, f)
+    }
+}
+
+impl Separator for &str {
+    fn write_separator(&self, _: Position, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str(self)
+    }
+}
+
+impl Separator for char {
+    fn write_separator(&self, _: Position, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_char(*self)
+    }
+}
+
+impl Separator for fmt::Arguments<'_> {
+    fn write_separator(&self, _: Position, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_fmt(*self)
+    }
+}
+
+impl<F, S> Separator for F where F: Fn(Position) -> S, S: fmt::Display {
+    fn write_separator(&self, position: Position, out: &mut dyn fmt::Write) -> fmt::Result {
+        write!(out, "{}", self(position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Position, Separator};
+
+    fn render<S>(separator: S, position: Position) -> String where S: Separator {
+        let mut output = String::new();
+        separator.write_separator(position, &mut output).unwrap();
+        output
+    }
+
+    #[test]
+    fn it_writes_separators() {
+        assert_eq!(render(", ", Position::Middle), ", ");
+        assert_eq!(render('|', Position::Last), "|");
+        assert_eq!(render(format_args!("{}-", 1), Position::Last), "1-");
+        assert_eq!(render(|position: Position| if position.is_last() { " or " } else { ", " }, Position::Last), " or ");
+    }
+}
@@ -0,0 +1,128 @@
+use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
+
+use {Position, Positioned, WithPosition};
+
+/// Positions are serialized as their name returned by `Position::as_str`, so as
+/// one of the strings `"first"`, `"middle"`, `"last"` or `"only"` in JSON.
+impl Serialize for Position {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let index = match *self {
+            Position::First => 0,
+            Position::Middle => 1,
+            Position::Last => 2,
+            Position::Only => 3,
+        };
+        serializer.serialize_unit_variant("Position", index, self.as_str())
+    }
+}
+
+/// Serialized as a struct with a `position` and a `value` field.
+impl<T> Serialize for Positioned<T> where T: Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let mut state = serializer.serialize_struct("Positioned", 2)?;
+        state.serialize_field("position", &self.position)?;
+        state.serialize_field("value", &self.value)?;
+        state.end()
+    }
+}
+
+/// A wrapper around an iterator which implements `Serialize` by serializing a
+/// sequence of `{"position": ..., "value": ...}` objects, as `Positioned` is
+/// serialized, without collecting the items first. Each time it is serialized
+/// the items are taken from a clone of the iterator, so it can be serialized
+/// more than once.
+///
+/// ```
+/// # extern crate serde_json;
+/// # extern crate with_position;
+/// use with_position::PositionedSeq;
+///
+/// # fn main() {
+/// let json = serde_json::to_string(&PositionedSeq::new(vec![1, 2])).unwrap();
+///
+/// assert_eq!(json, r#"[{"position":"first","value":1},{"position":"last","value":2}]"#);
+/// # }
+/// ```
+pub struct PositionedSeq<I> {
+    iter: I,
+}
+
+impl<I> PositionedSeq<I> where I: Iterator {
+    pub fn new<T>(iter: T) -> PositionedSeq<I> where T: IntoIterator<IntoIter = I, Item = I::Item> {
+        PositionedSeq { iter: iter.into_iter() }
+    }
+
+    /// Serialize the items as a plain sequence instead, calling the hook with
+    /// the position of each item before it is serialized.
+    pub fn with_hook<F>(self, hook: F) -> HookedSeq<I, F> where F: Fn(Position, &I::Item) {
+        HookedSeq { iter: self.iter, hook }
+    }
+}
+
+impl<I> Serialize for PositionedSeq<I> where I: Iterator + Clone, I::Item: Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let iter = self.iter.clone();
+        let mut seq = serializer.serialize_seq(size_hint(&iter))?;
+        for (position, value) in iter.with_position() {
+            seq.serialize_element(&Positioned { position, value })?;
+        }
+        seq.end()
+    }
+}
+
+/// A wrapper around an iterator which serializes its items as a sequence while
+/// calling a hook with their positions, created by `PositionedSeq::with_hook`.
+pub struct HookedSeq<I, F> {
+    iter: I,
+    hook: F,
+}
+
+impl<I, F> Serialize for HookedSeq<I, F> where I: Iterator + Clone, I::Item: Serialize, F: Fn(Position, &I::Item) {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let iter = self.iter.clone();
+        let mut seq = serializer.serialize_seq(size_hint(&iter))?;
+        for (position, value) in iter.with_position() {
+            (self.hook)(position, &value);
+            seq.serialize_element(&value)?;
+        }
+        seq.end()
+    }
+}
+
+fn size_hint<I>(iter: &I) -> Option<usize> where I: Iterator {
+    match iter.size_hint() {
+        (lower, Some(upper)) if lower == upper => Some(lower),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use serde_json;
+    use {Position, PositionedSeq, WithPosition};
+
+    #[test]
+    fn it_serializes_positioned_items() {
+        let items = vec!["a"].into_iter().collect_positioned();
+
+        assert_eq!(serde_json::to_string(&items).unwrap(), r#"[{"position":"only","value":"a"}]"#);
+    }
+
+    #[test]
+    fn it_serializes_a_sequence_more_than_once() {
+        let seq = PositionedSeq::new(vec![1]);
+
+        assert_eq!(serde_json::to_string(&seq).unwrap(), r#"[{"position":"only","value":1}]"#);
+        assert_eq!(serde_json::to_string(&seq).unwrap(), r#"[{"position":"only","value":1}]"#);
+    }
+
+    #[test]
+    fn it_calls_the_hook_with_the_position_of_each_item() {
+        let positions = RefCell::new(Vec::new());
+        let seq = PositionedSeq::new(vec![1, 2, 3]).with_hook(|position, _| positions.borrow_mut().push(position));
+
+        assert_eq!(serde_json::to_string(&seq).unwrap(), "[1,2,3]");
+        assert_eq!(*positions.borrow(), vec![Position::First, Position::Middle, Position::Last]);
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::VecDeque;
+use std::iter::{Fuse, FusedIterator};
+
+/// An iterator adapter that yields all but the last `n` items of the source,
+/// created by `WithPosition::skip_last`. The last `n` items read are kept in a
+/// ring buffer, and an item is only yielded once `n` more items have been read
+/// after it.
+pub struct SkipLast<I> where I: Iterator {
+    iter: Fuse<I>,
+    buffer: VecDeque<I::Item>,
+    n: usize,
+}
+
+impl<I> SkipLast<I> where I: Iterator {
+    pub(crate) fn new(iter: I, n: usize) -> SkipLast<I> {
+        SkipLast { iter: iter.fuse(), buffer: VecDeque::new(), n }
+    }
+}
+
+impl<I> Iterator for SkipLast<I> where I: Iterator {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        while self.buffer.len() < self.n.saturating_add(1) {
+            self.buffer.push_back(self.iter.next()?);
+        }
+        self.buffer.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let buffered = self.buffer.len();
+        let remaining = |len: usize| len.saturating_add(buffered).saturating_sub(self.n);
+        (remaining(lower), upper.map(remaining))
+    }
+}
+
+impl<I> FusedIterator for SkipLast<I> where I: Iterator {}
+
+#[cfg(test)]
+mod tests {
+    use {Position, WithPosition};
+
+    #[test]
+    fn it_skips_the_last_items() {
+        let result: Vec<_> = (1..6).skip_last(2).with_position().collect();
+
+        assert_eq!(result, vec![(Position::First, 1), (Position::Middle, 2), (Position::Last, 3)]);
+        assert_eq!((1..6).skip_last(2).size_hint(), (3, Some(3)));
+    }
+
+    #[test]
+    fn it_yields_nothing_when_skipping_more_than_there_are() {
+        assert_eq!((1..3).skip_last(5).next(), None);
+        assert_eq!((1..3).skip_last(0).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!((1..3).skip_last(usize::MAX).next(), None);
+    }
+}
@@ -0,0 +1,71 @@
+use std::slice;
+
+use ExactPositionIterator;
+
+/// Extension trait for slices which adds positioned iteration methods.
+pub trait SliceWithPosition<T> {
+    /// Iterate over references to the items of the slice, yielding a tuple of
+    /// `(Position, &item)`. Positions are computed from the index of each
+    /// item, so this also works from the back.
+    fn iter_with_position(&self) -> ExactPositionIterator<slice::Iter<'_, T>>;
+
+    /// Iterate over mutable references to the items of the slice, yielding a
+    /// tuple of `(Position, &mut item)`.
+    fn iter_mut_with_position(&mut self) -> ExactPositionIterator<slice::IterMut<'_, T>>;
+}
+
+impl<T> SliceWithPosition<T> for [T] {
+    fn iter_with_position(&self) -> ExactPositionIterator<slice::Iter<'_, T>> {
+        ExactPositionIterator::new(self.iter())
+    }
+
+    fn iter_mut_with_position(&mut self) -> ExactPositionIterator<slice::IterMut<'_, T>> {
+        ExactPositionIterator::new(self.iter_mut())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Position, SliceWithPosition};
+
+    #[test]
+    fn it_marks_positions_in_a_slice() {
+        let result: Vec<_> = [1, 2, 3].iter_with_position().collect();
+
+        assert_eq!(result, vec![(Position::First, &1), (Position::Middle, &2), (Position::Last, &3)]);
+    }
+
+    #[test]
+    fn it_marks_positions_from_both_ends() {
+        let slice = [1, 2, 3, 4];
+        let mut iter = slice.iter_with_position();
+
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next_back(), Some((Position::Last, &4)));
+        assert_eq!(iter.next(), Some((Position::First, &1)));
+        assert_eq!(iter.next_back(), Some((Position::Middle, &3)));
+        assert_eq!(iter.next(), Some((Position::Middle, &2)));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn it_marks_only_position_in_a_slice() {
+        let result: Vec<_> = [1].iter_with_position().collect();
+
+        assert_eq!(result, vec![(Position::Only, &1)]);
+    }
+
+    #[test]
+    fn it_mutates_items_by_position() {
+        let mut words = vec![String::from("a "), String::from("b "), String::from("c ")];
+
+        for (position, word) in words.iter_mut_with_position() {
+            if position.is_last() {
+                let trimmed = word.trim_end().len();
+                word.truncate(trimmed);
+            }
+        }
+
+        assert_eq!(words, vec!["a ", "b ", "c"]);
+    }
+}
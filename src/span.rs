@@ -0,0 +1,141 @@
+use std::iter::FusedIterator;
+use std::ops::Range;
+
+use {Position, PositionIterator, WithPosition};
+
+/// A location in source text, as a byte offset together with a line and a
+/// column, both counting from one. Columns count characters, not bytes.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Location {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    const START: Location = Location { offset: 0, line: 1, column: 1 };
+}
+
+/// The part of the source text covered by a token, from the location of its
+/// first byte to the location just after its last byte.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Span {
+    pub start: Location,
+    pub end: Location,
+}
+
+impl Span {
+    /// Return the range of bytes covered by the span.
+    pub fn bytes(&self) -> Range<usize> {
+        self.start.offset..self.end.offset
+    }
+}
+
+/// Label tokens, given together with the range of bytes they cover in the
+/// source text, with their position and their span, yielding a tuple of
+/// `(Position, Span, token)`. The tokens are expected in the order of the
+/// source, so that lines and columns are counted in a single pass. The span
+/// of the end of the input, which parsers report errors at after the last
+/// token, is available from `SpannedTokens::end_of_input`.
+///
+/// Iterating panics if a token range goes past the end of the source text, or
+/// if it does not start and end on character boundaries.
+///
+/// ```
+/// use with_position::{spans_with_position, Position};
+///
+/// let source = "let x\n= 1";
+/// let tokens = vec![(0..3, "let"), (4..5, "x"), (6..7, "="), (8..9, "1")];
+/// let mut spanned = spans_with_position(source, tokens);
+///
+/// let (position, span, token) = spanned.nth(3).unwrap();
+/// assert_eq!((position, token), (Position::Last, "1"));
+/// assert_eq!((span.start.line, span.start.column), (2, 3));
+/// assert_eq!(span.end, spanned.end_of_input().start);
+/// ```
+pub fn spans_with_position<'a, I, T>(source: &'a str, tokens: I) -> SpannedTokens<'a, I::IntoIter> where I: IntoIterator<Item = (Range<usize>, T)> {
+    SpannedTokens { source, iter: tokens.into_iter().with_position(), cursor: Location::START }
+}
+
+/// An iterator adapter that yields tokens with their position and span,
+/// created by `spans_with_position`.
+pub struct SpannedTokens<'a, I> where I: Iterator {
+    source: &'a str,
+    iter: PositionIterator<I>,
+    cursor: Location,
+}
+
+impl<'a, I> SpannedTokens<'a, I> where I: Iterator {
+    /// Return the empty span at the end of the source text.
+    pub fn end_of_input(&mut self) -> Span {
+        let end = self.locate(self.source.len());
+        Span { start: end, end }
+    }
+
+    /// Return the location of the byte offset, moving the cursor forward from
+    /// the previous location, or starting over if the offset is before it.
+    fn locate(&mut self, offset: usize) -> Location {
+        assert!(self.source.is_char_boundary(offset), "token offset {} is out of bounds or not on a character boundary", offset);
+        if offset < self.cursor.offset {
+            self.cursor = Location::START;
+        }
+        let Location { mut line, mut column, .. } = self.cursor;
+        for c in self.source[self.cursor.offset..offset].chars() {
+            if c == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+        self.cursor = Location { offset, line, column };
+        self.cursor
+    }
+}
+
+impl<'a, I, T> Iterator for SpannedTokens<'a, I> where I: Iterator<Item = (Range<usize>, T)> {
+    type Item = (Position, Span, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (position, (range, token)) = self.iter.next()?;
+        let start = self.locate(range.start);
+        let end = self.locate(range.end);
+        Some((position, Span { start, end }, token))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, I, T> FusedIterator for SpannedTokens<'a, I> where I: Iterator<Item = (Range<usize>, T)> {}
+
+#[cfg(test)]
+mod tests {
+    use {spans_with_position, Location, Position};
+
+    #[test]
+    fn it_counts_columns_in_characters_across_lines() {
+        let source = "é ü\nö";
+        let result: Vec<_> = spans_with_position(source, vec![(0..2, 'é'), (3..5, 'ü'), (6..8, 'ö')])
+            .map(|(position, span, token)| (position, span.start.line, span.start.column, token))
+            .collect();
+
+        assert_eq!(result, vec![(Position::First, 1, 1, 'é'), (Position::Middle, 1, 3, 'ü'), (Position::Last, 2, 1, 'ö')]);
+    }
+
+    #[test]
+    fn it_has_an_end_of_input_span_without_tokens() {
+        let mut spanned = spans_with_position("a\n", Vec::<(_, ())>::new());
+
+        assert_eq!(spanned.next(), None);
+        assert_eq!(spanned.end_of_input().start, Location { offset: 2, line: 2, column: 1 });
+        assert_eq!(spanned.end_of_input().bytes(), 2..2);
+    }
+
+    #[test]
+    #[should_panic(expected = "not on a character boundary")]
+    fn it_panics_on_a_token_inside_a_character() {
+        spans_with_position("é", vec![(1..2, ())]).next();
+    }
+}
@@ -0,0 +1,88 @@
+use std::iter::{Fuse, FusedIterator};
+
+/// An iterator over all but the last item of the source, which captures the
+/// last item instead of yielding it, created by `WithPosition::split_last_iter`.
+///
+/// The position of the last item is only known once the body has been
+/// exhausted, so the last item is retrieved afterwards with `into_last`. Only
+/// one item is looked ahead, so nothing is collected.
+///
+/// ```
+/// use with_position::WithPosition;
+///
+/// let mut frames = vec!["a", "b", "c"].into_iter().split_last_iter();
+/// let mut output = String::new();
+/// for frame in &mut frames {
+///     output.push_str(frame);
+///     output.push(',');
+/// }
+/// output.push_str(&frames.into_last().unwrap().to_uppercase());
+///
+/// assert_eq!(output, "a,b,C");
+/// ```
+pub struct SplitLast<I> where I: Iterator {
+    iter: Fuse<I>,
+    next: Option<I::Item>,
+    started: bool,
+}
+
+impl<I> SplitLast<I> where I: Iterator {
+    pub(crate) fn new(iter: I) -> SplitLast<I> {
+        SplitLast { iter: iter.fuse(), next: None, started: false }
+    }
+
+    /// Return the last item of the source, skipping the remaining items of the
+    /// body if it has not been exhausted yet.
+    pub fn into_last(self) -> Option<I::Item> {
+        let next = self.next;
+        self.iter.last().or(next)
+    }
+}
+
+impl<I> Iterator for SplitLast<I> where I: Iterator {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if !self.started {
+            self.started = true;
+            self.next = self.iter.next();
+        }
+        let item = self.next.take()?;
+        match self.iter.next() {
+            Some(next) => {
+                self.next = Some(next);
+                Some(item)
+            }
+            None => {
+                // Keep the last item until `into_last` is called.
+                self.next = Some(item);
+                None
+            }
+        }
+    }
+}
+
+impl<I> FusedIterator for SplitLast<I> where I: Iterator {}
+
+#[cfg(test)]
+mod tests {
+    use WithPosition;
+
+    #[test]
+    fn it_splits_off_the_last_item() {
+        let mut iter = (1..4).split_last_iter();
+
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(iter.into_last(), Some(3));
+    }
+
+    #[test]
+    fn it_returns_the_last_item_without_exhausting_the_body() {
+        assert_eq!((1..4).split_last_iter().into_last(), Some(3));
+
+        let mut single = Some(1).into_iter().split_last_iter();
+        assert_eq!(single.next(), None);
+        assert_eq!(single.next(), None);
+        assert_eq!(single.into_last(), Some(1));
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt::Write;
+use std::iter::Peekable;
+
+use {Position, WithPosition};
+
+/// The style of bind parameter placeholders used by a database driver.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PlaceholderStyle {
+    /// `?, ?, ?`, as used by SQLite and MySQL.
+    Question,
+    /// `$1, $2, $3`, as used by PostgreSQL.
+    Dollar,
+}
+
+/// Builds lists of bind parameter placeholders from positioned bind values,
+/// with commas between them and not after the last one.
+///
+/// ```
+/// use with_position::{Placeholders, PlaceholderStyle, WithPosition};
+///
+/// let ids = vec![3, 5, 8];
+/// let placeholders = Placeholders::new(PlaceholderStyle::Dollar).starting_at(2);
+///
+/// assert_eq!(placeholders.in_clause("id", ids.iter().with_position()), "id IN ($2, $3, $4)");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Placeholders {
+    style: PlaceholderStyle,
+    start: usize,
+}
+
+impl Placeholders {
+    pub fn new(style: PlaceholderStyle) -> Placeholders {
+        Placeholders { style, start: 1 }
+    }
+
+    /// Set the number of the first placeholder, for when other parameters come
+    /// before the list in the query. Ignored by the `Question` style.
+    pub fn starting_at(mut self, start: usize) -> Placeholders {
+        self.start = start;
+        self
+    }
+
+    /// Return a placeholder for each value, such as `?, ?, ?`, or an empty
+    /// string if there are no values.
+    pub fn list<I, T>(&self, values: I) -> String where I: IntoIterator<Item = (Position, T)> {
+        let mut output = String::new();
+        for (index, (position, _)) in values.into_iter().enumerate() {
+            match self.style {
+                PlaceholderStyle::Question => output.push('?'),
+                PlaceholderStyle::Dollar => write!(output, "${}", self.start + index).unwrap(),
+            }
+            if !position.is_last() {
+                output.push_str(", ");
+            }
+        }
+        output
+    }
+
+    /// Return an `IN` condition for the column, such as `id IN (?, ?)`. Since
+    /// `IN ()` is not valid SQL, a condition which is always false is returned
+    /// if there are no values.
+    pub fn in_clause<I, T>(&self, column: &str, values: I) -> String where I: IntoIterator<Item = (Position, T)> {
+        let list = self.list(values);
+        if list.is_empty() {
+            String::from("1 = 0")
+        } else {
+            format!("{} IN ({})", column, list)
+        }
+    }
+}
+
+/// Split rows into batches of `batch_size` rows for multi-row inserts,
+/// yielding a tuple of `(Position, InsertBatch)`. The position of the batch can
+/// be used to commit the transaction after the last batch, and the rows of
+/// each batch carry their own position within the batch.
+///
+/// Panics if `batch_size` is zero.
+///
+/// ```
+/// use with_position::{insert_batches, PlaceholderStyle};
+///
+/// let rows = vec![("a", 1), ("b", 2), ("c", 3)];
+/// let statements: Vec<_> = insert_batches(rows, 2).map(|(position, batch)| {
+///     (position.is_last(), batch.insert_statement("items", &["name", "count"], PlaceholderStyle::Question))
+/// }).collect();
+///
+/// assert_eq!(statements, vec![
+///     (false, String::from("INSERT INTO items (name, count) VALUES (?, ?), (?, ?)")),
+///     (true, String::from("INSERT INTO items (name, count) VALUES (?, ?)")),
+/// ]);
+/// ```
+pub fn insert_batches<I>(rows: I, batch_size: usize) -> InsertBatches<I::IntoIter> where I: IntoIterator {
+    assert!(batch_size > 0, "batch size must be greater than zero");
+    InsertBatches { iter: rows.into_iter().peekable(), batch_size, did_iter: false }
+}
+
+/// An iterator adapter that yields batches of rows with their position, created
+/// by `insert_batches`.
+pub struct InsertBatches<I> where I: Iterator {
+    iter: Peekable<I>,
+    batch_size: usize,
+    did_iter: bool,
+}
+
+impl<I> Iterator for InsertBatches<I> where I: Iterator {
+    type Item = (Position, InsertBatch<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.peek()?;
+        let mut rows = Vec::with_capacity(self.batch_size);
+        while rows.len() < self.batch_size {
+            match self.iter.next() {
+                Some(row) => rows.push(row),
+                None => break,
+            }
+        }
+        let is_first = !self.did_iter;
+        let is_last = self.iter.peek().is_none();
+        self.did_iter = true;
+
+        let len = rows.len();
+        let rows = rows.into_iter().enumerate().map(|(index, row)| (Position::from_index(index, len), row)).collect();
+        Some((Position::from_flags(is_first, is_last), InsertBatch { rows }))
+    }
+}
+
+/// A batch of rows yielded by `insert_batches`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InsertBatch<T> {
+    /// The rows of the batch together with their position within the batch.
+    pub rows: Vec<(Position, T)>,
+}
+
+impl<T> InsertBatch<T> {
+    /// Return the `VALUES` list for the batch, with a group of placeholders for
+    /// each row, such as `(?, ?), (?, ?)`. Dollar placeholders are numbered
+    /// from one across the whole batch.
+    pub fn values(&self, style: PlaceholderStyle, columns: usize) -> String {
+        let mut output = String::new();
+        for (index, &(position, _)) in self.rows.iter().enumerate() {
+            let placeholders = Placeholders::new(style).starting_at(1 + index * columns);
+            write!(output, "({})", placeholders.list((0..columns).with_position())).unwrap();
+            if !position.is_last() {
+                output.push_str(", ");
+            }
+        }
+        output
+    }
+
+    /// Return an `INSERT` statement for the batch into the given table and
+    /// columns.
+    pub fn insert_statement(&self, table: &str, columns: &[&str], style: PlaceholderStyle) -> String {
+        format!("INSERT INTO {} ({}) VALUES {}", table, columns.join(", "), self.values(style, columns.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+    use {insert_batches, PlaceholderStyle, Placeholders, Position, WithPosition};
+
+    #[test]
+    fn it_builds_question_mark_placeholders() {
+        let placeholders = Placeholders::new(PlaceholderStyle::Question);
+
+        assert_eq!(placeholders.list(vec!["a", "b", "c"].into_iter().with_position()), "?, ?, ?");
+        assert_eq!(placeholders.list(iter::once(1).with_position()), "?");
+    }
+
+    #[test]
+    fn it_builds_a_false_condition_for_an_empty_in_clause() {
+        let placeholders = Placeholders::new(PlaceholderStyle::Dollar);
+
+        assert_eq!(placeholders.in_clause("id", iter::empty::<i32>().with_position()), "1 = 0");
+    }
+
+    #[test]
+    fn it_splits_rows_into_positioned_batches() {
+        let batches: Vec<_> = insert_batches(1..6, 2).collect();
+
+        assert_eq!(batches.iter().map(|&(position, _)| position).collect::<Vec<_>>(), vec![Position::First, Position::Middle, Position::Last]);
+        assert_eq!(batches[0].1.rows, vec![(Position::First, 1), (Position::Last, 2)]);
+        assert_eq!(batches[2].1.rows, vec![(Position::Only, 5)]);
+        assert_eq!(batches[0].1.values(PlaceholderStyle::Dollar, 3), "($1, $2, $3), ($4, $5, $6)");
+    }
+}
@@ -0,0 +1,86 @@
+use rusqlite::{MappedRows, Params, Result, Row, Rows, Statement};
+
+use {PositionIterator, WithPosition};
+
+/// Extension trait for rusqlite statements which runs a query and yields the
+/// mapped rows with their position, so that a report can emit a header before
+/// the first row and a footer after the last, or notice that there are no
+/// rows at all. Only one row is read ahead to find the last row.
+///
+/// ```
+/// extern crate rusqlite;
+/// # extern crate with_position;
+/// use rusqlite::Connection;
+/// use with_position::{Position, StatementWithPosition};
+///
+/// # fn main() {
+/// let connection = Connection::open_in_memory().unwrap();
+/// let mut statement = connection.prepare("SELECT 1 UNION ALL SELECT 2").unwrap();
+///
+/// let rows: Vec<_> = statement.query_map_with_position([], |row| row.get::<_, i64>(0)).unwrap()
+///     .map(|(position, value)| (position, value.unwrap()))
+///     .collect();
+///
+/// assert_eq!(rows, vec![(Position::First, 1), (Position::Last, 2)]);
+/// # }
+/// ```
+pub trait StatementWithPosition {
+    /// Run the query with the given parameters, yielding a tuple of
+    /// `(Position, Result<T>)` for every row mapped by `f`.
+    fn query_map_with_position<T, P, F>(&mut self, params: P, f: F) -> Result<PositionIterator<MappedRows<'_, F>>> where P: Params, F: FnMut(&Row<'_>) -> Result<T>;
+}
+
+impl<'conn> StatementWithPosition for Statement<'conn> {
+    fn query_map_with_position<T, P, F>(&mut self, params: P, f: F) -> Result<PositionIterator<MappedRows<'_, F>>> where P: Params, F: FnMut(&Row<'_>) -> Result<T> {
+        Ok(self.query_map(params, f)?.with_position())
+    }
+}
+
+/// Extension trait for the `Rows` of a query which have already been
+/// fetched. A `Row` borrows the cursor, so the rows are mapped by `f` before
+/// the next one is read.
+pub trait SqliteRowsWithPosition<'stmt> {
+    /// Map every row by `f`, yielding a tuple of `(Position, Result<T>)`.
+    fn mapped_with_position<T, F>(self, f: F) -> PositionIterator<MappedRows<'stmt, F>> where F: FnMut(&Row<'_>) -> Result<T>;
+}
+
+impl<'stmt> SqliteRowsWithPosition<'stmt> for Rows<'stmt> {
+    fn mapped_with_position<T, F>(self, f: F) -> PositionIterator<MappedRows<'stmt, F>> where F: FnMut(&Row<'_>) -> Result<T> {
+        self.mapped(f).with_position()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+    use {Position, SqliteRowsWithPosition, StatementWithPosition};
+
+    fn connection() -> Connection {
+        let connection = Connection::open_in_memory().unwrap();
+        connection.execute_batch("CREATE TABLE names (name TEXT); INSERT INTO names VALUES ('a'), ('b'), ('c');").unwrap();
+        connection
+    }
+
+    #[test]
+    fn it_marks_positions_of_fetched_rows() {
+        let connection = connection();
+        let mut statement = connection.prepare("SELECT name FROM names ORDER BY name").unwrap();
+
+        let names: Vec<_> = statement.query([]).unwrap().mapped_with_position(|row| row.get::<_, String>(0)).map(|(position, name)| (position, name.unwrap())).collect();
+
+        assert_eq!(names, vec![
+            (Position::First, String::from("a")),
+            (Position::Middle, String::from("b")),
+            (Position::Last, String::from("c")),
+        ]);
+    }
+
+    #[test]
+    fn it_yields_nothing_for_an_empty_result_set() {
+        let connection = connection();
+        let mut statement = connection.prepare("SELECT name FROM names WHERE name = ?1").unwrap();
+
+        assert!(statement.query_map_with_position(["z"], |row| row.get::<_, String>(0)).unwrap().next().is_none());
+        assert_eq!(statement.query_map_with_position(["b"], |row| row.get::<_, String>(0)).unwrap().next().map(|(position, _)| position), Some(Position::Only));
+    }
+}
@@ -0,0 +1,151 @@
+use std::iter::{FromIterator, FusedIterator};
+
+use Position;
+
+/// Counts of the positions in one or more iterations, for checking invariants
+/// such as exactly one first and one last item per batch.
+///
+/// ```
+/// use with_position::{PositionStats, PositionStatsWithPosition, WithPosition};
+///
+/// let stats = vec!["a", "b", "c"].into_iter().with_position().position_stats();
+///
+/// assert_eq!(stats.total, 3);
+/// assert_eq!((stats.first, stats.middle, stats.last), (1, 1, 1));
+/// assert!(stats.is_well_formed());
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct PositionStats {
+    pub total: usize,
+    pub first: usize,
+    pub middle: usize,
+    pub last: usize,
+    pub only: usize,
+}
+
+impl PositionStats {
+    pub fn new() -> PositionStats {
+        PositionStats::default()
+    }
+
+    /// Count another position.
+    pub fn record(&mut self, position: Position) {
+        self.total += 1;
+        match position {
+            Position::First => self.first += 1,
+            Position::Middle => self.middle += 1,
+            Position::Last => self.last += 1,
+            Position::Only => self.only += 1,
+        }
+    }
+
+    /// Return the count of the position.
+    pub fn count(&self, position: Position) -> usize {
+        match position {
+            Position::First => self.first,
+            Position::Middle => self.middle,
+            Position::Last => self.last,
+            Position::Only => self.only,
+        }
+    }
+
+    /// Return true if an `Only` position was counted.
+    pub fn has_only(&self) -> bool {
+        self.only > 0
+    }
+
+    /// Return true if the positions are those of a single complete iteration,
+    /// that is either nothing at all, a single `Only`, or exactly one `First`
+    /// and one `Last` with any number of `Middle` positions in between.
+    pub fn is_well_formed(&self) -> bool {
+        match (self.first, self.last, self.only) {
+            (0, 0, 0) | (0, 0, 1) => self.middle == 0,
+            (1, 1, 0) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Extend<Position> for PositionStats {
+    fn extend<I>(&mut self, iter: I) where I: IntoIterator<Item = Position> {
+        for position in iter {
+            self.record(position);
+        }
+    }
+}
+
+impl FromIterator<Position> for PositionStats {
+    fn from_iter<I>(iter: I) -> PositionStats where I: IntoIterator<Item = Position> {
+        let mut stats = PositionStats::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+/// Extension trait for iterators over `(Position, item)` tuples which adds
+/// methods to count the positions.
+pub trait PositionStatsWithPosition<T>: Iterator<Item = (Position, T)> + Sized {
+    /// Consume the iterator and count its positions.
+    fn position_stats(self) -> PositionStats {
+        self.map(|(position, _)| position).collect()
+    }
+
+    /// Yield the tuples unchanged, while counting their positions into the
+    /// given stats, which are added to rather than reset.
+    fn collect_stats(self, stats: &mut PositionStats) -> CollectStats<'_, Self> {
+        CollectStats { iter: self, stats }
+    }
+}
+
+impl<I, T> PositionStatsWithPosition<T> for I where I: Iterator<Item = (Position, T)> {}
+
+/// An iterator adapter that counts the positions of the tuples passing
+/// through it, created by `PositionStatsWithPosition::collect_stats`.
+pub struct CollectStats<'a, I> {
+    iter: I,
+    stats: &'a mut PositionStats,
+}
+
+impl<'a, I, T> Iterator for CollectStats<'a, I> where I: Iterator<Item = (Position, T)> {
+    type Item = (Position, T);
+
+    fn next(&mut self) -> Option<(Position, T)> {
+        let (position, item) = self.iter.next()?;
+        self.stats.record(position);
+        Some((position, item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, I, T> FusedIterator for CollectStats<'a, I> where I: FusedIterator<Item = (Position, T)> {}
+
+#[cfg(test)]
+mod tests {
+    use {Position, PositionStats, PositionStatsWithPosition, WithPosition};
+
+    #[test]
+    fn it_counts_positions_across_batches() {
+        let mut stats = PositionStats::new();
+
+        for batch in [vec![1, 2, 3], vec![4]] {
+            let items: Vec<_> = batch.into_iter().with_position().collect_stats(&mut stats).map(|(_, item)| item).collect();
+            assert!(!items.is_empty());
+        }
+
+        assert_eq!(stats, PositionStats { total: 4, first: 1, middle: 1, last: 1, only: 1 });
+        assert_eq!(stats.count(Position::Only), 1);
+        assert!(stats.has_only());
+        assert!(!stats.is_well_formed());
+    }
+
+    #[test]
+    fn it_checks_whether_positions_form_a_single_iteration() {
+        assert!(PositionStats::new().is_well_formed());
+        assert!(vec![Position::Only].into_iter().collect::<PositionStats>().is_well_formed());
+        assert!(!vec![Position::First, Position::Middle].into_iter().collect::<PositionStats>().is_well_formed());
+        assert!(!vec![Position::Middle].into_iter().collect::<PositionStats>().is_well_formed());
+    }
+}
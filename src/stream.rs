@@ -0,0 +1,235 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::future::Future;
+use futures_core::stream::{FusedStream, Stream};
+use futures_sink::Sink;
+
+use Position;
+
+/// Extension trait for streams which adds the `with_position` method. This only
+/// depends on the `Stream` trait from `futures-core`, so it works the same on
+/// any executor.
+pub trait StreamWithPositionExt: Stream + Sized {
+    /// Yield a tuple of `(Position, item)` where position indicates whether this
+    /// is the first, middle or last item. One item is held back until the next
+    /// one arrives, so the last item is only yielded once the stream has ended.
+    fn with_position(self) -> PositionStream<Self> {
+        PositionStream { stream: self, pending: None, did_iter: false, done: false }
+    }
+}
+
+impl<S> StreamWithPositionExt for S where S: Stream {}
+
+pin_project! {
+    /// A stream adapter that yields tuples where the first element is a Position
+    /// and the second is the item.
+    pub struct PositionStream<S> where S: Stream {
+        #[pin]
+        stream: S,
+        pending: Option<S::Item>,
+        did_iter: bool,
+        done: bool,
+    }
+}
+
+impl<S> PositionStream<S> where S: Stream {
+    /// Return the underlying stream, for example to close a channel. Items
+    /// taken from it directly are not labelled.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+}
+
+impl<S> Stream for PositionStream<S> where S: Stream {
+    type Item = (Position, S::Item);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        if this.pending.is_none() {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => *this.pending = Some(item),
+                Poll::Ready(None) => {
+                    *this.done = true;
+                    return Poll::Ready(None);
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let is_last = match this.stream.as_mut().poll_next(cx) {
+            Poll::Ready(Some(next)) => {
+                let current = this.pending.replace(next);
+                let is_first = !*this.did_iter;
+                *this.did_iter = true;
+                return Poll::Ready(current.map(|item| (Position::from_flags(is_first, false), item)));
+            }
+            Poll::Ready(None) => true,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        *this.done = true;
+        let is_first = !*this.did_iter;
+        *this.did_iter = true;
+        Poll::Ready(this.pending.take().map(|item| (Position::from_flags(is_first, is_last), item)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        let pending = if self.pending.is_some() { 1 } else { 0 };
+        let (lower, upper) = self.stream.size_hint();
+        (lower.saturating_add(pending), upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+impl<S> FusedStream for PositionStream<S> where S: Stream {
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+/// Forward all items of a stream into a sink, after mapping each of them
+/// together with its position. Once the last item has been sent the sink is
+/// flushed and closed. The returned future resolves when this has completed, or
+/// to the first error returned by the sink.
+pub fn forward_positioned<St, Si, F, T>(stream: St, sink: Si, f: F) -> ForwardPositioned<St, Si, F, T>
+    where St: Stream, Si: Sink<T>, F: FnMut(Position, St::Item) -> T
+{
+    ForwardPositioned { stream: stream.with_position(), sink, f, buffered: None }
+}
+
+pin_project! {
+    /// A future which forwards positioned items into a sink, created by
+    /// `forward_positioned`.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct ForwardPositioned<St, Si, F, T> where St: Stream {
+        #[pin]
+        stream: PositionStream<St>,
+        #[pin]
+        sink: Si,
+        f: F,
+        buffered: Option<T>,
+    }
+}
+
+impl<St, Si, F, T> Future for ForwardPositioned<St, Si, F, T>
+    where St: Stream, Si: Sink<T>, F: FnMut(Position, St::Item) -> T
+{
+    type Output = Result<(), Si::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            if this.buffered.is_some() {
+                match this.sink.as_mut().poll_ready(cx) {
+                    Poll::Ready(Ok(())) => {}
+                    Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
+                    Poll::Pending => return Poll::Pending,
+                }
+                if let Some(item) = this.buffered.take() {
+                    if let Err(error) = this.sink.as_mut().start_send(item) {
+                        return Poll::Ready(Err(error));
+                    }
+                }
+            }
+
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some((position, item))) => *this.buffered = Some((this.f)(position, item)),
+                Poll::Ready(None) => return this.sink.as_mut().poll_close(cx),
+                Poll::Pending => {
+                    return match this.sink.as_mut().poll_flush(cx) {
+                        Poll::Ready(Err(error)) => Poll::Ready(Err(error)),
+                        _ => Poll::Pending,
+                    };
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::channel::mpsc;
+    use futures::executor::block_on;
+    use futures::future::{self, FutureExt};
+    use futures::stream::{self, StreamExt};
+    use {forward_positioned, Position, StreamWithPositionExt};
+
+    #[test]
+    fn it_marks_positions_in_a_stream() {
+        let result: Vec<_> = block_on(stream::iter(vec![1, 2, 3]).with_position().collect());
+
+        assert_eq!(result, vec![(Position::First, 1), (Position::Middle, 2), (Position::Last, 3)]);
+    }
+
+    #[test]
+    fn it_marks_only_position_in_a_stream() {
+        let result: Vec<_> = block_on(stream::once(future::ready(1)).with_position().collect());
+
+        assert_eq!(result, vec![(Position::Only, 1)]);
+    }
+
+    #[test]
+    fn it_waits_for_the_end_of_the_stream_before_yielding_last() {
+        let (sender, receiver) = mpsc::unbounded();
+        let mut positions = receiver.with_position();
+
+        sender.unbounded_send(1).unwrap();
+        assert!(positions.next().now_or_never().is_none());
+
+        sender.unbounded_send(2).unwrap();
+        assert_eq!(block_on(positions.next()), Some((Position::First, 1)));
+
+        drop(sender);
+        assert_eq!(block_on(positions.next()), Some((Position::Last, 2)));
+        assert_eq!(block_on(positions.next()), None);
+    }
+
+    #[test]
+    fn it_forwards_positioned_items_into_a_sink_and_closes_it() {
+        let (sender, receiver) = mpsc::unbounded();
+        let frames = stream::iter(vec!["a", "b", "c"]);
+
+        block_on(forward_positioned(frames, sender, |position, item| {
+            if position.is_last() { format!("{}!", item) } else { String::from(item) }
+        })).unwrap();
+
+        let result: Vec<_> = block_on(receiver.collect());
+        assert_eq!(result, vec!["a", "b", "c!"]);
+    }
+}
+
+#[cfg(all(test, feature = "async-std"))]
+mod async_std_tests {
+    use async_std::channel;
+    use async_std::task::block_on;
+    use futures::stream::{self, StreamExt};
+    use {Position, StreamWithPositionExt};
+
+    #[test]
+    fn it_marks_positions_on_the_async_std_executor() {
+        let result: Vec<_> = block_on(stream::iter(vec![1, 2, 3]).with_position().collect());
+
+        assert_eq!(result, vec![(Position::First, 1), (Position::Middle, 2), (Position::Last, 3)]);
+    }
+
+    #[test]
+    fn it_marks_positions_of_async_std_channel_messages() {
+        let (sender, receiver) = channel::unbounded();
+        sender.try_send("a").unwrap();
+        sender.try_send("b").unwrap();
+        drop(sender);
+
+        let result: Vec<_> = block_on(receiver.with_position().collect());
+
+        assert_eq!(result, vec![(Position::First, "a"), (Position::Last, "b")]);
+    }
+}
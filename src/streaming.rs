@@ -0,0 +1,115 @@
+use std::borrow::Borrow;
+
+use streaming_iterator::StreamingIterator;
+
+use Position;
+
+/// Extension trait for streaming iterators which adds the `with_position`
+/// method.
+pub trait StreamingWithPosition: StreamingIterator + Sized where Self::Item: ToOwned {
+    /// Yield the items of the streaming iterator, while keeping track of the
+    /// position of the current item, which can be retrieved through
+    /// `PositionStreamingIterator::position`.
+    ///
+    /// Finding out if an item is the last one requires advancing the source,
+    /// which invalidates the item it yielded, so the current item is copied
+    /// into a buffer owned by the adapter. The buffer is reused between items
+    /// via `ToOwned::clone_into`, so it only allocates when it needs to grow.
+    fn with_position(self) -> PositionStreamingIterator<Self> {
+        PositionStreamingIterator { iter: self, current: None, position: None, started: false, did_iter: false }
+    }
+}
+
+impl<I> StreamingWithPosition for I where I: StreamingIterator, I::Item: ToOwned {}
+
+/// A streaming iterator adapter that yields the items of the source and keeps
+/// track of the position of the current item.
+pub struct PositionStreamingIterator<I> where I: StreamingIterator, I::Item: ToOwned {
+    iter: I,
+    current: Option<<I::Item as ToOwned>::Owned>,
+    position: Option<Position>,
+    started: bool,
+    did_iter: bool,
+}
+
+impl<I> PositionStreamingIterator<I> where I: StreamingIterator, I::Item: ToOwned {
+    /// The position of the current item, or `None` before the first call to
+    /// `advance` and once the iterator is exhausted.
+    pub fn position(&self) -> Option<Position> {
+        self.position
+    }
+
+    /// Advance the iterator, returning the position and the item.
+    pub fn next_with_position(&mut self) -> Option<(Position, &I::Item)> {
+        self.advance();
+        let position = self.position?;
+        self.current.as_ref().map(|item| (position, item.borrow()))
+    }
+}
+
+impl<I> StreamingIterator for PositionStreamingIterator<I> where I: StreamingIterator, I::Item: ToOwned {
+    type Item = I::Item;
+
+    fn advance(&mut self) {
+        if !self.started {
+            self.iter.advance();
+            self.started = true;
+        }
+
+        match self.iter.get() {
+            Some(item) => match self.current {
+                Some(ref mut current) => item.clone_into(current),
+                None => self.current = Some(item.to_owned()),
+            },
+            None => {
+                self.position = None;
+                return;
+            }
+        }
+
+        self.iter.advance();
+        let is_first = !self.did_iter;
+        let is_last = self.iter.get().is_none();
+        self.did_iter = true;
+        self.position = Some(Position::from_flags(is_first, is_last));
+    }
+
+    fn get(&self) -> Option<&I::Item> {
+        match self.position {
+            Some(_) => self.current.as_ref().map(Borrow::borrow),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use streaming_iterator::{convert, StreamingIterator};
+    use {Position, StreamingWithPosition};
+
+    #[test]
+    fn it_marks_positions_of_streamed_items() {
+        let mut iter = convert(vec![1, 2, 3]).with_position();
+        let mut result = Vec::new();
+
+        while let Some((position, &item)) = iter.next_with_position() {
+            result.push((position, item));
+        }
+
+        assert_eq!(result, vec![(Position::First, 1), (Position::Middle, 2), (Position::Last, 3)]);
+        assert_eq!(iter.position(), None);
+    }
+
+    #[test]
+    fn it_works_with_unsized_items() {
+        let words = ["one", "two"];
+        let mut iter = convert(words.iter().cloned()).map_ref(|word: &&str| *word).with_position();
+
+        iter.advance();
+        assert_eq!((iter.position(), iter.get()), (Some(Position::First), Some("one")));
+        iter.advance();
+        assert_eq!((iter.position(), iter.get()), (Some(Position::Last), Some("two")));
+        iter.advance();
+        assert_eq!(iter.get(), None);
+    }
+}
@@ -0,0 +1,82 @@
+use std::str::{CharIndices, Chars, Split, SplitTerminator, SplitWhitespace};
+
+use {PositionIterator, WithPosition};
+
+/// Extension trait for string slices which adds positioned iteration methods.
+pub trait StrWithPosition {
+    /// Yield a tuple of `(Position, char)` for each character in the string.
+    fn chars_with_position(&self) -> PositionIterator<Chars<'_>>;
+
+    /// Yield a tuple of `(Position, (byte_index, char))` for each character in
+    /// the string.
+    fn char_indices_with_position(&self) -> PositionIterator<CharIndices<'_>>;
+
+    /// Yield a tuple of `(Position, field)` for each substring separated by the
+    /// given pattern.
+    fn split_with_position<'a>(&'a self, pat: &'a str) -> PositionIterator<Split<'a, &'a str>>;
+
+    /// Like `split_with_position`, but a trailing empty substring is skipped,
+    /// so the last field is the last one actually terminated by the pattern.
+    fn split_terminator_with_position<'a>(&'a self, pat: &'a str) -> PositionIterator<SplitTerminator<'a, &'a str>>;
+
+    /// Yield a tuple of `(Position, word)` for each substring separated by any
+    /// amount of whitespace.
+    fn split_whitespace_with_position(&self) -> PositionIterator<SplitWhitespace<'_>>;
+}
+
+impl StrWithPosition for str {
+    fn chars_with_position(&self) -> PositionIterator<Chars<'_>> {
+        self.chars().with_position()
+    }
+
+    fn char_indices_with_position(&self) -> PositionIterator<CharIndices<'_>> {
+        self.char_indices().with_position()
+    }
+
+    fn split_with_position<'a>(&'a self, pat: &'a str) -> PositionIterator<Split<'a, &'a str>> {
+        self.split(pat).with_position()
+    }
+
+    fn split_terminator_with_position<'a>(&'a self, pat: &'a str) -> PositionIterator<SplitTerminator<'a, &'a str>> {
+        self.split_terminator(pat).with_position()
+    }
+
+    fn split_whitespace_with_position(&self) -> PositionIterator<SplitWhitespace<'_>> {
+        self.split_whitespace().with_position()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Position, StrWithPosition};
+
+    #[test]
+    fn it_marks_positions_of_chars() {
+        let result: Vec<_> = "abc".chars_with_position().collect();
+
+        assert_eq!(result, vec![(Position::First, 'a'), (Position::Middle, 'b'), (Position::Last, 'c')]);
+    }
+
+    #[test]
+    fn it_marks_positions_of_char_indices() {
+        let result: Vec<_> = "añ".char_indices_with_position().collect();
+
+        assert_eq!(result, vec![(Position::First, (0, 'a')), (Position::Last, (1, 'ñ'))]);
+    }
+
+    #[test]
+    fn it_marks_positions_of_split_fields() {
+        let result: Vec<_> = "a,b,".split_with_position(",").collect();
+        assert_eq!(result, vec![(Position::First, "a"), (Position::Middle, "b"), (Position::Last, "")]);
+
+        let result: Vec<_> = "a;b;".split_terminator_with_position(";").collect();
+        assert_eq!(result, vec![(Position::First, "a"), (Position::Last, "b")]);
+    }
+
+    #[test]
+    fn it_marks_positions_of_whitespace_separated_words() {
+        let result: Vec<_> = "  one  two ".split_whitespace_with_position().collect();
+
+        assert_eq!(result, vec![(Position::First, "one"), (Position::Last, "two")]);
+    }
+}
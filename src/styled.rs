@@ -0,0 +1,111 @@
+use std::fmt::Display;
+use std::io::{self, IsTerminal, Write};
+
+use anstyle::Style;
+
+use {Position, WithPosition};
+
+/// Terminal styles for lines depending on their position, such as a bold
+/// header line and a dimmed summary line. All styles are plain by default.
+///
+/// ```
+/// extern crate anstyle;
+/// # extern crate with_position;
+/// use anstyle::Style;
+/// use with_position::LineStyles;
+///
+/// # fn main() {
+/// let styles = LineStyles::new().first(Style::new().bold()).last(Style::new().dimmed());
+/// let mut output = Vec::new();
+/// styles.write_lines(&mut output, vec!["Name", "a", "2 rows"], false).unwrap();
+///
+/// assert_eq!(output, b"Name\na\n2 rows\n");
+/// # }
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct LineStyles {
+    first: Style,
+    middle: Style,
+    last: Style,
+    only: Style,
+}
+
+impl LineStyles {
+    pub fn new() -> LineStyles {
+        LineStyles::default()
+    }
+
+    /// Set the style of the first line, when there is more than one line.
+    pub fn first(mut self, style: Style) -> LineStyles {
+        self.first = style;
+        self
+    }
+
+    /// Set the style of every line between the first and the last one.
+    pub fn middle(mut self, style: Style) -> LineStyles {
+        self.middle = style;
+        self
+    }
+
+    /// Set the style of the last line, when there is more than one line.
+    pub fn last(mut self, style: Style) -> LineStyles {
+        self.last = style;
+        self
+    }
+
+    /// Set the style of a line which is both the first and the last one.
+    pub fn only(mut self, style: Style) -> LineStyles {
+        self.only = style;
+        self
+    }
+
+    /// Return the style of a line at the position.
+    pub fn style(&self, position: Position) -> Style {
+        match position {
+            Position::First => self.first,
+            Position::Middle => self.middle,
+            Position::Last => self.last,
+            Position::Only => self.only,
+        }
+    }
+
+    /// Write every line followed by a newline, styled by its position if
+    /// `color` is true and as plain text otherwise.
+    pub fn write_lines<W, I>(&self, out: &mut W, lines: I, color: bool) -> io::Result<()> where W: Write + ?Sized, I: IntoIterator, I::Item: Display {
+        for (position, line) in lines.into_iter().with_position() {
+            if color {
+                let style = self.style(position);
+                writeln!(out, "{}{}{}", style.render(), line, style.render_reset())?;
+            } else {
+                writeln!(out, "{}", line)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the lines to standard output, styled only if it is a terminal.
+    pub fn write_lines_to_stdout<I>(&self, lines: I) -> io::Result<()> where I: IntoIterator, I::Item: Display {
+        let stdout = io::stdout();
+        let color = stdout.is_terminal();
+        self.write_lines(&mut stdout.lock(), lines, color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anstyle::Style;
+    use {LineStyles, Position};
+
+    #[test]
+    fn it_styles_lines_by_position() {
+        let bold = Style::new().bold();
+        let styles = LineStyles::new().first(bold).only(bold);
+        let mut output = Vec::new();
+
+        styles.write_lines(&mut output, vec!["head", "body"], true).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), format!("{}head{}\nbody\n", bold.render(), bold.render_reset()));
+        assert_eq!(styles.style(Position::Only), bold);
+        assert_eq!(styles.style(Position::Last), Style::new());
+    }
+}
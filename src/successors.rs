@@ -0,0 +1,46 @@
+use std::iter::{successors, Successors};
+
+use {PositionIterator, WithPosition};
+
+/// Generate a sequence starting at `first`, where each following item is
+/// computed from the one before it until the closure returns `None`, like
+/// `std::iter::successors`, and yield a tuple of `(Position, item)`. The
+/// closure is called one item ahead, so an item is known to be the last one
+/// as soon as it is yielded.
+///
+/// ```
+/// use with_position::{successors_with_position, Position};
+///
+/// let pages: Vec<_> = successors_with_position(Some(1), |&page| if page < 3 { Some(page + 1) } else { None }).collect();
+///
+/// assert_eq!(pages, vec![(Position::First, 1), (Position::Middle, 2), (Position::Last, 3)]);
+/// ```
+pub fn successors_with_position<T, F>(first: Option<T>, succ: F) -> PositionIterator<Successors<T, F>> where F: FnMut(&T) -> Option<T> {
+    successors(first, succ).with_position()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use {successors_with_position, Position};
+
+    #[test]
+    fn it_calls_the_closure_once_per_item() {
+        let calls = Cell::new(0);
+        let parents = [None, Some(0), Some(1)];
+        let mut chain = successors_with_position(Some(2), |&node| {
+            calls.set(calls.get() + 1);
+            parents[node]
+        });
+
+        assert_eq!(chain.next(), Some((Position::First, 2)));
+        assert_eq!(calls.get(), 2);
+        assert_eq!(chain.collect::<Vec<_>>(), vec![(Position::Middle, 1), (Position::Last, 0)]);
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn it_yields_nothing_without_a_first_item() {
+        assert_eq!(successors_with_position(None::<u8>, |_| Some(1)).next(), None);
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt::Display;
+
+use {Position, WithPosition};
+
+/// The characters used to draw the borders of a table. Each three element
+/// array holds the character for the left edge, between columns and the right
+/// edge of a row.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TableCharset {
+    pub horizontal: char,
+    pub vertical: [char; 3],
+    pub top: [char; 3],
+    pub separator: [char; 3],
+    pub bottom: [char; 3],
+}
+
+impl TableCharset {
+    /// Borders drawn with `+`, `-` and `|`.
+    pub const ASCII: TableCharset = TableCharset {
+        horizontal: '-',
+        vertical: ['|', '|', '|'],
+        top: ['+', '+', '+'],
+        separator: ['+', '+', '+'],
+        bottom: ['+', '+', '+'],
+    };
+
+    /// Borders drawn with Unicode box drawing characters.
+    pub const UNICODE: TableCharset = TableCharset {
+        horizontal: '─',
+        vertical: ['│', '│', '│'],
+        top: ['┌', '┬', '┐'],
+        separator: ['├', '┼', '┤'],
+        bottom: ['└', '┴', '┘'],
+    };
+}
+
+impl Default for TableCharset {
+    fn default() -> TableCharset {
+        TableCharset::ASCII
+    }
+}
+
+/// Render the rows as a table, with a top border before the first row, a
+/// separator line between rows and a bottom border after the last row. Columns
+/// are as wide as their widest cell. Nothing is rendered for an empty table.
+///
+/// ```
+/// use with_position::{render_table, TableCharset};
+///
+/// let table = render_table(vec![vec!["a", "bb"], vec!["ccc", "d"]], &TableCharset::ASCII);
+///
+/// assert_eq!(table, "\
+/// +-----+----+
+/// | a   | bb |
+/// +-----+----+
+/// | ccc | d  |
+/// +-----+----+
+/// ");
+/// ```
+pub fn render_table<I, R>(rows: I, charset: &TableCharset) -> String
+    where I: IntoIterator<Item = R>, R: IntoIterator, R::Item: Display
+{
+    let rows: Vec<Vec<String>> = rows.into_iter().map(|row| row.into_iter().map(|cell| cell.to_string()).collect()).collect();
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns).map(|column| {
+        rows.iter().filter_map(|row| row.get(column)).map(|cell| cell.chars().count()).max().unwrap_or(0)
+    }).collect();
+
+    let mut output = String::new();
+    for (position, row) in rows.iter().with_position() {
+        let border = if position.is_first() { charset.top } else { charset.separator };
+        push_border(&mut output, &widths, charset.horizontal, border);
+
+        for (column, (column_position, width)) in widths.iter().with_position().enumerate() {
+            let cell = row.get(column).map_or("", String::as_str);
+            push_edge(&mut output, column_position, charset.vertical);
+            output.push(' ');
+            output.push_str(cell);
+            output.extend(std::iter::repeat_n(' ', width - cell.chars().count() + 1));
+        }
+        output.push(charset.vertical[2]);
+        output.push('\n');
+
+        if position.is_last() {
+            push_border(&mut output, &widths, charset.horizontal, charset.bottom);
+        }
+    }
+    output
+}
+
+fn push_border(output: &mut String, widths: &[usize], horizontal: char, border: [char; 3]) {
+    for (position, &width) in widths.iter().with_position() {
+        push_edge(output, position, border);
+        output.extend(std::iter::repeat_n(horizontal, width + 2));
+    }
+    output.push(border[2]);
+    output.push('\n');
+}
+
+fn push_edge(output: &mut String, position: Position, edges: [char; 3]) {
+    output.push(if position.is_first() { edges[0] } else { edges[1] });
+}
+
+#[cfg(test)]
+mod tests {
+    use {render_table, TableCharset};
+
+    #[test]
+    fn it_renders_a_table_with_unicode_borders() {
+        let table = render_table(vec![vec![1, 2]], &TableCharset::UNICODE);
+
+        assert_eq!(table, "┌───┬───┐\n│ 1 │ 2 │\n└───┴───┘\n");
+    }
+
+    #[test]
+    fn it_renders_nothing_for_an_empty_table() {
+        assert_eq!(render_table(Vec::<Vec<u8>>::new(), &TableCharset::ASCII), "");
+    }
+}
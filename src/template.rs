@@ -0,0 +1,146 @@
+#[cfg(feature = "tera")]
+use std::collections::HashMap;
+use std::iter::Enumerate;
+
+use {Position, PositionIterator, WithPosition};
+
+/// An item together with its position, with fields which are convenient to
+/// use from templates, such as `{% if item.is_last %}`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TemplateItem<T> {
+    pub position: Position,
+    pub index: usize,
+    pub is_first: bool,
+    pub is_last: bool,
+    pub value: T,
+}
+
+/// Return an iterator over `TemplateItem`s for the items, for use by template
+/// engines which access fields of Rust values, such as Askama.
+pub fn template_items<I>(iter: I) -> TemplateItems<I::IntoIter> where I: IntoIterator {
+    TemplateItems { iter: iter.into_iter().with_position().enumerate() }
+}
+
+/// An iterator over `TemplateItem`s, created by `template_items`.
+pub struct TemplateItems<I> where I: Iterator {
+    iter: Enumerate<PositionIterator<I>>,
+}
+
+impl<I> Iterator for TemplateItems<I> where I: Iterator {
+    type Item = TemplateItem<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, (position, value)) = self.iter.next()?;
+        Some(TemplateItem { position, index, is_first: position.is_first(), is_last: position.is_last(), value })
+    }
+}
+
+/// Filters for Askama templates. Make them available to templates by
+/// re-exporting them from the `filters` module next to the template:
+///
+/// ```text
+/// mod filters {
+///     pub use with_position::askama_filters::*;
+/// }
+///
+/// {% for item in names|with_position %}
+///     {{ item.value }}{% if !item.is_last %},{% endif %}
+/// {% endfor %}
+/// ```
+#[cfg(feature = "askama")]
+pub mod askama_filters {
+    use askama;
+
+    use super::{template_items, TemplateItems};
+
+    /// Turn the items into `TemplateItem`s.
+    #[askama::filter_fn]
+    pub fn with_position<I>(iter: I, _: &dyn askama::Values) -> askama::Result<TemplateItems<I::IntoIter>> where I: IntoIterator {
+        Ok(template_items(iter))
+    }
+}
+
+/// Register the `with_position` filter with Tera. The filter turns an array
+/// into an array of objects with `position`, `index`, `is_first`, `is_last`
+/// and `value` fields, where `position` is one of `"first"`, `"middle"`,
+/// `"last"` or `"only"`.
+///
+/// ```text
+/// {% for item in names | with_position %}
+///     {{ item.value }}{% if not item.is_last %},{% endif %}
+/// {% endfor %}
+/// ```
+#[cfg(feature = "tera")]
+pub fn register_tera_filters(tera: &mut tera::Tera) {
+    tera.register_filter("with_position", tera_with_position);
+}
+
+#[cfg(feature = "tera")]
+fn tera_with_position(value: &tera::Value, _: &HashMap<String, tera::Value>) -> tera::Result<tera::Value> {
+    let values = match *value {
+        tera::Value::Array(ref values) => values,
+        _ => return Err(tera::Error::msg("Filter `with_position` was called on a value which is not an array")),
+    };
+    let items = template_items(values).map(|item| {
+        let mut object = tera::Map::new();
+        object.insert(String::from("position"), tera::Value::from(item.position.as_str()));
+        object.insert(String::from("index"), tera::Value::from(item.index));
+        object.insert(String::from("is_first"), tera::Value::from(item.is_first));
+        object.insert(String::from("is_last"), tera::Value::from(item.is_last));
+        object.insert(String::from("value"), item.value.clone());
+        tera::Value::Object(object)
+    });
+    Ok(tera::Value::Array(items.collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use {template_items, Position, TemplateItem};
+
+    #[test]
+    fn it_yields_template_items() {
+        let items: Vec<_> = template_items(vec!["a", "b"]).collect();
+
+        assert_eq!(items[0], TemplateItem { position: Position::First, index: 0, is_first: true, is_last: false, value: "a" });
+        assert_eq!(items[1], TemplateItem { position: Position::Last, index: 1, is_first: false, is_last: true, value: "b" });
+    }
+}
+
+#[cfg(all(test, feature = "askama"))]
+mod askama_tests {
+    use askama::Template;
+
+    mod filters {
+        pub use askama_filters::*;
+    }
+
+    #[derive(Template)]
+    #[template(source = "{% for item in names|with_position %}{{ item.value }}{% if !item.is_last %}, {% endif %}{% endfor %}", ext = "txt")]
+    struct Names {
+        names: Vec<&'static str>,
+    }
+
+    #[test]
+    fn it_exposes_positions_to_askama_templates() {
+        assert_eq!(Names { names: vec!["a", "b", "c"] }.render().unwrap(), "a, b, c");
+    }
+}
+
+#[cfg(all(test, feature = "tera"))]
+mod tera_tests {
+    use tera::{Context, Tera};
+    use register_tera_filters;
+
+    #[test]
+    fn it_exposes_positions_to_tera_templates() {
+        let mut tera = Tera::default();
+        register_tera_filters(&mut tera);
+        let mut context = Context::new();
+        context.insert("names", &vec!["a", "b", "c"]);
+
+        let template = "{% for item in names | with_position %}{{ item.value }}{% if not item.is_last %}, {% endif %}{% endfor %}";
+        let output = tera.render_str(template, &context).unwrap();
+
+        assert_eq!(output, "a, b, c");
+    }
+}
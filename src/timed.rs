@@ -0,0 +1,87 @@
+use std::iter::Peekable;
+use std::time::Duration;
+
+use Position;
+
+/// A bucket of items which fall into the same time window, yielded by
+/// `TimeWindowWithPosition::window_by_time`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TimeWindow<T> {
+    /// The start of the window, as a multiple of the window duration.
+    pub start: Duration,
+    /// The items of the window together with their position within it.
+    pub items: Vec<(Position, T)>,
+}
+
+/// Extension trait for iterators which adds the `window_by_time` method.
+pub trait TimeWindowWithPosition: Iterator + Sized {
+    /// Group consecutive items into windows of the given duration, according to
+    /// the timestamp returned by the closure, and yield a tuple of
+    /// `(Position, TimeWindow)` for each window which has items. Timestamps are
+    /// durations since some fixed point in time, and the items are expected to
+    /// be in order, so a window ends as soon as an item falls outside of it.
+    ///
+    /// Panics if `duration` is zero.
+    fn window_by_time<F>(self, duration: Duration, timestamp: F) -> TimeWindows<Self, F> where F: FnMut(&Self::Item) -> Duration {
+        assert!(duration > Duration::from_secs(0), "window duration must be greater than zero");
+        TimeWindows { iter: self.peekable(), duration, timestamp, did_iter: false }
+    }
+}
+
+impl<I> TimeWindowWithPosition for I where I: Iterator {}
+
+/// An iterator adapter that yields positioned time windows, created by
+/// `TimeWindowWithPosition::window_by_time`.
+pub struct TimeWindows<I, F> where I: Iterator {
+    iter: Peekable<I>,
+    duration: Duration,
+    timestamp: F,
+    did_iter: bool,
+}
+
+impl<I, F> Iterator for TimeWindows<I, F> where I: Iterator, F: FnMut(&I::Item) -> Duration {
+    type Item = (Position, TimeWindow<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.iter.next()?;
+        let nanos = self.duration.as_nanos();
+        let timestamp = &mut self.timestamp;
+        let window = timestamp(&first).as_nanos() / nanos;
+        let mut items = vec![first];
+        while let Some(item) = self.iter.next_if(|item| timestamp(item).as_nanos() / nanos == window) {
+            items.push(item);
+        }
+
+        let is_first = !self.did_iter;
+        let is_last = self.iter.peek().is_none();
+        self.did_iter = true;
+
+        let len = items.len();
+        let items = items.into_iter().enumerate().map(|(index, item)| (Position::from_index(index, len), item)).collect();
+        let start = window * nanos;
+        let start = Duration::new((start / 1_000_000_000) as u64, (start % 1_000_000_000) as u32);
+        Some((Position::from_flags(is_first, is_last), TimeWindow { start, items }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use {Position, TimeWindowWithPosition};
+
+    #[test]
+    fn it_groups_items_into_positioned_time_windows() {
+        let samples = vec![(1, "a"), (4, "b"), (12, "c"), (31, "d"), (39, "e")];
+
+        let result: Vec<_> = samples.into_iter()
+            .window_by_time(Duration::from_secs(10), |&(seconds, _)| Duration::from_secs(seconds))
+            .map(|(position, window)| (position, window.start.as_secs(), window.items.into_iter().map(|(p, (_, name))| (p, name)).collect::<Vec<_>>()))
+            .collect();
+
+        assert_eq!(result, vec![
+            (Position::First, 0, vec![(Position::First, "a"), (Position::Last, "b")]),
+            (Position::Middle, 10, vec![(Position::Only, "c")]),
+            (Position::Last, 30, vec![(Position::First, "d"), (Position::Last, "e")]),
+        ]);
+    }
+}
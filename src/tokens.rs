@@ -0,0 +1,34 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+
+use WithPosition;
+
+/// Join the items into a token stream with the separator between them, but
+/// not after the last one, such as for the arguments of a generated function
+/// call.
+pub fn join_tokens<I, S>(items: I, separator: &S) -> TokenStream where I: IntoIterator, I::Item: ToTokens, S: ToTokens {
+    let mut tokens = TokenStream::new();
+    for (position, item) in items.into_iter().with_position() {
+        item.to_tokens(&mut tokens);
+        if !position.is_last() {
+            separator.to_tokens(&mut tokens);
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::{Ident, Punct, Spacing, Span};
+    use join_tokens;
+
+    #[test]
+    fn it_joins_tokens_with_a_separator_between_them() {
+        let idents: Vec<_> = ["a", "b", "c"].iter().map(|name| Ident::new(name, Span::call_site())).collect();
+        let comma = Punct::new(',', Spacing::Alone);
+
+        assert_eq!(join_tokens(&idents, &comma).to_string(), "a , b , c");
+        assert_eq!(join_tokens(&idents[..1], &comma).to_string(), "a");
+        assert!(join_tokens(Vec::<Ident>::new(), &comma).is_empty());
+    }
+}
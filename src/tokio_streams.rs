@@ -0,0 +1,271 @@
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+use tokio::sync::mpsc::{Receiver, UnboundedReceiver};
+use tokio::time::{sleep, Instant, Interval, Sleep};
+use tokio_stream::adapters::Take;
+use tokio_stream::wrappers::{IntervalStream, LinesStream, ReceiverStream, UnboundedReceiverStream};
+use tokio_stream::StreamExt;
+
+use {Position, PositionStream, StreamWithPositionExt};
+
+/// Wrap a tokio channel receiver in a positioned stream. The last message is
+/// yielded once all senders have been dropped and the channel is empty.
+pub fn receiver_with_position<T>(receiver: Receiver<T>) -> PositionStream<ReceiverStream<T>> {
+    ReceiverStream::new(receiver).with_position()
+}
+
+/// Wrap an unbounded tokio channel receiver in a positioned stream.
+pub fn unbounded_receiver_with_position<T>(receiver: UnboundedReceiver<T>) -> PositionStream<UnboundedReceiverStream<T>> {
+    UnboundedReceiverStream::new(receiver).with_position()
+}
+
+/// An async wrapper around a tokio channel receiver which yields tuples of
+/// `(Position, message)`. It is the stream returned by
+/// `receiver_with_position`, with `recv` and `close` methods like those of the
+/// receiver it wraps.
+pub struct PositionedTokioReceiver<T> {
+    stream: PositionStream<ReceiverStream<T>>,
+}
+
+impl<T> PositionedTokioReceiver<T> {
+    pub fn new(receiver: Receiver<T>) -> PositionedTokioReceiver<T> {
+        PositionedTokioReceiver { stream: receiver_with_position(receiver) }
+    }
+
+    /// Receive the next positioned message, resolving to `None` once the
+    /// channel has closed and all messages have been yielded.
+    pub fn recv(&mut self) -> RecvPositioned<'_, T> {
+        RecvPositioned { receiver: self }
+    }
+
+    /// Close the receiving half of the channel, so no more messages can be
+    /// sent. Messages already in the channel are still yielded.
+    pub fn close(&mut self) {
+        self.stream.get_mut().close();
+    }
+
+    /// Poll for the next positioned message.
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<(Position, T)>> {
+        Pin::new(&mut self.stream).poll_next(cx)
+    }
+}
+
+/// A future which resolves to the next positioned message, created by
+/// `PositionedTokioReceiver::recv`.
+pub struct RecvPositioned<'a, T: 'a> {
+    receiver: &'a mut PositionedTokioReceiver<T>,
+}
+
+impl<'a, T> Future for RecvPositioned<'a, T> {
+    type Output = Option<(Position, T)>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<(Position, T)>> {
+        self.get_mut().receiver.poll_recv(cx)
+    }
+}
+
+/// Yield the given number of ticks of an interval as a positioned stream, so
+/// the final tick is marked as last. Intervals never end by themselves, so the
+/// number of ticks has to be known up front.
+pub fn interval_with_position(interval: Interval, ticks: usize) -> PositionStream<Take<IntervalStream>> {
+    IntervalStream::new(interval).take(ticks).with_position()
+}
+
+/// Extension trait for tokio's buffered async readers which adds the
+/// `lines_with_position` method.
+pub trait AsyncBufReadWithPosition: AsyncBufRead + Sized {
+    /// Yield an `io::Result<(Position, line)>` for each line of the reader, as
+    /// a stream. The last line is yielded once the reader reaches the end.
+    fn lines_with_position(self) -> PositionedAsyncLines<Self> {
+        PositionedAsyncLines { inner: LinesStream::new(self.lines()).with_position() }
+    }
+}
+
+impl<R> AsyncBufReadWithPosition for R where R: AsyncBufRead {}
+
+pin_project! {
+    /// A stream over the positioned lines of an async reader, created by
+    /// `AsyncBufReadWithPosition::lines_with_position`.
+    pub struct PositionedAsyncLines<R> where R: AsyncBufRead {
+        #[pin]
+        inner: PositionStream<LinesStream<R>>,
+    }
+}
+
+impl<R> Stream for PositionedAsyncLines<R> where R: AsyncBufRead {
+    type Item = io::Result<(Position, String)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().inner.poll_next(cx).map(|next| next.map(|(position, line)| line.map(|line| (position, line))))
+    }
+}
+
+/// Extension trait for streams which adds the `with_position_timeout` method.
+pub trait StreamTimeoutWithPosition: Stream + Sized {
+    /// Like `StreamWithPositionExt::with_position`, but if no item arrives
+    /// within the timeout after the most recent one, that item is yielded as
+    /// the last item and the stream ends, even though the source has not. This
+    /// is useful for feeds which never close cleanly. No timeout applies
+    /// before the first item, and the rest of the source is dropped unread.
+    ///
+    /// Panics if polled outside of a Tokio runtime with the time driver
+    /// enabled.
+    fn with_position_timeout(self, timeout: Duration) -> PositionTimeoutStream<Self> {
+        PositionTimeoutStream { stream: self, timeout, sleep: None, pending: None, did_iter: false, done: false }
+    }
+}
+
+impl<S> StreamTimeoutWithPosition for S where S: Stream {}
+
+pin_project! {
+    /// A stream adapter that yields positioned items and ends once the source
+    /// has been idle for a while, created by
+    /// `StreamTimeoutWithPosition::with_position_timeout`.
+    pub struct PositionTimeoutStream<S> where S: Stream {
+        #[pin]
+        stream: S,
+        timeout: Duration,
+        sleep: Option<Pin<Box<Sleep>>>,
+        pending: Option<S::Item>,
+        did_iter: bool,
+        done: bool,
+    }
+}
+
+impl<S> Stream for PositionTimeoutStream<S> where S: Stream {
+    type Item = (Position, S::Item);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        let deadline = Instant::now() + *this.timeout;
+        if this.pending.is_none() {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => *this.pending = Some(item),
+                Poll::Ready(None) => {
+                    *this.done = true;
+                    return Poll::Ready(None);
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+            match *this.sleep {
+                Some(ref mut sleep) => sleep.as_mut().reset(deadline),
+                None => *this.sleep = Some(Box::pin(sleep(*this.timeout))),
+            }
+        }
+
+        let is_first = !*this.did_iter;
+        match this.stream.as_mut().poll_next(cx) {
+            Poll::Ready(Some(next)) => {
+                if let Some(ref mut sleep) = *this.sleep {
+                    sleep.as_mut().reset(deadline);
+                }
+                *this.did_iter = true;
+                let current = this.pending.replace(next);
+                return Poll::Ready(current.map(|item| (Position::from_flags(is_first, false), item)));
+            }
+            Poll::Ready(None) => {}
+            Poll::Pending => {
+                let timed_out = match *this.sleep {
+                    Some(ref mut sleep) => sleep.as_mut().poll(cx).is_ready(),
+                    None => false,
+                };
+                if !timed_out {
+                    return Poll::Pending;
+                }
+            }
+        }
+
+        *this.done = true;
+        *this.did_iter = true;
+        Poll::Ready(this.pending.take().map(|item| (Position::from_flags(is_first, true), item)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::stream::StreamExt;
+    use tokio::runtime::{Builder, Runtime};
+    use tokio::sync::mpsc;
+    use {interval_with_position, receiver_with_position, AsyncBufReadWithPosition, Position, PositionedTokioReceiver, StreamTimeoutWithPosition};
+
+    fn runtime() -> Runtime {
+        Builder::new_current_thread().enable_time().build().unwrap()
+    }
+
+    #[test]
+    fn it_marks_positions_of_received_messages() {
+        let result: Vec<_> = runtime().block_on({
+            let (sender, receiver) = mpsc::channel(4);
+            sender.try_send(1).unwrap();
+            sender.try_send(2).unwrap();
+            drop(sender);
+            receiver_with_position(receiver).collect()
+        });
+
+        assert_eq!(result, vec![(Position::First, 1), (Position::Last, 2)]);
+    }
+
+    #[test]
+    fn it_marks_positions_of_interval_ticks() {
+        let runtime = runtime();
+        let interval = {
+            let _guard = runtime.enter();
+            tokio::time::interval(Duration::from_millis(1))
+        };
+        let result: Vec<_> = runtime.block_on(interval_with_position(interval, 3).map(|(position, _)| position).collect());
+
+        assert_eq!(result, vec![Position::First, Position::Middle, Position::Last]);
+    }
+
+    #[test]
+    fn it_marks_positions_of_async_lines() {
+        let result: Vec<_> = runtime().block_on(b"one\ntwo\nthree\n".lines_with_position().collect::<Vec<_>>());
+
+        assert_eq!(result.into_iter().map(Result::unwrap).collect::<Vec<_>>(), vec![
+            (Position::First, String::from("one")),
+            (Position::Middle, String::from("two")),
+            (Position::Last, String::from("three")),
+        ]);
+    }
+
+    #[test]
+    fn it_receives_positioned_messages_until_the_channel_closes() {
+        let runtime = runtime();
+        let (sender, receiver) = mpsc::channel(4);
+        let mut receiver = PositionedTokioReceiver::new(receiver);
+
+        sender.try_send("a").unwrap();
+        sender.try_send("b").unwrap();
+        assert_eq!(runtime.block_on(receiver.recv()), Some((Position::First, "a")));
+
+        drop(sender);
+        assert_eq!(runtime.block_on(receiver.recv()), Some((Position::Last, "b")));
+        assert_eq!(runtime.block_on(receiver.recv()), None);
+    }
+
+    #[test]
+    fn it_ends_with_the_last_item_when_the_source_is_idle() {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        sender.send(1).unwrap();
+        sender.send(2).unwrap();
+
+        let stream = tokio_stream::wrappers::UnboundedReceiverStream::new(receiver).with_position_timeout(Duration::from_millis(10));
+        let result: Vec<_> = runtime().block_on(stream.collect());
+
+        assert_eq!(result, vec![(Position::First, 1), (Position::Last, 2)]);
+        drop(sender);
+    }
+}
@@ -0,0 +1,100 @@
+use tracing;
+use tracing::span::EnteredSpan;
+
+use {Position, PositionIterator, WithPosition};
+
+/// Extension trait for iterators which adds the `with_position_traced`
+/// method.
+pub trait TracedWithPosition: Iterator + Sized {
+    /// Yield a tuple of `(Position, item)` like `with_position`, while entering
+    /// a `with_position` span at the info level for each item. The span has a
+    /// `name` field with the given name, and `position`, `index` and `is_last`
+    /// fields for the item. It stays entered until the next item is requested,
+    /// so anything logged while handling an item is recorded within its span.
+    fn with_position_traced(self, name: &'static str) -> PositionTraced<Self> {
+        PositionTraced { iter: self.with_position(), name, index: 0, span: None }
+    }
+}
+
+impl<I> TracedWithPosition for I where I: Iterator {}
+
+/// An iterator adapter that yields tuples of `(Position, item)` and enters a
+/// tracing span for each item, created by
+/// `TracedWithPosition::with_position_traced`.
+pub struct PositionTraced<I> where I: Iterator {
+    iter: PositionIterator<I>,
+    name: &'static str,
+    index: usize,
+    span: Option<EnteredSpan>,
+}
+
+impl<I> Iterator for PositionTraced<I> where I: Iterator {
+    type Item = (Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.span = None;
+        let (position, item) = self.iter.next()?;
+        let span = tracing::info_span!("with_position", name = self.name, position = ?position, index = self.index, is_last = position.is_last());
+        self.span = Some(span.entered());
+        self.index += 1;
+        Some((position, item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{self, Event, Metadata, Subscriber};
+    use {Position, TracedWithPosition};
+
+    #[derive(Clone, Default)]
+    struct Recorder {
+        spans: Arc<Mutex<Vec<String>>>,
+    }
+
+    struct Fields(String);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0.push_str(&format!("{}={:?} ", field.name(), value));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn new_span(&self, attributes: &Attributes) -> Id {
+            let mut fields = Fields(String::new());
+            attributes.record(&mut fields);
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(fields.0.trim_end().to_string());
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _: &Id, _: &Record) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, _: &Event) {}
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn it_enters_a_span_for_each_item() {
+        let recorder = Recorder::default();
+
+        let result: Vec<_> = tracing::subscriber::with_default(recorder.clone(), || {
+            vec!["a", "b"].into_iter().with_position_traced("import").collect()
+        });
+
+        assert_eq!(result, vec![(Position::First, "a"), (Position::Last, "b")]);
+        assert_eq!(*recorder.spans.lock().unwrap(), vec![
+            "name=\"import\" position=First index=0 is_last=false",
+            "name=\"import\" position=Last index=1 is_last=true",
+        ]);
+    }
+}
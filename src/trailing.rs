@@ -0,0 +1,143 @@
+use std::iter::{Fuse, FusedIterator};
+
+use PositionIterator;
+
+/// An iterator adapter that passes the last item through a closure which may
+/// change or drop it, and yields the other items unchanged, created by
+/// `WithPosition::replace_last`.
+pub struct ReplaceLast<I, F> where I: Iterator {
+    iter: PositionIterator<I>,
+    f: Option<F>,
+}
+
+impl<I, F> ReplaceLast<I, F> where I: Iterator, F: FnOnce(I::Item) -> Option<I::Item> {
+    pub(crate) fn new(iter: PositionIterator<I>, f: F) -> ReplaceLast<I, F> {
+        ReplaceLast { iter, f: Some(f) }
+    }
+}
+
+impl<I, F> Iterator for ReplaceLast<I, F> where I: Iterator, F: FnOnce(I::Item) -> Option<I::Item> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let (position, item) = self.iter.next()?;
+        if position.is_last() {
+            self.f.take().and_then(|f| f(item))
+        } else {
+            Some(item)
+        }
+    }
+}
+
+impl<I, F> FusedIterator for ReplaceLast<I, F> where I: FusedIterator, F: FnOnce(I::Item) -> Option<I::Item> {}
+
+/// An iterator adapter that yields the items of the source, followed by an
+/// extra item if the last item does not satisfy a predicate, created by
+/// `WithPosition::ensure_trailing`.
+pub struct EnsureTrailing<I, P, M> where I: Iterator {
+    iter: PositionIterator<I>,
+    predicate: Option<P>,
+    make_item: Option<M>,
+    extra: Option<I::Item>,
+}
+
+impl<I, P, M> EnsureTrailing<I, P, M> where I: Iterator, P: FnOnce(&I::Item) -> bool, M: FnOnce() -> I::Item {
+    pub(crate) fn new(iter: PositionIterator<I>, predicate: P, make_item: M) -> EnsureTrailing<I, P, M> {
+        EnsureTrailing { iter, predicate: Some(predicate), make_item: Some(make_item), extra: None }
+    }
+}
+
+impl<I, P, M> Iterator for EnsureTrailing<I, P, M> where I: Iterator, P: FnOnce(&I::Item) -> bool, M: FnOnce() -> I::Item {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if let Some(extra) = self.extra.take() {
+            return Some(extra);
+        }
+        let (position, item) = self.iter.next()?;
+        if position.is_last() {
+            if let (Some(predicate), Some(make_item)) = (self.predicate.take(), self.make_item.take()) {
+                if !predicate(&item) {
+                    self.extra = Some(make_item());
+                }
+            }
+        }
+        Some(item)
+    }
+}
+
+/// An iterator adapter that yields the items of the source unchanged while
+/// folding them into a summary, followed by an extra item made from the
+/// summary, created by `WithPosition::append_summary`.
+pub struct AppendSummary<I, A, F, S> {
+    iter: Fuse<I>,
+    acc: Option<A>,
+    fold: F,
+    summary: Option<S>,
+    seen: bool,
+}
+
+impl<I, A, F, S> AppendSummary<I, A, F, S> where I: Iterator, F: FnMut(A, &I::Item) -> A, S: FnOnce(A) -> I::Item {
+    pub(crate) fn new(iter: I, init: A, fold: F, summary: S) -> AppendSummary<I, A, F, S> {
+        AppendSummary { iter: iter.fuse(), acc: Some(init), fold, summary: Some(summary), seen: false }
+    }
+}
+
+impl<I, A, F, S> Iterator for AppendSummary<I, A, F, S> where I: Iterator, F: FnMut(A, &I::Item) -> A, S: FnOnce(A) -> I::Item {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        match self.iter.next() {
+            Some(item) => {
+                self.seen = true;
+                self.acc = self.acc.take().map(|acc| (self.fold)(acc, &item));
+                Some(item)
+            }
+            None if self.seen => match (self.acc.take(), self.summary.take()) {
+                (Some(acc), Some(summary)) => Some(summary(acc)),
+                _ => None,
+            },
+            None => None,
+        }
+    }
+}
+
+impl<I, A, F, S> FusedIterator for AppendSummary<I, A, F, S> where I: Iterator, F: FnMut(A, &I::Item) -> A, S: FnOnce(A) -> I::Item {}
+
+#[cfg(test)]
+mod tests {
+    use WithPosition;
+
+    #[test]
+    fn it_replaces_the_last_item() {
+        let result: Vec<_> = vec!["a,", "b,", "c,"].into_iter().replace_last(|item| Some(item.trim_end_matches(','))).collect();
+
+        assert_eq!(result, vec!["a,", "b,", "c"]);
+    }
+
+    #[test]
+    fn it_drops_the_last_item() {
+        let result: Vec<_> = vec!["a", "b", ""].into_iter().replace_last(|item| if item.is_empty() { None } else { Some(item) }).collect();
+
+        assert_eq!(result, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn it_appends_an_item_if_the_last_item_fails_the_predicate() {
+        let result: String = vec!["a\n", "b"].into_iter().ensure_trailing(|item| item.ends_with('\n'), || "\n").collect();
+        assert_eq!(result, "a\nb\n");
+
+        let result: String = vec!["a\n", "b\n"].into_iter().ensure_trailing(|item| item.ends_with('\n'), || "\n").collect();
+        assert_eq!(result, "a\nb\n");
+    }
+
+    #[test]
+    fn it_appends_a_summary_after_the_last_item() {
+        let rows = vec![(String::from("a"), 2), (String::from("b"), 3)];
+        let result: Vec<_> = rows.into_iter().append_summary(0, |total, &(_, count)| total + count, |total| (String::from("total"), total)).collect();
+
+        assert_eq!(result.last(), Some(&(String::from("total"), 5)));
+        assert_eq!(result.len(), 3);
+        assert_eq!(Vec::<u32>::new().into_iter().append_summary(0, |sum, n| sum + n, |sum| sum).next(), None);
+    }
+}
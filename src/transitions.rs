@@ -0,0 +1,103 @@
+use std::iter::{Fuse, Peekable};
+
+use Position;
+
+/// A transition between two consecutive states.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Transition<S> {
+    pub from: S,
+    pub to: S,
+}
+
+/// Extension trait for iterators of states which adds the
+/// `transitions_with_position` method.
+pub trait TransitionsWithPosition: Iterator + Sized where Self::Item: Clone {
+    /// Yield a tuple of `(Position, Transition)` for each pair of consecutive
+    /// states, so that entry actions can be run on the first transition and
+    /// exit actions on the last one. A sequence of fewer than two states has no
+    /// transitions.
+    fn transitions_with_position(self) -> Transitions<Self, fn(&Self::Item)> {
+        Transitions { iter: self.fuse().peekable(), from: None, started: false, did_iter: false, finalize: None }
+    }
+}
+
+impl<I> TransitionsWithPosition for I where I: Iterator, I::Item: Clone {}
+
+/// An iterator adapter that yields positioned transitions between states,
+/// created by `TransitionsWithPosition::transitions_with_position`.
+pub struct Transitions<I, F> where I: Iterator {
+    iter: Peekable<Fuse<I>>,
+    from: Option<I::Item>,
+    started: bool,
+    did_iter: bool,
+    finalize: Option<F>,
+}
+
+impl<I, F> Transitions<I, F> where I: Iterator {
+    /// Call the given closure with the final state once the iteration ends
+    /// after the last transition. It is not called if there were no
+    /// transitions.
+    pub fn finalize<G>(self, finalize: G) -> Transitions<I, G> where G: FnOnce(&I::Item) {
+        Transitions { iter: self.iter, from: self.from, started: self.started, did_iter: self.did_iter, finalize: Some(finalize) }
+    }
+}
+
+impl<I, F> Iterator for Transitions<I, F> where I: Iterator, I::Item: Clone, F: FnOnce(&I::Item) {
+    type Item = (Position, Transition<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            self.from = self.iter.next();
+        }
+
+        let to = match self.iter.next() {
+            Some(to) => to,
+            None => {
+                if let (true, Some(state), Some(finalize)) = (self.did_iter, self.from.as_ref(), self.finalize.take()) {
+                    finalize(state);
+                }
+                return None;
+            }
+        };
+        let from = self.from.replace(to.clone())?;
+
+        let is_first = !self.did_iter;
+        let is_last = self.iter.peek().is_none();
+        self.did_iter = true;
+        Some((Position::from_flags(is_first, is_last), Transition { from, to }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use {Position, Transition, TransitionsWithPosition};
+
+    #[test]
+    fn it_marks_initial_and_final_transitions() {
+        let finalized = Cell::new(None);
+
+        let result: Vec<_> = vec!["idle", "running", "done"].into_iter()
+            .transitions_with_position()
+            .finalize(|state| finalized.set(Some(*state)))
+            .collect();
+
+        assert_eq!(result, vec![
+            (Position::First, Transition { from: "idle", to: "running" }),
+            (Position::Last, Transition { from: "running", to: "done" }),
+        ]);
+        assert_eq!(finalized.get(), Some("done"));
+    }
+
+    #[test]
+    fn it_has_no_transitions_for_a_single_state() {
+        let finalized = Cell::new(false);
+
+        let count = Some("idle").into_iter().transitions_with_position().finalize(|_| finalized.set(true)).count();
+
+        assert_eq!(count, 0);
+        assert!(!finalized.get());
+        assert_eq!(vec![1, 2].into_iter().transitions_with_position().next(), Some((Position::Only, Transition { from: 1, to: 2 })));
+    }
+}
@@ -0,0 +1,474 @@
+use std::fmt;
+use std::iter::{self, Once};
+use std::mem;
+use std::vec;
+
+use {ExactPositionIterator, Position, PositionIterator, WithPosition};
+
+/// Traverse the tree below `root` depth first, yielding a tuple of
+/// `(depth, Position, node)` for each node, where the position is the
+/// position of the node among its siblings. The root has depth zero and is the
+/// only node at that depth.
+///
+/// ```
+/// use with_position::{dfs_with_position, Position};
+///
+/// let nodes: Vec<_> = dfs_with_position(1, |&n| if n < 3 { vec![n * 2, n * 2 + 1] } else { vec![] }).collect();
+///
+/// assert_eq!(nodes, vec![
+///     (0, Position::Only, 1),
+///     (1, Position::First, 2),
+///     (2, Position::First, 4),
+///     (2, Position::Last, 5),
+///     (1, Position::Last, 3),
+/// ]);
+/// ```
+pub fn dfs_with_position<T, F, C>(root: T, children: F) -> DfsWithPosition<Once<T>, F, C>
+    where F: FnMut(&T) -> C, C: IntoIterator<Item = T>
+{
+    dfs_forest_with_position(iter::once(root), children)
+}
+
+/// Traverse a forest depth first, like `dfs_with_position`, where the roots
+/// have depth zero and are positioned among each other.
+pub fn dfs_forest_with_position<I, F, C>(roots: I, children: F) -> DfsWithPosition<I::IntoIter, F, C>
+    where I: IntoIterator, F: FnMut(&I::Item) -> C, C: IntoIterator<Item = I::Item>
+{
+    DfsWithPosition { roots: roots.into_iter().with_position(), stack: Vec::new(), children }
+}
+
+/// An iterator over the nodes of a tree in depth first order, created by
+/// `dfs_with_position` and `dfs_forest_with_position`.
+pub struct DfsWithPosition<I, F, C> where I: Iterator, C: IntoIterator {
+    roots: PositionIterator<I>,
+    stack: Vec<(Position, PositionIterator<C::IntoIter>)>,
+    children: F,
+}
+
+impl<I, F, C> Iterator for DfsWithPosition<I, F, C>
+    where I: Iterator, F: FnMut(&I::Item) -> C, C: IntoIterator<Item = I::Item>
+{
+    type Item = (usize, Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let depth = self.stack.len();
+            let next = match self.stack.last_mut() {
+                Some(&mut (_, ref mut siblings)) => siblings.next(),
+                None => Some(self.roots.next()?),
+            };
+            match next {
+                Some((position, node)) => {
+                    let mut children = (self.children)(&node).into_iter().with_position();
+                    if children.iter.peek().is_some() {
+                        self.stack.push((position, children));
+                    }
+                    return Some((depth, position, node));
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+impl<I, F, C> DfsWithPosition<I, F, C>
+    where I: Iterator, F: FnMut(&I::Item) -> C, C: IntoIterator<Item = I::Item>
+{
+    /// Yield a tuple of `(OutlineNumber, Position, node)` for each node, where
+    /// the outline number is the hierarchical number of the node, such as
+    /// `1.2`, as used in tables of contents, and the position is the position
+    /// of the node among its siblings.
+    ///
+    /// ```
+    /// use with_position::{dfs_forest_with_position, Position};
+    ///
+    /// let chapters = vec![("Intro", vec![]), ("Usage", vec!["Install", "Run"])];
+    /// let toc: Vec<_> = dfs_forest_with_position(chapters, |&(_, ref sections)| {
+    ///     sections.iter().map(|&section| (section, vec![])).collect::<Vec<_>>()
+    /// }).outline().map(|(number, _, (title, _))| format!("{} {}", number, title)).collect();
+    ///
+    /// assert_eq!(toc, vec!["1 Intro", "2 Usage", "2.1 Install", "2.2 Run"]);
+    /// ```
+    pub fn outline(self) -> Outline<I, F, C> {
+        Outline { dfs: self, counters: Vec::new() }
+    }
+
+    /// Yield a tuple of `(last_at_depth, Position, node)` for each node, where
+    /// `last_at_depth[d]` tells whether the ancestor of the node at depth `d`
+    /// is the last among its siblings. The last element is the flag for the
+    /// node itself, so the vector has one element per level down to the node.
+    /// This is what is needed to draw the lines of a tree.
+    pub fn with_ancestor_flags(self) -> AncestorFlags<I, F, C> {
+        AncestorFlags { dfs: self, flags: Vec::new() }
+    }
+
+    /// Yield a tuple of `(prefix, node)` for each node, where the prefix is the
+    /// string of tree drawing characters which goes before the node when the
+    /// tree is printed with one node per line. Nodes at depth zero have an
+    /// empty prefix.
+    ///
+    /// ```
+    /// use with_position::{dfs_with_position, TreeCharset};
+    ///
+    /// let lines: Vec<_> = dfs_with_position(1, |&n| if n < 3 { vec![n * 2, n * 2 + 1] } else { vec![] })
+    ///     .tree_prefix(TreeCharset::UNICODE)
+    ///     .map(|(prefix, node)| format!("{}{}", prefix, node))
+    ///     .collect();
+    ///
+    /// assert_eq!(lines, vec!["1", "├── 2", "│   ├── 4", "│   └── 5", "└── 3"]);
+    /// ```
+    pub fn tree_prefix(self, charset: TreeCharset) -> TreePrefixes<I, F, C> {
+        TreePrefixes { flags: self.with_ancestor_flags(), charset }
+    }
+
+    /// Turn the traversal into a flat stream of events, where nodes with
+    /// children are entered and later left again, and nodes without children
+    /// are leaves. Each event carries the position of the node among its
+    /// siblings, which suits visitors which open and close scopes, such as
+    /// serializers and pretty printers. If the traversal has already been
+    /// started, the events continue where it left off, and each node whose
+    /// children were pending is left once they have been yielded.
+    ///
+    /// ```
+    /// use with_position::{dfs_with_position, Position, TreeEvent};
+    ///
+    /// let events: Vec<_> = dfs_with_position(1, |&n| if n == 1 { vec![2, 3] } else { vec![] }).events().collect();
+    ///
+    /// assert_eq!(events, vec![
+    ///     TreeEvent::Enter(1, Position::Only),
+    ///     TreeEvent::Leaf(2, Position::First),
+    ///     TreeEvent::Leaf(3, Position::Last),
+    ///     TreeEvent::Leave(Position::Only),
+    /// ]);
+    /// ```
+    pub fn events(self) -> TreeEvents<I, F, C> {
+        TreeEvents { roots: self.roots, stack: self.stack, children: self.children }
+    }
+}
+
+/// An event in the traversal of a tree, as yielded by `TreeEvents`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TreeEvent<T> {
+    /// A node with children, which are yielded next.
+    Enter(T, Position),
+    /// A node without children.
+    Leaf(T, Position),
+    /// The end of the children of the last entered node which has not been
+    /// left yet, with the position of that node.
+    Leave(Position),
+}
+
+/// An iterator over the events of a depth first traversal of a tree, created
+/// by `DfsWithPosition::events`.
+pub struct TreeEvents<I, F, C> where I: Iterator, C: IntoIterator {
+    roots: PositionIterator<I>,
+    stack: Vec<(Position, PositionIterator<C::IntoIter>)>,
+    children: F,
+}
+
+impl<I, F, C> Iterator for TreeEvents<I, F, C>
+    where I: Iterator, F: FnMut(&I::Item) -> C, C: IntoIterator<Item = I::Item>
+{
+    type Item = TreeEvent<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = match self.stack.last_mut() {
+            Some(&mut (_, ref mut siblings)) => siblings.next(),
+            None => Some(self.roots.next()?),
+        };
+        let (position, node) = match next {
+            Some(next) => next,
+            None => return self.stack.pop().map(|(position, _)| TreeEvent::Leave(position)),
+        };
+        let mut children = (self.children)(&node).into_iter().with_position();
+        if children.iter.peek().is_some() {
+            self.stack.push((position, children));
+            Some(TreeEvent::Enter(node, position))
+        } else {
+            Some(TreeEvent::Leaf(node, position))
+        }
+    }
+}
+
+/// The strings used to draw a tree by `DfsWithPosition::tree_prefix`. All of
+/// them should have the same width.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TreeCharset {
+    /// Drawn for an ancestor which has more siblings below it.
+    pub vertical: &'static str,
+    /// Drawn for an ancestor which is the last among its siblings.
+    pub blank: &'static str,
+    /// Drawn before a node which has more siblings below it.
+    pub branch: &'static str,
+    /// Drawn before a node which is the last among its siblings.
+    pub last_branch: &'static str,
+}
+
+impl TreeCharset {
+    /// Lines drawn with `|`, `-` and `` ` ``.
+    pub const ASCII: TreeCharset = TreeCharset { vertical: "|   ", blank: "    ", branch: "|-- ", last_branch: "`-- " };
+
+    /// Lines drawn with Unicode box drawing characters, as `cargo tree` does.
+    pub const UNICODE: TreeCharset = TreeCharset { vertical: "│   ", blank: "    ", branch: "├── ", last_branch: "└── " };
+}
+
+impl Default for TreeCharset {
+    fn default() -> TreeCharset {
+        TreeCharset::UNICODE
+    }
+}
+
+/// An iterator over the nodes of a tree together with the prefixes for drawing
+/// it, created by `DfsWithPosition::tree_prefix`.
+pub struct TreePrefixes<I, F, C> where I: Iterator, C: IntoIterator {
+    flags: AncestorFlags<I, F, C>,
+    charset: TreeCharset,
+}
+
+impl<I, F, C> Iterator for TreePrefixes<I, F, C>
+    where I: Iterator, F: FnMut(&I::Item) -> C, C: IntoIterator<Item = I::Item>
+{
+    type Item = (String, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (flags, _, node) = self.flags.next()?;
+        let mut prefix = String::new();
+        for (position, &is_last) in flags.iter().with_position().skip(1) {
+            prefix.push_str(match (position.is_last(), is_last) {
+                (false, false) => self.charset.vertical,
+                (false, true) => self.charset.blank,
+                (true, false) => self.charset.branch,
+                (true, true) => self.charset.last_branch,
+            });
+        }
+        Some((prefix, node))
+    }
+}
+
+/// An iterator over the nodes of a tree together with flags telling which of
+/// their ancestors are the last among their siblings, created by
+/// `DfsWithPosition::with_ancestor_flags`.
+pub struct AncestorFlags<I, F, C> where I: Iterator, C: IntoIterator {
+    dfs: DfsWithPosition<I, F, C>,
+    flags: Vec<bool>,
+}
+
+impl<I, F, C> Iterator for AncestorFlags<I, F, C>
+    where I: Iterator, F: FnMut(&I::Item) -> C, C: IntoIterator<Item = I::Item>
+{
+    type Item = (Vec<bool>, Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, position, node) = self.dfs.next()?;
+        self.flags.truncate(depth);
+        self.flags.push(position.is_last());
+        Some((self.flags.clone(), position, node))
+    }
+}
+
+/// The hierarchical number of a node in an outline, with one component per
+/// level, counting from one. Displayed with the components separated by dots.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OutlineNumber(pub Vec<usize>);
+
+impl fmt::Display for OutlineNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (position, component) in self.0.iter().with_position() {
+            write!(f, "{}", component)?;
+            if !position.is_last() {
+                f.write_str(".")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An iterator over the nodes of a tree together with their outline numbers,
+/// created by `DfsWithPosition::outline`.
+pub struct Outline<I, F, C> where I: Iterator, C: IntoIterator {
+    dfs: DfsWithPosition<I, F, C>,
+    counters: Vec<usize>,
+}
+
+impl<I, F, C> Iterator for Outline<I, F, C>
+    where I: Iterator, F: FnMut(&I::Item) -> C, C: IntoIterator<Item = I::Item>
+{
+    type Item = (OutlineNumber, Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, position, node) = self.dfs.next()?;
+        self.counters.truncate(depth + 1);
+        if position.is_first() {
+            self.counters.truncate(depth);
+            self.counters.push(1);
+        } else if let Some(counter) = self.counters.last_mut() {
+            *counter += 1;
+        }
+        Some((OutlineNumber(self.counters.clone()), position, node))
+    }
+}
+
+/// Traverse the tree below `root` breadth first, level by level, yielding a
+/// tuple of `(depth, Position, is_last_level, node)` for each node, where the
+/// position is the position of the node within its level. Since knowing
+/// whether a level is the last one requires knowing whether it has any
+/// children, the children of a level are collected before its first node is
+/// yielded, so up to two levels are held in memory.
+///
+/// ```
+/// use with_position::{bfs_with_position, Position};
+///
+/// let nodes: Vec<_> = bfs_with_position(1, |&n| if n < 3 { vec![n * 2, n * 2 + 1] } else { vec![] }).collect();
+///
+/// assert_eq!(nodes, vec![
+///     (0, Position::Only, false, 1),
+///     (1, Position::First, false, 2),
+///     (1, Position::Last, false, 3),
+///     (2, Position::First, true, 4),
+///     (2, Position::Last, true, 5),
+/// ]);
+/// ```
+pub fn bfs_with_position<T, F, C>(root: T, children: F) -> BfsWithPosition<T, F>
+    where F: FnMut(&T) -> C, C: IntoIterator<Item = T>
+{
+    let mut bfs = BfsWithPosition { level: ExactPositionIterator::new(Vec::new().into_iter()), next: vec![root], depth: 0, children };
+    bfs.advance_level();
+    bfs
+}
+
+/// An iterator over the nodes of a tree in breadth first order, created by
+/// `bfs_with_position`.
+pub struct BfsWithPosition<T, F> {
+    level: ExactPositionIterator<vec::IntoIter<T>>,
+    next: Vec<T>,
+    depth: usize,
+    children: F,
+}
+
+impl<T, F, C> BfsWithPosition<T, F> where F: FnMut(&T) -> C, C: IntoIterator<Item = T> {
+    fn advance_level(&mut self) {
+        let level = mem::take(&mut self.next);
+        self.next = level.iter().flat_map(&mut self.children).collect();
+        self.level = ExactPositionIterator::new(level.into_iter());
+    }
+}
+
+impl<T, F, C> Iterator for BfsWithPosition<T, F> where F: FnMut(&T) -> C, C: IntoIterator<Item = T> {
+    type Item = (usize, Position, bool, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((position, node)) = self.level.next() {
+                return Some((self.depth, position, self.next.is_empty(), node));
+            }
+            if self.next.is_empty() {
+                return None;
+            }
+            self.advance_level();
+            self.depth += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use {bfs_with_position, dfs_forest_with_position, dfs_with_position, OutlineNumber, Position, TreeCharset, TreeEvent};
+
+    #[test]
+    fn it_traverses_a_tree_depth_first() {
+        let tree: HashMap<&str, Vec<&str>> = vec![("src", vec!["lib.rs", "tree"]), ("tree", vec!["mod.rs"])].into_iter().collect();
+
+        let nodes: Vec<_> = dfs_with_position("src", |node| tree.get(node).cloned().unwrap_or_default()).collect();
+
+        assert_eq!(nodes, vec![
+            (0, Position::Only, "src"),
+            (1, Position::First, "lib.rs"),
+            (1, Position::Last, "tree"),
+            (2, Position::Only, "mod.rs"),
+        ]);
+    }
+
+    #[test]
+    fn it_positions_the_roots_of_a_forest() {
+        let nodes: Vec<_> = dfs_forest_with_position(vec![1, 2], |_| Vec::new()).collect();
+
+        assert_eq!(nodes, vec![(0, Position::First, 1), (0, Position::Last, 2)]);
+    }
+
+    #[test]
+    fn it_marks_the_last_level_of_a_single_node_tree() {
+        let nodes: Vec<_> = bfs_with_position("root", |_| Vec::new()).collect();
+
+        assert_eq!(nodes, vec![(0, Position::Only, true, "root")]);
+    }
+
+    #[test]
+    fn it_resets_outline_counters_per_level() {
+        let numbers: Vec<_> = dfs_with_position(1, |&n| if n < 4 { vec![n * 2, n * 2 + 1] } else { vec![] })
+            .outline()
+            .map(|(number, _, _)| number.to_string())
+            .collect();
+
+        assert_eq!(numbers, vec!["1", "1.1", "1.1.1", "1.1.2", "1.2", "1.2.1", "1.2.2"]);
+        assert_eq!(OutlineNumber(vec![]).to_string(), "");
+    }
+
+    #[test]
+    fn it_yields_ancestor_last_flags() {
+        let flags: Vec<_> = dfs_with_position(1, |&n| if n < 3 { vec![n * 2, n * 2 + 1] } else { vec![] })
+            .with_ancestor_flags()
+            .map(|(flags, _, node)| (node, flags))
+            .collect();
+
+        assert_eq!(flags, vec![
+            (1, vec![true]),
+            (2, vec![true, false]),
+            (4, vec![true, false, false]),
+            (5, vec![true, false, true]),
+            (3, vec![true, true]),
+        ]);
+    }
+
+    #[test]
+    fn it_draws_tree_prefixes_with_ascii_characters() {
+        let prefixes: Vec<_> = dfs_forest_with_position(vec![1, 2], |&n| if n == 1 { vec![3] } else { vec![] })
+            .tree_prefix(TreeCharset::ASCII)
+            .map(|(prefix, _)| prefix)
+            .collect();
+
+        assert_eq!(prefixes, vec!["", "`-- ", ""]);
+    }
+
+    #[test]
+    fn it_leaves_nested_nodes_in_order() {
+        let events: Vec<_> = dfs_forest_with_position(vec![1, 4], |&n| if n < 3 { vec![n + 1] } else { vec![] }).events().collect();
+
+        assert_eq!(events, vec![
+            TreeEvent::Enter(1, Position::First),
+            TreeEvent::Enter(2, Position::Only),
+            TreeEvent::Leaf(3, Position::Only),
+            TreeEvent::Leave(Position::Only),
+            TreeEvent::Leave(Position::First),
+            TreeEvent::Leaf(4, Position::Last),
+        ]);
+    }
+
+    #[test]
+    fn it_continues_a_started_traversal_with_events() {
+        let mut dfs = dfs_with_position(1, |&n| if n < 3 { vec![n * 2, n * 2 + 1] } else { vec![] });
+        dfs.next();
+        dfs.next();
+        let events: Vec<_> = dfs.events().collect();
+
+        assert_eq!(events, vec![
+            TreeEvent::Leaf(4, Position::First),
+            TreeEvent::Leaf(5, Position::Last),
+            TreeEvent::Leave(Position::First),
+            TreeEvent::Leaf(3, Position::Last),
+            TreeEvent::Leave(Position::Only),
+        ]);
+    }
+}
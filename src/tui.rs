@@ -0,0 +1,134 @@
+use ratatui::style::Style;
+use ratatui::text::{Line, Text};
+use ratatui::widgets::ListItem;
+
+use {Position, WithPosition};
+
+/// Styles and separators for turning items into the `ListItem`s of a
+/// `ratatui` list, with distinct styles for the first row, such as a header,
+/// and the last row, such as a summary. All styles are plain by default.
+///
+/// ```
+/// extern crate ratatui;
+/// # extern crate with_position;
+/// use ratatui::style::{Style, Stylize};
+/// use ratatui::text::Line;
+/// use with_position::ListItemStyles;
+///
+/// # fn main() {
+/// let styles = ListItemStyles::new().first(Style::new().bold()).separator(Line::from("--"));
+/// let items = styles.list_items(vec!["Name", "a", "b"]);
+///
+/// assert_eq!(items.len(), 5);
+/// assert!(ListItemStyles::new().list_items(Vec::<&str>::new()).is_empty());
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ListItemStyles<'a> {
+    first: Style,
+    middle: Style,
+    last: Style,
+    only: Style,
+    separator: Option<Line<'a>>,
+    empty: Option<Text<'a>>,
+}
+
+impl<'a> ListItemStyles<'a> {
+    pub fn new() -> ListItemStyles<'a> {
+        ListItemStyles::default()
+    }
+
+    /// Set the style of the first row, when there is more than one row.
+    pub fn first(mut self, style: Style) -> ListItemStyles<'a> {
+        self.first = style;
+        self
+    }
+
+    /// Set the style of every row between the first and the last one.
+    pub fn middle(mut self, style: Style) -> ListItemStyles<'a> {
+        self.middle = style;
+        self
+    }
+
+    /// Set the style of the last row, when there is more than one row.
+    pub fn last(mut self, style: Style) -> ListItemStyles<'a> {
+        self.last = style;
+        self
+    }
+
+    /// Set the style of a row which is both the first and the last one.
+    pub fn only(mut self, style: Style) -> ListItemStyles<'a> {
+        self.only = style;
+        self
+    }
+
+    /// Insert a separator row between every two rows, but not before the
+    /// first or after the last one.
+    pub fn separator(mut self, separator: Line<'a>) -> ListItemStyles<'a> {
+        self.separator = Some(separator);
+        self
+    }
+
+    /// Show a placeholder row, such as "No results", when there are no rows.
+    /// Without a placeholder an empty list stays empty.
+    pub fn empty(mut self, placeholder: Text<'a>) -> ListItemStyles<'a> {
+        self.empty = Some(placeholder);
+        self
+    }
+
+    /// Return the style of a row at the position.
+    pub fn style(&self, position: Position) -> Style {
+        match position {
+            Position::First => self.first,
+            Position::Middle => self.middle,
+            Position::Last => self.last,
+            Position::Only => self.only,
+        }
+    }
+
+    /// Turn the rows into list items, styled by their position and with the
+    /// separators between them.
+    pub fn list_items<I>(&self, rows: I) -> Vec<ListItem<'a>> where I: IntoIterator, I::Item: Into<Text<'a>> {
+        let mut items = Vec::new();
+        for (position, row) in rows.into_iter().with_position() {
+            items.push(ListItem::new(row).style(self.style(position)));
+            match self.separator {
+                Some(ref separator) if !position.is_last() => items.push(ListItem::new(separator.clone())),
+                _ => {}
+            }
+        }
+        if items.is_empty() {
+            items.extend(self.empty.clone().map(ListItem::new));
+        }
+        items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Style, Stylize};
+    use ratatui::text::{Line, Text};
+    use ratatui::widgets::ListItem;
+    use ListItemStyles;
+
+    #[test]
+    fn it_styles_rows_by_position_with_separators() {
+        let bold = Style::new().bold();
+        let dim = Style::new().dim();
+        let styles = ListItemStyles::new().first(bold).last(dim).separator(Line::from("-"));
+
+        assert_eq!(styles.list_items(vec!["head", "total"]), vec![
+            ListItem::new("head").style(bold),
+            ListItem::new("-"),
+            ListItem::new("total").style(dim),
+        ]);
+    }
+
+    #[test]
+    fn it_shows_the_placeholder_for_an_empty_list() {
+        let styles = ListItemStyles::new().separator(Line::from("-")).empty(Text::from("No results"));
+
+        assert_eq!(styles.list_items(Vec::<&str>::new()), vec![ListItem::new("No results")]);
+        assert_eq!(styles.list_items(vec!["a"]), vec![ListItem::new("a")]);
+    }
+}
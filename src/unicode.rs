@@ -0,0 +1,44 @@
+use unicode_segmentation::{Graphemes, UnicodeSegmentation, UnicodeWords};
+
+use {PositionIterator, WithPosition};
+
+/// Extension trait for string slices which adds positioned iteration over
+/// user-visible units of text, as defined by unicode text segmentation.
+pub trait UnicodeWithPosition {
+    /// Yield a tuple of `(Position, grapheme)` for each extended grapheme
+    /// cluster in the string.
+    fn graphemes_with_position(&self) -> PositionIterator<Graphemes<'_>>;
+
+    /// Yield a tuple of `(Position, word)` for each word in the string,
+    /// skipping whitespace and punctuation.
+    fn words_with_position(&self) -> PositionIterator<UnicodeWords<'_>>;
+}
+
+impl UnicodeWithPosition for str {
+    fn graphemes_with_position(&self) -> PositionIterator<Graphemes<'_>> {
+        self.graphemes(true).with_position()
+    }
+
+    fn words_with_position(&self) -> PositionIterator<UnicodeWords<'_>> {
+        self.unicode_words().with_position()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Position, UnicodeWithPosition};
+
+    #[test]
+    fn it_marks_positions_of_graphemes() {
+        let result: Vec<_> = "ae\u{301}o".graphemes_with_position().collect();
+
+        assert_eq!(result, vec![(Position::First, "a"), (Position::Middle, "e\u{301}"), (Position::Last, "o")]);
+    }
+
+    #[test]
+    fn it_marks_positions_of_words() {
+        let result: Vec<_> = "Hello, big world!".words_with_position().collect();
+
+        assert_eq!(result, vec![(Position::First, "Hello"), (Position::Middle, "big"), (Position::Last, "world")]);
+    }
+}
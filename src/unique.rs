@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::iter::{Fuse, FusedIterator};
+
+use Position;
+
+/// An iterator adapter that yields the first occurrence of each item, labelled
+/// with its position among the unique items, created by
+/// `WithPosition::unique_with_position`.
+pub struct UniqueWithPosition<I> where I: Iterator {
+    iter: Fuse<I>,
+    seen: HashSet<I::Item>,
+    next: Option<I::Item>,
+    did_iter: bool,
+}
+
+impl<I> UniqueWithPosition<I> where I: Iterator, I::Item: Clone + Eq + Hash {
+    pub(crate) fn new(iter: I) -> UniqueWithPosition<I> {
+        UniqueWithPosition { iter: iter.fuse(), seen: HashSet::new(), next: None, did_iter: false }
+    }
+
+    fn find_next(&mut self) -> Option<I::Item> {
+        let seen = &mut self.seen;
+        self.iter.find(|item| seen.insert(item.clone()))
+    }
+}
+
+impl<I> Iterator for UniqueWithPosition<I> where I: Iterator, I::Item: Clone + Eq + Hash {
+    type Item = (Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = match self.next.take() {
+            Some(item) => item,
+            None => self.find_next()?,
+        };
+        self.next = self.find_next();
+
+        let is_first = !self.did_iter;
+        self.did_iter = true;
+        Some((Position::from_flags(is_first, self.next.is_none()), item))
+    }
+}
+
+impl<I> FusedIterator for UniqueWithPosition<I> where I: Iterator, I::Item: Clone + Eq + Hash {}
+
+/// An iterator adapter that drops consecutive items with the same key as the
+/// item before them, and labels the remaining items with their position among
+/// each other, created by `WithPosition::dedup_by_key_with_position`.
+pub struct DedupByKeyWithPosition<I, K, F> where I: Iterator {
+    iter: Fuse<I>,
+    key: F,
+    next: Option<(K, I::Item)>,
+    did_iter: bool,
+}
+
+impl<I, K, F> DedupByKeyWithPosition<I, K, F> where I: Iterator, K: PartialEq, F: FnMut(&I::Item) -> K {
+    pub(crate) fn new(iter: I, key: F) -> DedupByKeyWithPosition<I, K, F> {
+        DedupByKeyWithPosition { iter: iter.fuse(), key, next: None, did_iter: false }
+    }
+
+    fn find_next(&mut self, previous: &K) -> Option<(K, I::Item)> {
+        for item in &mut self.iter {
+            let key = (self.key)(&item);
+            if key != *previous {
+                return Some((key, item));
+            }
+        }
+        None
+    }
+}
+
+impl<I, K, F> Iterator for DedupByKeyWithPosition<I, K, F> where I: Iterator, K: PartialEq, F: FnMut(&I::Item) -> K {
+    type Item = (Position, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, item) = match self.next.take() {
+            Some(next) => next,
+            None => {
+                let item = self.iter.next()?;
+                ((self.key)(&item), item)
+            }
+        };
+        self.next = self.find_next(&key);
+
+        let is_first = !self.did_iter;
+        self.did_iter = true;
+        Some((Position::from_flags(is_first, self.next.is_none()), item))
+    }
+}
+
+impl<I, K, F> FusedIterator for DedupByKeyWithPosition<I, K, F> where I: Iterator, K: PartialEq, F: FnMut(&I::Item) -> K {}
+
+#[cfg(test)]
+mod tests {
+    use {Position, WithPosition};
+
+    #[test]
+    fn it_assigns_positions_over_unique_items() {
+        let result: Vec<_> = vec!["a", "b", "a", "c", "b", "c"].into_iter().unique_with_position().collect();
+
+        assert_eq!(result, vec![(Position::First, "a"), (Position::Middle, "b"), (Position::Last, "c")]);
+    }
+
+    #[test]
+    fn it_marks_a_repeated_single_item_as_only() {
+        let result: Vec<_> = vec![1, 1, 1].into_iter().unique_with_position().collect();
+
+        assert_eq!(result, vec![(Position::Only, 1)]);
+    }
+
+    #[test]
+    fn it_assigns_positions_over_items_left_after_dedup() {
+        let lines = vec!["a: 1", "a: 2", "b: 1", "a: 3", "a: 4"];
+        let result: Vec<_> = lines.into_iter().dedup_by_key_with_position(|line| &line[..1]).collect();
+
+        assert_eq!(result, vec![(Position::First, "a: 1"), (Position::Middle, "b: 1"), (Position::Last, "a: 3")]);
+    }
+}
@@ -0,0 +1,68 @@
+use std::ops::RangeBounds;
+use std::vec::Drain;
+
+use {ExactPositionIterator, Position};
+
+/// Extension trait for vectors which adds position aware versions of some of
+/// the in-place methods on `Vec`.
+pub trait VecWithPosition<T> {
+    /// Retain only the items for which the predicate returns true. The
+    /// predicate receives the position of the item in the vector before any
+    /// items were removed.
+    fn retain_with_position<F>(&mut self, f: F) where F: FnMut(Position, &T) -> bool;
+
+    /// Remove the given range from the vector, yielding a tuple of
+    /// `(Position, item)` for each removed item. Positions are relative to the
+    /// drained range, not to the whole vector.
+    fn drain_with_position<R>(&mut self, range: R) -> ExactPositionIterator<Drain<'_, T>> where R: RangeBounds<usize>;
+}
+
+impl<T> VecWithPosition<T> for Vec<T> {
+    fn retain_with_position<F>(&mut self, mut f: F) where F: FnMut(Position, &T) -> bool {
+        let len = self.len();
+        let mut index = 0;
+
+        self.retain(|item| {
+            let position = Position::from_index(index, len);
+            index += 1;
+            f(position, item)
+        });
+    }
+
+    fn drain_with_position<R>(&mut self, range: R) -> ExactPositionIterator<Drain<'_, T>> where R: RangeBounds<usize> {
+        ExactPositionIterator::new(self.drain(range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Position, VecWithPosition};
+
+    #[test]
+    fn it_retains_by_position() {
+        let mut samples = vec![1, 2, 3, 4, 5, 6];
+
+        samples.retain_with_position(|position, &sample| position != Position::Middle || sample % 2 == 0);
+
+        assert_eq!(samples, vec![1, 2, 4, 6]);
+    }
+
+    #[test]
+    fn it_retains_nothing_from_an_empty_vec() {
+        let mut samples: Vec<i32> = vec![];
+
+        samples.retain_with_position(|_, _| true);
+
+        assert!(samples.is_empty());
+    }
+
+    #[test]
+    fn it_drains_with_positions_relative_to_the_range() {
+        let mut buffer = vec![1, 2, 3, 4, 5];
+
+        let drained: Vec<_> = buffer.drain_with_position(1..4).collect();
+
+        assert_eq!(drained, vec![(Position::First, 2), (Position::Middle, 3), (Position::Last, 4)]);
+        assert_eq!(buffer, vec![1, 5]);
+    }
+}
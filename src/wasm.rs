@@ -0,0 +1,47 @@
+use js_sys::{Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+use {Position, WithPosition};
+
+/// An iterator which can be handed to JavaScript, following the JavaScript
+/// iterator protocol. Each call to `next` returns an object with `position`,
+/// `value` and `done` fields, where `position` is one of `"first"`,
+/// `"middle"`, `"last"` or `"only"`. Once the iterator is exhausted, objects
+/// with `done` set to `true` and no position or value are returned.
+///
+/// ```text
+/// const iter = rust.items();
+/// for (let item = iter.next(); !item.done; item = iter.next()) {
+///     console.log(item.position, item.value);
+/// }
+/// ```
+#[wasm_bindgen]
+pub struct JsPositionIterator {
+    iter: Box<dyn Iterator<Item = (Position, JsValue)>>,
+}
+
+impl JsPositionIterator {
+    pub fn new<I>(iter: I) -> JsPositionIterator where I: IntoIterator, I::IntoIter: 'static, I::Item: Into<JsValue> + 'static {
+        JsPositionIterator { iter: Box::new(iter.into_iter().map(Into::into).with_position()) }
+    }
+}
+
+#[wasm_bindgen]
+impl JsPositionIterator {
+    /// Return the next iterator result object.
+    #[wasm_bindgen(js_name = next)]
+    pub fn next_result(&mut self) -> Result<Object, JsValue> {
+        let result = Object::new();
+        match self.iter.next() {
+            Some((position, value)) => {
+                Reflect::set(&result, &JsValue::from_str("position"), &JsValue::from_str(position.as_str()))?;
+                Reflect::set(&result, &JsValue::from_str("value"), &value)?;
+                Reflect::set(&result, &JsValue::from_str("done"), &JsValue::FALSE)?;
+            }
+            None => {
+                Reflect::set(&result, &JsValue::from_str("done"), &JsValue::TRUE)?;
+            }
+        }
+        Ok(result)
+    }
+}
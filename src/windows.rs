@@ -0,0 +1,207 @@
+use std::iter::Fuse;
+
+use inline::InlineVec;
+use Position;
+
+/// A frame of samples yielded by `overlapping_windows_with_position`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Frame<T> {
+    samples: InlineVec<T>,
+    /// Whether the frame contains the first sample of the stream.
+    pub contains_start: bool,
+    /// Whether the frame contains the last sample of the stream, in which case
+    /// it may have been padded.
+    pub contains_end: bool,
+}
+
+impl<T> Frame<T> {
+    /// Return the samples of the frame, padded with default values past the
+    /// end of the stream.
+    pub fn samples(&self) -> &[T] {
+        &self.samples
+    }
+
+    /// Return the samples of the frame as a vector.
+    pub fn into_samples(self) -> Vec<T> {
+        self.samples.into_iter().collect()
+    }
+}
+
+/// Extension trait for iterators which adds the
+/// `overlapping_windows_with_position` method.
+pub trait OverlappingWindowsWithPosition: Iterator + Sized where Self::Item: Clone + Default {
+    /// Yield a tuple of `(Position, Frame)` for frames of `size` samples, where
+    /// each frame starts `hop` samples after the previous one. Frames are
+    /// yielded as long as they start within the stream, so when `hop` is
+    /// smaller than `size` several frames at the end may contain the end of the
+    /// stream.
+    ///
+    /// Panics if `size` or `hop` is zero.
+    fn overlapping_windows_with_position(self, size: usize, hop: usize) -> OverlappingWindows<Self> {
+        assert!(size > 0 && hop > 0, "size and hop must be greater than zero");
+        OverlappingWindows { iter: self.fuse(), buffer: InlineVec::new(), size, hop, did_iter: false }
+    }
+}
+
+impl<I> OverlappingWindowsWithPosition for I where I: Iterator, I::Item: Clone + Default {}
+
+/// An iterator adapter that yields overlapping frames with their position,
+/// created by `OverlappingWindowsWithPosition::overlapping_windows_with_position`.
+pub struct OverlappingWindows<I> where I: Iterator {
+    iter: Fuse<I>,
+    buffer: InlineVec<I::Item>,
+    size: usize,
+    hop: usize,
+    did_iter: bool,
+}
+
+impl<I> Iterator for OverlappingWindows<I> where I: Iterator, I::Item: Clone + Default {
+    type Item = (Position, Frame<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Read one sample past the end of this frame and the start of the next
+        // one, to know whether this frame contains the end and is the last.
+        let wanted = self.size.max(self.hop) + 1;
+        while self.buffer.len() < wanted {
+            match self.iter.next() {
+                Some(sample) => self.buffer.push(sample),
+                None => break,
+            }
+        }
+        if self.buffer.is_empty() {
+            return None;
+        }
+
+        let mut samples: InlineVec<_> = self.buffer.iter().take(self.size).cloned().collect();
+        let contains_end = self.buffer.len() <= self.size;
+        samples.resize(self.size, I::Item::default());
+
+        let is_first = !self.did_iter;
+        let is_last = self.buffer.len() <= self.hop;
+        self.did_iter = true;
+        let hop = self.hop.min(self.buffer.len());
+        self.buffer.drain(..hop);
+
+        let frame = Frame { samples, contains_start: is_first, contains_end };
+        Some((Position::from_flags(is_first, is_last), frame))
+    }
+}
+
+/// The phase of a rolling window, yielded by `RollingWithPosition::rolling`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum WindowPhase {
+    /// The window is still filling up after the start of the stream.
+    WarmUp,
+    /// The window holds the full number of items.
+    Full,
+    /// The window is emptying after the end of the stream.
+    Drain,
+}
+
+/// Extension trait for iterators which adds the `rolling` method.
+pub trait RollingWithPosition: Iterator + Sized {
+    /// Slide a window of up to `size` items over the stream, and yield a tuple
+    /// of `(WindowPhase, value)` with the value computed by the closure from
+    /// the items of the window, oldest first. The window grows one item at a
+    /// time from the start of the stream, and shrinks one item at a time after
+    /// its end, so partial windows at both ends can be told apart from full
+    /// ones.
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// ```
+    /// use with_position::{RollingWithPosition, WindowPhase};
+    ///
+    /// let sums: Vec<_> = (1..5).rolling(3, |window| window.iter().sum::<i32>()).collect();
+    ///
+    /// assert_eq!(sums, vec![
+    ///     (WindowPhase::WarmUp, 1),
+    ///     (WindowPhase::WarmUp, 3),
+    ///     (WindowPhase::Full, 6),
+    ///     (WindowPhase::Full, 9),
+    ///     (WindowPhase::Drain, 7),
+    ///     (WindowPhase::Drain, 4),
+    /// ]);
+    /// ```
+    fn rolling<F, U>(self, size: usize, f: F) -> Rolling<Self, F> where F: FnMut(&[Self::Item]) -> U {
+        assert!(size > 0, "size must be greater than zero");
+        Rolling { iter: self.fuse(), window: InlineVec::new(), size, f }
+    }
+}
+
+impl<I> RollingWithPosition for I where I: Iterator {}
+
+/// An iterator adapter that yields values computed from a rolling window with
+/// the phase of the window, created by `RollingWithPosition::rolling`.
+pub struct Rolling<I, F> where I: Iterator {
+    iter: Fuse<I>,
+    window: InlineVec<I::Item>,
+    size: usize,
+    f: F,
+}
+
+impl<I, F, U> Iterator for Rolling<I, F> where I: Iterator, F: FnMut(&[I::Item]) -> U {
+    type Item = (WindowPhase, U);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let phase = match self.iter.next() {
+            Some(item) => {
+                self.window.push(item);
+                if self.window.len() > self.size {
+                    self.window.remove(0);
+                }
+                if self.window.len() < self.size { WindowPhase::WarmUp } else { WindowPhase::Full }
+            }
+            None => {
+                if self.window.len() <= 1 {
+                    self.window.clear();
+                    return None;
+                }
+                self.window.remove(0);
+                WindowPhase::Drain
+            }
+        };
+        Some((phase, (self.f)(&self.window)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {OverlappingWindowsWithPosition, Position, RollingWithPosition, WindowPhase};
+
+    #[test]
+    fn it_yields_overlapping_frames_with_boundary_flags() {
+        let result: Vec<_> = (1..6).overlapping_windows_with_position(4, 2)
+            .map(|(position, frame)| (position, frame.samples().to_vec(), frame.contains_start, frame.contains_end))
+            .collect();
+
+        assert_eq!(result, vec![
+            (Position::First, vec![1, 2, 3, 4], true, false),
+            (Position::Middle, vec![3, 4, 5, 0], false, true),
+            (Position::Last, vec![5, 0, 0, 0], false, true),
+        ]);
+    }
+
+    #[test]
+    fn it_yields_a_single_padded_frame_for_a_short_stream() {
+        let result: Vec<_> = (1..3).overlapping_windows_with_position(4, 4).collect();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].0, Position::Only);
+        assert_eq!(result[0].1.samples().to_vec(), vec![1, 2, 0, 0]);
+        assert!(result[0].1.contains_start && result[0].1.contains_end);
+        assert_eq!((0..0).overlapping_windows_with_position(4, 2).count(), 0);
+    }
+
+    #[test]
+    fn it_never_fills_a_window_larger_than_the_stream() {
+        let result: Vec<_> = vec!["a", "b"].into_iter().rolling(3, |window| window.concat()).collect();
+
+        assert_eq!(result, vec![
+            (WindowPhase::WarmUp, String::from("a")),
+            (WindowPhase::WarmUp, String::from("ab")),
+            (WindowPhase::Drain, String::from("b")),
+        ]);
+        assert_eq!((0..0).rolling(2, |window| window.len()).next(), None);
+    }
+}
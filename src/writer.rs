@@ -0,0 +1,309 @@
+use std::fmt;
+use std::io;
+
+use separator::DisplaySeparator;
+use {Position, Separator};
+
+/// Writes positioned items as a delimited list, with an opening token before
+/// the first item, a separator between items and a closing token after the
+/// last one. This is the kind of list found in generated code, such as
+/// `(a, b, c)` or `[a, b, c,]`.
+///
+/// ```
+/// use with_position::{ListWriter, WithPosition};
+///
+/// let mut output = String::new();
+/// ListWriter::new("(", ", ", ")").write_to(&mut output, vec!["a", "b"].into_iter().with_position()).unwrap();
+///
+/// assert_eq!(output, "(a, b)");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct ListWriter<'a, S = &'a str> {
+    open: &'a str,
+    separator: S,
+    close: &'a str,
+    trailing_separator: bool,
+}
+
+impl<'a, S> ListWriter<'a, S> where S: Separator {
+    pub fn new(open: &'a str, separator: S, close: &'a str) -> ListWriter<'a, S> {
+        ListWriter { open, separator, close, trailing_separator: false }
+    }
+
+    /// Set whether the separator is also written after the last item, with
+    /// the position of the last item. It is never written for an empty list.
+    pub fn trailing_separator(mut self, trailing_separator: bool) -> ListWriter<'a, S> {
+        self.trailing_separator = trailing_separator;
+        self
+    }
+
+    /// Write the list to a `fmt::Write`, such as a `String` or a formatter.
+    pub fn write_to<W, I, T>(&self, out: &mut W, iter: I) -> fmt::Result
+        where W: fmt::Write + ?Sized, I: IntoIterator<Item = (Position, T)>, T: fmt::Display
+    {
+        out.write_str(self.open)?;
+        for (position, item) in iter {
+            if !position.is_first() {
+                write!(out, "{}", DisplaySeparator(&self.separator, position))?;
+            }
+            write!(out, "{}", item)?;
+            if self.trailing_separator && position.is_last() {
+                write!(out, "{}", DisplaySeparator(&self.separator, position))?;
+            }
+        }
+        out.write_str(self.close)
+    }
+
+    /// Write the list to an `io::Write`, such as a file or a socket.
+    pub fn write_io<W, I, T>(&self, out: &mut W, iter: I) -> io::Result<()>
+        where W: io::Write + ?Sized, I: IntoIterator<Item = (Position, T)>, T: fmt::Display
+    {
+        let mut adapter = IoAdapter { inner: out, error: Ok(()) };
+        match self.write_to(&mut adapter, iter) {
+            Ok(()) => Ok(()),
+            Err(_) => match adapter.error {
+                Err(error) => Err(error),
+                Ok(()) => Err(io::Error::other("formatter error")),
+            },
+        }
+    }
+}
+
+/// Lets the `fmt::Write` code of `ListWriter` write to an `io::Write`, keeping
+/// the I/O error which made a write fail, as `io::Write::write_fmt` does.
+struct IoAdapter<'a, W: ?Sized + 'a> {
+    inner: &'a mut W,
+    error: io::Result<()>,
+}
+
+impl<'a, W> fmt::Write for IoAdapter<'a, W> where W: io::Write + ?Sized {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Err(error);
+            fmt::Error
+        })
+    }
+}
+
+/// Writes positioned items as a JSON array to an `io::Write`, one item at a
+/// time, so the output never needs to be buffered as a whole. The opening
+/// bracket is written before the first item, commas between items and the
+/// closing bracket after the last one. Items are written with their `Display`
+/// implementation, so they must already be valid JSON values, such as numbers
+/// or JSON documents produced elsewhere.
+///
+/// An empty array is written by `finish` when no items have been written.
+pub struct JsonArrayWriter<W> {
+    writer: W,
+    started: bool,
+    closed: bool,
+}
+
+impl<W> JsonArrayWriter<W> where W: io::Write {
+    pub fn new(writer: W) -> JsonArrayWriter<W> {
+        JsonArrayWriter { writer, started: false, closed: false }
+    }
+
+    /// Write a single item, using its position to decide whether to open or
+    /// close the array around it.
+    pub fn write_element<T>(&mut self, position: Position, element: T) -> io::Result<()> where T: fmt::Display {
+        if !self.started {
+            self.writer.write_all(b"[")?;
+            self.started = true;
+        } else if position.is_first() || self.closed {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "array has already been written"));
+        }
+        write!(self.writer, "{}", element)?;
+        if position.is_last() {
+            self.writer.write_all(b"]")?;
+            self.closed = true;
+        } else {
+            self.writer.write_all(b",")?;
+        }
+        Ok(())
+    }
+
+    /// Write all items of a positioned iterator and finish the array.
+    pub fn write_positioned<I, T>(mut self, iter: I) -> io::Result<W>
+        where I: IntoIterator<Item = (Position, T)>, T: fmt::Display
+    {
+        for (position, element) in iter {
+            self.write_element(position, element)?;
+        }
+        self.finish()
+    }
+
+    /// Finish the array, writing `[]` if no items have been written, and
+    /// return the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if !self.started {
+            self.writer.write_all(b"[]")?;
+        } else if !self.closed {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "array was finished before its last item"));
+        }
+        Ok(self.writer)
+    }
+}
+
+/// Writes positioned records of fields as CSV to an `io::Write`. Fields are
+/// separated by the delimiter, with none after the last field of a record, and
+/// records are separated by the line terminator. Fields which contain the
+/// delimiter, a quote or a line break are quoted.
+///
+/// ```
+/// use with_position::{CsvWriter, WithPosition};
+///
+/// let rows = vec![vec!["name", "city"], vec!["Jonas", "Gothenburg, Sweden"]];
+/// let mut writer = CsvWriter::new(Vec::new());
+/// for (position, row) in rows.into_iter().with_position() {
+///     writer.write_record(position, row.into_iter().with_position()).unwrap();
+/// }
+///
+/// assert_eq!(writer.into_inner(), b"name,city\nJonas,\"Gothenburg, Sweden\"\n");
+/// ```
+pub struct CsvWriter<W> {
+    writer: W,
+    delimiter: u8,
+    terminator: &'static str,
+    trailing_newline: bool,
+}
+
+impl<W> CsvWriter<W> where W: io::Write {
+    pub fn new(writer: W) -> CsvWriter<W> {
+        CsvWriter { writer, delimiter: b',', terminator: "\n", trailing_newline: true }
+    }
+
+    /// Set the field delimiter, which is a comma by default.
+    pub fn delimiter(mut self, delimiter: u8) -> CsvWriter<W> {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set the line terminator written between records, which is `"\n"` by
+    /// default.
+    pub fn terminator(mut self, terminator: &'static str) -> CsvWriter<W> {
+        self.terminator = terminator;
+        self
+    }
+
+    /// Set whether the line terminator is also written after the last record,
+    /// which it is by default.
+    pub fn trailing_newline(mut self, trailing_newline: bool) -> CsvWriter<W> {
+        self.trailing_newline = trailing_newline;
+        self
+    }
+
+    /// Write a record, given its position among the records of the file and
+    /// a positioned iterator over its fields.
+    pub fn write_record<I, T>(&mut self, position: Position, fields: I) -> io::Result<()>
+        where I: IntoIterator<Item = (Position, T)>, T: fmt::Display
+    {
+        for (field_position, field) in fields {
+            self.write_field(&field.to_string())?;
+            if !field_position.is_last() {
+                self.writer.write_all(&[self.delimiter])?;
+            }
+        }
+        if self.trailing_newline || !position.is_last() {
+            self.writer.write_all(self.terminator.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_field(&mut self, field: &str) -> io::Result<()> {
+        let delimiter = self.delimiter;
+        let needs_quotes = field.bytes().any(|byte| byte == delimiter || byte == b'"' || byte == b'\n' || byte == b'\r');
+        if !needs_quotes {
+            return self.writer.write_all(field.as_bytes());
+        }
+        self.writer.write_all(b"\"")?;
+        self.writer.write_all(field.replace('"', "\"\"").as_bytes())?;
+        self.writer.write_all(b"\"")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::iter;
+    use {CsvWriter, JsonArrayWriter, ListWriter, Position, WithPosition};
+
+    #[test]
+    fn it_writes_a_list_with_a_trailing_separator() {
+        let writer = ListWriter::new("[\n", ",\n", "\n]").trailing_separator(true);
+        let mut output = String::new();
+
+        writer.write_to(&mut output, vec![1, 2].into_iter().with_position()).unwrap();
+
+        assert_eq!(output, "[\n1,\n2,\n\n]");
+    }
+
+    #[test]
+    fn it_writes_a_list_with_a_separator_depending_on_the_position() {
+        let writer = ListWriter::new("", |position: Position| if position.is_last() { " or " } else { ", " }, "?");
+        let mut output = Vec::new();
+
+        writer.write_io(&mut output, vec!["tea", "coffee", "water"].into_iter().with_position()).unwrap();
+
+        assert_eq!(output, b"tea, coffee or water?");
+    }
+
+    #[test]
+    fn it_writes_an_empty_list_to_an_io_writer() {
+        let writer = ListWriter::new("{", ", ", "}").trailing_separator(true);
+        let mut output = Vec::new();
+
+        writer.write_io(&mut output, iter::empty::<u8>().with_position()).unwrap();
+
+        assert_eq!(output, b"{}");
+    }
+
+    #[test]
+    fn it_returns_the_io_error_of_a_failed_write() {
+        let writer = ListWriter::new("[", ", ", "]");
+        let mut buffer = [0u8; 4];
+        let mut output = &mut buffer[..];
+
+        let error = writer.write_io(&mut output, vec![1, 2, 3].into_iter().with_position()).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+        assert_eq!(&buffer, b"[1, ");
+    }
+
+    #[test]
+    fn it_writes_a_json_array() {
+        let output = JsonArrayWriter::new(Vec::new()).write_positioned(vec![1, 2, 3].into_iter().with_position()).unwrap();
+
+        assert_eq!(output, b"[1,2,3]");
+    }
+
+    #[test]
+    fn it_writes_an_empty_json_array() {
+        let output = JsonArrayWriter::new(Vec::new()).write_positioned(iter::empty::<u8>().with_position()).unwrap();
+
+        assert_eq!(output, b"[]");
+    }
+
+    #[test]
+    fn it_rejects_finishing_a_json_array_before_its_last_item() {
+        let mut writer = JsonArrayWriter::new(Vec::new());
+        writer.write_element(Position::First, "{}").unwrap();
+
+        assert!(writer.finish().is_err());
+    }
+
+    #[test]
+    fn it_writes_csv_records_without_a_trailing_newline() {
+        let mut writer = CsvWriter::new(Vec::new()).delimiter(b';').terminator("\r\n").trailing_newline(false);
+
+        writer.write_record(Position::First, vec!["a", "b;c"].into_iter().with_position()).unwrap();
+        writer.write_record(Position::Last, vec!["say \"hi\""].into_iter().with_position()).unwrap();
+
+        assert_eq!(writer.into_inner(), b"a;\"b;c\"\r\n\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,102 @@
+use std::iter::{Fuse, FusedIterator};
+
+/// A pair of items from two sequences, or a single item from the longer one
+/// once the other has run out, yielded by `ZipLongest`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum EitherOrBoth<A, B> {
+    Both(A, B),
+    Left(A),
+    Right(B),
+}
+
+impl<A, B> EitherOrBoth<A, B> {
+    /// Return the item from the first sequence, if there is one.
+    pub fn left(self) -> Option<A> {
+        match self {
+            EitherOrBoth::Both(left, _) | EitherOrBoth::Left(left) => Some(left),
+            EitherOrBoth::Right(_) => None,
+        }
+    }
+
+    /// Return the item from the second sequence, if there is one.
+    pub fn right(self) -> Option<B> {
+        match self {
+            EitherOrBoth::Both(_, right) | EitherOrBoth::Right(right) => Some(right),
+            EitherOrBoth::Left(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "itertools")]
+impl<A, B> From<EitherOrBoth<A, B>> for ::itertools::EitherOrBoth<A, B> {
+    fn from(value: EitherOrBoth<A, B>) -> ::itertools::EitherOrBoth<A, B> {
+        match value {
+            EitherOrBoth::Both(left, right) => ::itertools::EitherOrBoth::Both(left, right),
+            EitherOrBoth::Left(left) => ::itertools::EitherOrBoth::Left(left),
+            EitherOrBoth::Right(right) => ::itertools::EitherOrBoth::Right(right),
+        }
+    }
+}
+
+/// An iterator which zips two iterators until both of them have run out,
+/// used by `WithPosition::zip_longest_with_position`.
+pub struct ZipLongest<A, B> {
+    a: Fuse<A>,
+    b: Fuse<B>,
+}
+
+impl<A, B> ZipLongest<A, B> where A: Iterator, B: Iterator {
+    pub(crate) fn new(a: A, b: B) -> ZipLongest<A, B> {
+        ZipLongest { a: a.fuse(), b: b.fuse() }
+    }
+}
+
+impl<A, B> Iterator for ZipLongest<A, B> where A: Iterator, B: Iterator {
+    type Item = EitherOrBoth<A::Item, B::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.a.next(), self.b.next()) {
+            (Some(left), Some(right)) => Some(EitherOrBoth::Both(left, right)),
+            (Some(left), None) => Some(EitherOrBoth::Left(left)),
+            (None, Some(right)) => Some(EitherOrBoth::Right(right)),
+            (None, None) => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a_upper), Some(b_upper)) => Some(a_upper.max(b_upper)),
+            _ => None,
+        };
+        (a_lower.max(b_lower), upper)
+    }
+}
+
+impl<A, B> FusedIterator for ZipLongest<A, B> where A: Iterator, B: Iterator {}
+
+#[cfg(test)]
+mod tests {
+    use {EitherOrBoth, Position, WithPosition};
+
+    #[test]
+    fn it_marks_the_end_of_the_longer_sequence_as_last() {
+        let result: Vec<_> = vec![1, 2, 3].into_iter().zip_longest_with_position(vec!["a"]).collect();
+
+        assert_eq!(result, vec![
+            (Position::First, EitherOrBoth::Both(1, "a")),
+            (Position::Middle, EitherOrBoth::Left(2)),
+            (Position::Last, EitherOrBoth::Left(3)),
+        ]);
+    }
+
+    #[test]
+    fn it_yields_items_of_the_second_sequence_when_the_first_runs_out() {
+        let result: Vec<_> = None::<u8>.into_iter().zip_longest_with_position(Some('x')).collect();
+
+        assert_eq!(result, vec![(Position::Only, EitherOrBoth::Right('x'))]);
+        assert_eq!(result[0].1.left(), None);
+        assert_eq!(result[0].1.right(), Some('x'));
+    }
+}